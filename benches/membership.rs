@@ -0,0 +1,74 @@
+//! Compares membership queries of scales and ranges with their precomputed forms.
+//!
+//! Run with `cargo bench --bench membership`.
+
+use std::{
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use pitches::{PitchClass, Range, Scale, ScaleType, PITCHES};
+
+const ROUNDS: u32 = 20_000;
+
+/// Time of `ROUNDS` runs of `f`, best of 5 attempts.
+fn measure(mut f: impl FnMut() -> usize) -> Duration {
+    (0..5)
+        .map(|_| {
+            let start = Instant::now();
+            for _ in 0..ROUNDS {
+                black_box(f());
+            }
+            start.elapsed()
+        })
+        .min()
+        .expect("attempts")
+}
+
+fn report(name: &str, slow: Duration, fast: Duration) {
+    let per_pitch =
+        |time: Duration| time.as_nanos() as f64 / (ROUNDS as usize * PITCHES.len()) as f64;
+    println!(
+        "{:<6} contains: {:>6.2} ns/pitch, contains_fast: {:>6.2} ns/pitch ({:.1}x)",
+        name,
+        per_pitch(slow),
+        per_pitch(fast),
+        slow.as_secs_f64() / fast.as_secs_f64()
+    );
+}
+
+fn main() {
+    let pitches = &PITCHES[..];
+
+    let scale = black_box(Scale::new(PitchClass::new(2), ScaleType::Dorian));
+    let slow = measure(|| {
+        pitches
+            .iter()
+            .filter(|pitch| scale.contains(**pitch))
+            .count()
+    });
+    let mask = scale.membership_mask();
+    let fast = measure(|| {
+        pitches
+            .iter()
+            .filter(|pitch| Scale::contains_fast(mask, **pitch))
+            .count()
+    });
+    report("scale", slow, fast);
+
+    let range = black_box(Range::new(PITCHES[28], PITCHES[76]).expect("range is correct"));
+    let slow = measure(|| {
+        pitches
+            .iter()
+            .filter(|pitch| range.contains(**pitch))
+            .count()
+    });
+    let bitset = range.membership_bitset();
+    let fast = measure(|| {
+        pitches
+            .iter()
+            .filter(|pitch| Range::contains_fast(bitset, **pitch))
+            .count()
+    });
+    report("range", slow, fast);
+}
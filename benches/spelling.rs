@@ -0,0 +1,59 @@
+//! Compares spelling pitches with `SpellingPolicy` directly and through warmed up `SpellingCache`.
+//!
+//! Run with `cargo bench --bench spelling`.
+
+use std::{
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use pitches::{KeySignature, SpellingCache, SpellingPolicy, PITCHES};
+
+const ROUNDS: u32 = 2_000;
+
+/// Time of `ROUNDS` runs of `f`, best of 5 attempts.
+fn measure(mut f: impl FnMut() -> usize) -> Duration {
+    (0..5)
+        .map(|_| {
+            let start = Instant::now();
+            for _ in 0..ROUNDS {
+                black_box(f());
+            }
+            start.elapsed()
+        })
+        .min()
+        .expect("attempts")
+}
+
+fn main() {
+    let per_pitch =
+        |time: Duration| time.as_nanos() as f64 / (ROUNDS as usize * PITCHES.len()) as f64;
+
+    for policy in [
+        SpellingPolicy::Sharps,
+        SpellingPolicy::ForKey(KeySignature::new(-3).expect("signature is correct")),
+    ] {
+        let policy = black_box(policy);
+        let uncached = measure(|| {
+            PITCHES
+                .iter()
+                .map(|pitch| policy.spell(*pitch).octave() as usize)
+                .sum()
+        });
+        let cache = SpellingCache::new(policy);
+        cache.warm_up();
+        let cached = measure(|| {
+            PITCHES
+                .iter()
+                .map(|pitch| cache.get(*pitch).octave() as usize)
+                .sum()
+        });
+        println!(
+            "{:<36} spell: {:>7.2} ns/pitch, cached: {:>5.2} ns/pitch ({:.0}x)",
+            format!("{:?}", policy),
+            per_pitch(uncached),
+            per_pitch(cached),
+            uncached.as_secs_f64() / cached.as_secs_f64()
+        );
+    }
+}
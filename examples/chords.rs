@@ -0,0 +1,47 @@
+//! Parses chord symbols given as arguments and prints their tones with frequencies.
+//!
+//! For example: `cargo run --example chords -- Cmaj7 F#m Bb7 Eø7`
+
+use pitches::{Chord, ChordQuality, ChordTable, Letter, Note, Octave, TableOptions};
+
+fn main() {
+    let symbols: Vec<String> = std::env::args().skip(1).collect();
+    if symbols.is_empty() {
+        eprintln!("usage: chords <chord symbol>...");
+        std::process::exit(1);
+    }
+
+    for symbol in symbols {
+        match parse_chord(&symbol) {
+            Some(chord) => {
+                println!("{}", chord);
+                print!(
+                    "{}",
+                    ChordTable::new(&chord, Octave::Fifth, TableOptions::default())
+                );
+                println!();
+            }
+            None => eprintln!("incorrect chord symbol: {}", symbol),
+        }
+    }
+}
+
+/// Parse chord symbol: root letter, optional accidental (`#`, `♯`, `b` or `♭`)
+/// and chord quality symbol (like `m7` or `°`).
+fn parse_chord(symbol: &str) -> Option<Chord> {
+    let mut characters = symbol.chars();
+    let letter = Letter::try_from(characters.next()?).ok()?;
+    let rest = characters.as_str();
+    let (alteration, quality) = if let Some(quality) = rest.strip_prefix(['#', '♯']) {
+        (1, quality)
+    } else if let Some(quality) = rest.strip_prefix(['b', '♭']) {
+        (-1, quality)
+    } else {
+        (0, rest)
+    };
+    let root = Note::try_from((char::from(letter), alteration, 4)).ok()?;
+    let quality = ChordQuality::ALL
+        .into_iter()
+        .find(|known| known.to_string() == quality)?;
+    Some(Chord::new(root, quality))
+}
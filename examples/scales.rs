@@ -0,0 +1,57 @@
+//! Prints table of scale with given root and type.
+//!
+//! For example: `cargo run --example scales -- D Dorian`
+
+use pitches::{Letter, Pitch, PitchClass, Scale, ScaleTable, ScaleType, TableOptions};
+
+fn main() {
+    let mut arguments = std::env::args().skip(1);
+    let (root, scale_type) = match (arguments.next(), arguments.next()) {
+        (Some(root), Some(scale_type)) => (root, scale_type),
+        _ => {
+            eprintln!("usage: scales <root, for example: F#> <scale type, for example: Major>");
+            std::process::exit(1);
+        }
+    };
+
+    let root = match parse_pitch_class(&root) {
+        Some(root) => root,
+        None => {
+            eprintln!("incorrect root: {}", root);
+            std::process::exit(1);
+        }
+    };
+    let scale_type = match ScaleType::ALL
+        .into_iter()
+        .find(|known| format!("{:?}", known).eq_ignore_ascii_case(&scale_type))
+    {
+        Some(scale_type) => scale_type,
+        None => {
+            let known: Vec<String> = ScaleType::ALL
+                .iter()
+                .map(|known| format!("{:?}", known))
+                .collect();
+            eprintln!("unknown scale type, known types: {}", known.join(", "));
+            std::process::exit(1);
+        }
+    };
+
+    let scale = Scale::new(root, scale_type);
+    print!("{}", ScaleTable::new(&scale, TableOptions::default()));
+}
+
+/// Parse pitch class name: letter and optional accidental (`#`, `♯`, `b` or `♭`).
+fn parse_pitch_class(name: &str) -> Option<PitchClass> {
+    let mut characters = name.chars();
+    let letter = Letter::try_from(characters.next()?).ok()?;
+    let alteration = match characters.as_str() {
+        "" => 0,
+        "#" | "♯" => 1,
+        "b" | "♭" => -1,
+        _ => return None,
+    };
+    let natural = Pitch::from(pitches::Note::try_from((char::from(letter), 0, 4)).ok()?);
+    Some(PitchClass::new(
+        (natural.number() as i8 + alteration).rem_euclid(12) as u8,
+    ))
+}
@@ -0,0 +1,46 @@
+//! Transposes notes read from standard input (separated by whitespace) by given interval.
+//!
+//! For example: `echo "C4 F#4 Bb3" | cargo run --example transpose -- M3`
+
+use std::io::Read;
+
+use pitches::{NamedInterval, Note, NoteStyle};
+
+fn main() {
+    let interval = match std::env::args().nth(1) {
+        Some(interval) => interval,
+        None => {
+            eprintln!("usage: transpose <interval, for example: M3 or P5>");
+            std::process::exit(1);
+        }
+    };
+    let interval = match NamedInterval::ALL
+        .into_iter()
+        .find(|known| known.to_string() == interval)
+    {
+        Some(interval) => interval,
+        None => {
+            let known: Vec<String> = NamedInterval::ALL.iter().map(ToString::to_string).collect();
+            eprintln!("unknown interval, known intervals: {}", known.join(", "));
+            std::process::exit(1);
+        }
+    };
+
+    let mut input = String::new();
+    std::io::stdin()
+        .read_to_string(&mut input)
+        .expect("failed to read standard input");
+    for name in input.split_whitespace() {
+        match name.parse::<Note>() {
+            Ok(note) => match note.apply_interval(interval) {
+                Some(transposed) => println!(
+                    "{} -> {}",
+                    note.format_in(NoteStyle::Ascii),
+                    transposed.format_in(NoteStyle::Ascii)
+                ),
+                None => println!("{} -> (can't be spelled)", note.format_in(NoteStyle::Ascii)),
+            },
+            Err(error) => eprintln!("incorrect note {}: {}", name, error),
+        }
+    }
+}
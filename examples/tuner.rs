@@ -0,0 +1,25 @@
+//! Simple tuner - reads frequencies (one per line, empty line meaning silence)
+//! from standard input and prints tuner readings.
+//!
+//! For example: `printf "440\n441.5\n\n466\n" | cargo run --example tuner`
+
+use std::io::BufRead;
+
+use pitches::{Note, PitchTracker};
+
+fn main() {
+    let mut tracker = PitchTracker::default();
+    for line in std::io::stdin().lock().lines() {
+        let line = line.expect("failed to read standard input");
+        let frequency = line.trim().parse::<f64>().ok();
+        match tracker.update(frequency) {
+            Some(reading) => println!(
+                "{:>4}  {:>8.2} Hz  {:>+6.1} cents",
+                Note::from(reading.pitch).to_string(),
+                reading.frequency,
+                reading.cents.value()
+            ),
+            None => println!("-"),
+        }
+    }
+}
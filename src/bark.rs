@@ -0,0 +1,46 @@
+//! Bark psychoacoustic scale of critical bands.
+
+/// Convert frequency (in Hz) to Bark scale - position on scale of 24 critical bands of hearing
+/// (for example: about 8.53 Bark for 1000 Hz, about 4.39 Bark for A₄).
+///
+/// Uses Traunmüller's formula, so it can be exactly [inverted](bark_to_frequency).
+/// Frequency must be non-negative, otherwise result is meaningless.
+#[must_use]
+pub fn frequency_to_bark(freq: f64) -> f64 {
+    26.81 * freq / (1960.0 + freq) - 0.53
+}
+
+/// Convert value on Bark scale to frequency (in Hz) - inverse of [frequency_to_bark].
+///
+/// Value must be between -0.53 (0 Hz) and 26.28 (exclusive), otherwise result is meaningless.
+#[must_use]
+pub fn bark_to_frequency(bark: f64) -> f64 {
+    1960.0 * (bark + 0.53) / (26.28 - bark)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PITCHES;
+
+    #[test]
+    fn known_bark_values() {
+        assert!((frequency_to_bark(1000.0) - 8.53).abs() < 0.005);
+        assert!((frequency_to_bark(4000.0) - 17.46).abs() < 0.005);
+        assert!((frequency_to_bark(440.0) - 4.39).abs() < 0.005);
+        assert_eq!(frequency_to_bark(0.0), -0.53);
+        assert_eq!(bark_to_frequency(-0.53), 0.0);
+    }
+
+    #[test]
+    fn bark_round_trips() {
+        for pitch in PITCHES.iter() {
+            let frequency = pitch.frequency();
+            let round_trip = bark_to_frequency(frequency_to_bark(frequency));
+            assert!((round_trip - frequency).abs() < 1e-9 * frequency);
+        }
+        for bark in [0.0, 1.0, 8.53, 20.0, 26.0] {
+            assert!((frequency_to_bark(bark_to_frequency(bark)) - bark).abs() < 1e-9);
+        }
+    }
+}
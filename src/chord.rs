@@ -0,0 +1,499 @@
+//! Chord representation.
+
+use std::fmt::Display;
+
+use crate::{Error, NamedInterval, Note, NoteArray, Pitch, PitchClass, PitchClassSet};
+
+/// Chord quality.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChordQuality {
+    /// Major triad.
+    Major,
+    /// Minor triad.
+    Minor,
+    /// Diminished triad.
+    Diminished,
+    /// Augmented triad.
+    Augmented,
+    /// Suspended second chord.
+    Suspended2,
+    /// Suspended fourth chord.
+    Suspended4,
+    /// Major sixth chord.
+    Major6,
+    /// Minor sixth chord.
+    Minor6,
+    /// Dominant seventh chord.
+    Dominant7,
+    /// Major seventh chord.
+    Major7,
+    /// Minor seventh chord.
+    Minor7,
+    /// Minor-major seventh chord.
+    MinorMajor7,
+    /// Half-diminished seventh chord.
+    HalfDiminished7,
+    /// Diminished seventh chord.
+    Diminished7,
+    /// Augmented-major seventh chord.
+    AugmentedMajor7,
+}
+
+impl ChordQuality {
+    /// All chord qualities.
+    pub const ALL: [ChordQuality; 15] = [
+        ChordQuality::Major,
+        ChordQuality::Minor,
+        ChordQuality::Diminished,
+        ChordQuality::Augmented,
+        ChordQuality::Suspended2,
+        ChordQuality::Suspended4,
+        ChordQuality::Major6,
+        ChordQuality::Minor6,
+        ChordQuality::Dominant7,
+        ChordQuality::Major7,
+        ChordQuality::Minor7,
+        ChordQuality::MinorMajor7,
+        ChordQuality::HalfDiminished7,
+        ChordQuality::Diminished7,
+        ChordQuality::AugmentedMajor7,
+    ];
+
+    /// Intervals between root and other chord tones in root position, in ascending order.
+    ///
+    /// Root itself is not included.
+    #[must_use]
+    pub fn intervals(&self) -> &'static [NamedInterval] {
+        use NamedInterval::*;
+        match self {
+            ChordQuality::Major => &[MajorThird, PerfectFifth],
+            ChordQuality::Minor => &[MinorThird, PerfectFifth],
+            ChordQuality::Diminished => &[MinorThird, DiminishedFifth],
+            ChordQuality::Augmented => &[MajorThird, AugmentedFifth],
+            ChordQuality::Suspended2 => &[MajorSecond, PerfectFifth],
+            ChordQuality::Suspended4 => &[PerfectFourth, PerfectFifth],
+            ChordQuality::Major6 => &[MajorThird, PerfectFifth, MajorSixth],
+            ChordQuality::Minor6 => &[MinorThird, PerfectFifth, MajorSixth],
+            ChordQuality::Dominant7 => &[MajorThird, PerfectFifth, MinorSeventh],
+            ChordQuality::Major7 => &[MajorThird, PerfectFifth, MajorSeventh],
+            ChordQuality::Minor7 => &[MinorThird, PerfectFifth, MinorSeventh],
+            ChordQuality::MinorMajor7 => &[MinorThird, PerfectFifth, MajorSeventh],
+            ChordQuality::HalfDiminished7 => &[MinorThird, DiminishedFifth, MinorSeventh],
+            ChordQuality::Diminished7 => &[MinorThird, DiminishedFifth, DiminishedSeventh],
+            ChordQuality::AugmentedMajor7 => &[MajorThird, AugmentedFifth, MajorSeventh],
+        }
+    }
+
+    /// Recognize chord quality from intervals between root and other chord tones.
+    ///
+    /// Order of intervals doesn't matter, root (unison) must not be included.
+    #[must_use]
+    pub fn from_intervals(intervals: &[NamedInterval]) -> Option<ChordQuality> {
+        let mut sorted = intervals.to_vec();
+        sorted.sort_by_key(|interval| (interval.semitones(), interval.number()));
+        ChordQuality::ALL
+            .into_iter()
+            .find(|quality| quality.intervals() == sorted)
+    }
+}
+
+impl Display for ChordQuality {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let symbol = match self {
+            ChordQuality::Major => "",
+            ChordQuality::Minor => "m",
+            ChordQuality::Diminished => "°",
+            ChordQuality::Augmented => "+",
+            ChordQuality::Suspended2 => "sus2",
+            ChordQuality::Suspended4 => "sus4",
+            ChordQuality::Major6 => "6",
+            ChordQuality::Minor6 => "m6",
+            ChordQuality::Dominant7 => "7",
+            ChordQuality::Major7 => "maj7",
+            ChordQuality::Minor7 => "m7",
+            ChordQuality::MinorMajor7 => "m(maj7)",
+            ChordQuality::HalfDiminished7 => "ø7",
+            ChordQuality::Diminished7 => "°7",
+            ChordQuality::AugmentedMajor7 => "+maj7",
+        };
+        write!(f, "{}", symbol)
+    }
+}
+
+/// Struct representing chord.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Chord {
+    root: Note,
+    intervals: Vec<NamedInterval>,
+}
+
+impl Chord {
+    /// Number of notes that fits every chord of known quality
+    /// and every tertian chord up to thirteenth - capacity to use with [Chord::note_array].
+    pub const MAX_NOTES: usize = 7;
+
+    /// Create new chord.
+    #[must_use]
+    pub fn new(root: Note, quality: ChordQuality) -> Self {
+        Chord {
+            root,
+            intervals: quality.intervals().to_vec(),
+        }
+    }
+
+    /// Create custom chord from intervals between root and other chord tones.
+    ///
+    /// Root (unison) should not be included in `intervals`.
+    #[must_use]
+    pub fn from_intervals(root: Note, intervals: &[NamedInterval]) -> Self {
+        Chord {
+            root,
+            intervals: intervals.to_vec(),
+        }
+    }
+
+    /// Get chord root.
+    #[must_use]
+    pub fn root(&self) -> Note {
+        self.root
+    }
+
+    /// Get chord quality (or `None` if chord isn't one of known qualities).
+    #[must_use]
+    pub fn quality(&self) -> Option<ChordQuality> {
+        ChordQuality::from_intervals(&self.intervals)
+    }
+
+    /// Get intervals between root and other chord tones in root position.
+    #[must_use]
+    pub fn root_position_intervals(&self) -> &[NamedInterval] {
+        &self.intervals
+    }
+
+    /// Get chord notes in root position, starting with root.
+    ///
+    /// Notes are spelled according to intervals (for example: C-E♭-G for C minor),
+    /// notes whose correct spelling can't be represented (like B𝄫 in C°7)
+    /// are replaced with their enharmonic equivalent.
+    pub fn notes(&self) -> Result<Vec<Note>, Error> {
+        self.notes_iter().collect()
+    }
+
+    /// Get [notes](Chord::notes) of the chord as fixed-capacity list (without allocation).
+    ///
+    /// Fails if chord has more than `N` notes, see [Chord::MAX_NOTES].
+    pub fn note_array<const N: usize>(&self) -> Result<NoteArray<N>, Error> {
+        let mut notes = NoteArray::new();
+        for note in self.notes_iter() {
+            notes.push(note?)?;
+        }
+        Ok(notes)
+    }
+
+    /// Iterator over [notes](Chord::notes) of the chord, without collecting them.
+    ///
+    /// Every note that can't be represented is an error.
+    pub fn notes_iter(&self) -> impl Iterator<Item = Result<Note, Error>> + '_ {
+        let root_index = self.root.chromatic_index();
+        std::iter::once(Ok(self.root)).chain(self.intervals.iter().map(move |interval| {
+            match self.root.apply_interval(*interval) {
+                Some(note) => Ok(note),
+                None => root_index.transpose(interval.semitones() as i16).try_into(),
+            }
+        }))
+    }
+
+    /// Get tritone substitute of the chord - dominant seventh chord with root a tritone above
+    /// (for example: D♭7 for G7).
+    ///
+    /// Root is spelled as diminished fifth above original root if possible,
+    /// otherwise as augmented fourth above it. If neither can be represented
+    /// (for example: above B₉), nearest representable enharmonic note is used.
+    #[must_use]
+    pub fn tritone_substitute(&self) -> Chord {
+        let index = self.root.chromatic_index();
+        let root = self
+            .root
+            .apply_interval(NamedInterval::DiminishedFifth)
+            .or_else(|| self.root.apply_interval(NamedInterval::AugmentedFourth))
+            .or_else(|| index.transpose(6).try_into().ok())
+            .unwrap_or_else(|| {
+                index
+                    .transpose(-6)
+                    .try_into()
+                    .expect("note a tritone above or below every note is representable")
+            });
+        Chord::new(root, ChordQuality::Dominant7)
+    }
+
+    /// Tension of the chord - sum of weights of interval classes between every pair of chord tones.
+    ///
+    /// | Interval class             | Weight |
+    /// |----------------------------|--------|
+    /// | unison/octave              | 0      |
+    /// | minor second/major seventh | 4      |
+    /// | major second/minor seventh | 2      |
+    /// | minor third/major sixth    | 0.5    |
+    /// | major third/minor sixth    | 0.5    |
+    /// | perfect fourth/fifth       | 0      |
+    /// | tritone                    | 3      |
+    ///
+    /// For example: 1 for major triad, 4 for diminished triad.
+    #[must_use]
+    pub fn tension(&self) -> f64 {
+        const WEIGHTS: [f64; 7] = [0.0, 4.0, 2.0, 0.5, 0.5, 0.0, 3.0];
+        let tones: Vec<u8> = std::iter::once(0)
+            .chain(self.intervals.iter().map(NamedInterval::semitones))
+            .collect();
+        tones
+            .iter()
+            .enumerate()
+            .flat_map(|(i, a)| tones[i + 1..].iter().map(move |b| (*a, *b)))
+            .map(|(a, b)| {
+                let semitones = a.abs_diff(b) % 12;
+                WEIGHTS[semitones.min(12 - semitones) as usize]
+            })
+            .sum()
+    }
+
+    /// Get pitch classes of chord tones.
+    #[must_use]
+    pub fn pitch_classes(&self) -> PitchClassSet {
+        let root_index = self.root.chromatic_index();
+        std::iter::once(root_index)
+            .chain(
+                self.intervals
+                    .iter()
+                    .map(|interval| root_index.transpose(interval.semitones() as i16)),
+            )
+            .map(|index| index.class())
+            .collect()
+    }
+
+    /// Identify chords formed by upper voices over bass, best interpretations first.
+    ///
+    /// Every chord of known quality containing exactly pitch classes of all pitches
+    /// (bass being one of its tones), or exactly pitch classes of upper voices
+    /// (bass being a [non-chord tone](BassRole::NonChordTone)), is a candidate.
+    /// Candidates are ranked by [role](BassRole) of bass - root first, then third, fifth,
+    /// other chord tone and non-chord tone - then by order of [qualities](ChordQuality::ALL)
+    /// (so triads go before seventh chords), then by root (starting with C).
+    /// For example: C major triad over E is C/E, C-E-G-A over C is C6 rather than Am7/C.
+    ///
+    /// Notes are spelled with sharps (the same way as converting [Pitch] to [Note]),
+    /// chord root is placed in the octave of its lowest occurrence.
+    #[must_use]
+    pub fn identify_with_bass(upper: &[Pitch], bass: Pitch) -> Vec<ChordCandidate> {
+        let upper_classes: PitchClassSet = upper.iter().map(|pitch| pitch.class()).collect();
+        let mut all_classes = upper_classes;
+        all_classes.insert(bass.class());
+        let lowest_of_class = |class: PitchClass| {
+            std::iter::once(&bass)
+                .chain(upper)
+                .filter(|pitch| pitch.class() == class)
+                .min()
+                .copied()
+        };
+
+        let mut candidates = vec![];
+        for (quality_rank, quality) in ChordQuality::ALL.into_iter().enumerate() {
+            for root_class in all_classes.iter() {
+                let root = match lowest_of_class(root_class) {
+                    Some(root) => Note::from(root),
+                    None => continue,
+                };
+                let chord = Chord::new(root, quality);
+                let classes = chord.pitch_classes();
+                let bass_role = if classes == all_classes {
+                    let semitones = (bass.number() + PitchClass::COUNT - root_class.number())
+                        % PitchClass::COUNT;
+                    match quality
+                        .intervals()
+                        .iter()
+                        .find(|interval| interval.semitones() == semitones)
+                        .map(|interval| interval.number())
+                    {
+                        None => BassRole::Root,
+                        Some(3) => BassRole::Third,
+                        Some(5) => BassRole::Fifth,
+                        Some(_) => BassRole::Other,
+                    }
+                } else if classes == upper_classes {
+                    BassRole::NonChordTone
+                } else {
+                    continue;
+                };
+                candidates.push((
+                    (bass_role, quality_rank, root_class),
+                    ChordCandidate {
+                        chord,
+                        bass: Note::from(bass),
+                        bass_role,
+                    },
+                ));
+            }
+        }
+        candidates.sort_by_key(|(rank, _)| *rank);
+        candidates
+            .into_iter()
+            .map(|(_, candidate)| candidate)
+            .collect()
+    }
+}
+
+/// Role of bass in [chord candidate](ChordCandidate).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum BassRole {
+    /// Bass is root of the chord (root position).
+    Root,
+    /// Bass is third of the chord (first inversion).
+    Third,
+    /// Bass is fifth of the chord (second inversion).
+    Fifth,
+    /// Bass is other chord tone (like seventh, sixth or suspended second or fourth).
+    Other,
+    /// Bass doesn't belong to the chord.
+    NonChordTone,
+}
+
+/// Interpretation of pitches as chord over bass - see [Chord::identify_with_bass].
+///
+/// Displayed as slash chord symbol (like C/E) when bass isn't the root, otherwise as chord symbol.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChordCandidate {
+    /// Chord formed by the pitches.
+    pub chord: Chord,
+    /// Bass note.
+    pub bass: Note,
+    /// Role of bass in the chord.
+    pub bass_role: BassRole,
+}
+
+impl Display for ChordCandidate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.chord)?;
+        if self.bass_role != BassRole::Root {
+            write!(f, "/{}{}", self.bass.letter(), self.bass.accidental())?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for Chord {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}", self.root.letter(), self.root.accidental())?;
+        match self.quality() {
+            Some(quality) => write!(f, "{}", quality),
+            None => {
+                let intervals: Vec<String> =
+                    self.intervals.iter().map(ToString::to_string).collect();
+                write!(f, "({})", intervals.join(" "))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Accidental, Letter, Octave, PitchClass, PITCHES};
+
+    fn letter_position(note: Note) -> usize {
+        Letter::ALL
+            .iter()
+            .position(|letter| *letter == note.letter())
+            .expect("invariant")
+    }
+
+    #[test]
+    fn notes_agree_with_intervals() {
+        for quality in ChordQuality::ALL {
+            let intervals = quality.intervals();
+            assert_eq!(ChordQuality::from_intervals(intervals), Some(quality));
+            for pitch in &PITCHES[48..60] {
+                for root in pitch.spellings() {
+                    let chord = Chord::new(root, quality);
+                    assert_eq!(chord, Chord::from_intervals(root, intervals));
+                    assert_eq!(chord.quality(), Some(quality));
+                    assert_eq!(chord.root_position_intervals(), intervals);
+
+                    let notes = chord.notes().unwrap();
+                    assert_eq!(notes.len(), intervals.len() + 1);
+                    assert_eq!(notes[0], root);
+                    let array = chord.note_array::<{ Chord::MAX_NOTES }>().unwrap();
+                    assert_eq!(array.as_slice(), notes);
+                    for (note, interval) in notes[1..].iter().zip(intervals) {
+                        let semitones = root.chromatic_index().semitones_to(note.chromatic_index());
+                        assert_eq!(semitones, interval.semitones() as i16, "{} {}", chord, note);
+                        if root.apply_interval(*interval).is_some() {
+                            let letters = (letter_position(*note) + 7 - letter_position(root)) % 7;
+                            assert_eq!(letters, (interval.number() as usize - 1) % 7);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn chord_note_array_capacity() {
+        let c4 = Note::new(Letter::C, Octave::Fifth, Accidental::None).unwrap();
+        let chord = Chord::new(c4, ChordQuality::Dominant7);
+        let names: Vec<String> = chord
+            .note_array::<4>()
+            .unwrap()
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(names, ["C₄", "E₄", "G₄", "B♭₄"]);
+        assert_eq!(chord.note_array::<3>(), Err(Error::TooManyNotes));
+    }
+
+    #[test]
+    fn tritone_substitute_of_g7() {
+        let g4 = Note::new(Letter::G, Octave::Fifth, Accidental::None).unwrap();
+        let d_flat_5 = Note::new(Letter::D, Octave::Sixth, Accidental::Flat).unwrap();
+        let substitute = Chord::new(g4, ChordQuality::Dominant7).tritone_substitute();
+        assert_eq!(substitute, Chord::new(d_flat_5, ChordQuality::Dominant7));
+        assert_eq!(substitute.to_string(), "D♭7");
+        let classes: PitchClassSet = [1, 5, 8, 11].into_iter().map(PitchClass::new).collect();
+        assert_eq!(substitute.pitch_classes(), classes);
+    }
+
+    #[test]
+    fn diminished_chords_are_more_tense_than_major_triad() {
+        let c4 = Note::new(Letter::C, Octave::Fifth, Accidental::None).unwrap();
+        let tension = |quality| Chord::new(c4, quality).tension();
+        assert_eq!(tension(ChordQuality::Major), 1.0);
+        assert_eq!(tension(ChordQuality::Diminished), 4.0);
+        assert_eq!(tension(ChordQuality::Diminished7), 8.0);
+        assert!(tension(ChordQuality::Diminished) > tension(ChordQuality::Major));
+        assert!(tension(ChordQuality::Dominant7) > tension(ChordQuality::Major));
+    }
+
+    fn slash_chords(upper: &[usize], bass: usize) -> Vec<String> {
+        let upper: Vec<Pitch> = upper.iter().map(|index| PITCHES[*index]).collect();
+        Chord::identify_with_bass(&upper, PITCHES[bass])
+            .iter()
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn slash_chords_over_bass() {
+        // C₄ G₄ C₅ over E₃
+        assert_eq!(slash_chords(&[48, 55, 60], 40), ["C/E"]);
+        // C₄ E₄ over G₂
+        assert_eq!(slash_chords(&[48, 52], 31), ["C/G"]);
+        // C₄ E₄ G₄ over C₃
+        assert_eq!(slash_chords(&[48, 52, 55], 36), ["C"]);
+        // A₃ C₄ E₄ over G₂ - bass being fifth of C6 goes before seventh of Am7
+        assert_eq!(slash_chords(&[45, 48, 52], 31), ["C6/G", "Am7/G", "Am/G"]);
+        // C₄ E₄ G₄ A₄ over C₃ - root position goes first
+        assert_eq!(slash_chords(&[48, 52, 55, 57], 36), ["C6", "Am7/C"]);
+        // the same stack over A₂
+        assert_eq!(slash_chords(&[48, 52, 55, 57], 33), ["Am7", "C6/A"]);
+        // A₃ C₄ E₄ over D₃ - bass outside of the chord
+        assert_eq!(slash_chords(&[45, 48, 52], 38), ["Am/D"]);
+    }
+}
@@ -0,0 +1,119 @@
+//! Position on chromatic scale not limited to supported pitches.
+
+use crate::{Cents, Error, Pitch, PitchClass, FREQUENCIES, PITCHES};
+
+/// Position on chromatic scale - number of semitones above C₀.
+///
+/// Unlike [Pitch] it isn't limited to [FREQUENCIES] table,
+/// so it can be negative or above B₈ - it's used for intermediate
+/// results of note arithmetic, with conversion to [Pitch] at the very end.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ChromaticIndex(pub i16);
+
+impl ChromaticIndex {
+    /// Get index value.
+    #[must_use]
+    pub fn value(&self) -> i16 {
+        self.0
+    }
+
+    /// Pitch class of the index.
+    #[must_use]
+    pub fn class(&self) -> PitchClass {
+        PitchClass::new(self.0.rem_euclid(12) as u8)
+    }
+
+    /// Get index shifted by given number of semitones.
+    ///
+    /// Saturates at the bounds of `i16`.
+    #[must_use]
+    pub fn transpose(self, semitones: i16) -> ChromaticIndex {
+        ChromaticIndex(self.0.saturating_add(semitones))
+    }
+
+    /// Number of semitones from this index to `other`.
+    ///
+    /// Positive when `other` is higher.
+    #[must_use]
+    pub fn semitones_to(self, other: ChromaticIndex) -> i16 {
+        other.0.saturating_sub(self.0)
+    }
+
+    /// Position of the index in cents relative to A₄ (for example: -900 for C₄).
+    #[must_use]
+    pub fn cents_from_a4(&self) -> Cents {
+        Cents::new_unchecked(100.0 * (self.0 as f64 - 57.0))
+    }
+
+    /// Convert to [Pitch], fails if index is outside [FREQUENCIES] table.
+    pub fn try_into_pitch(self) -> Result<Pitch, Error> {
+        if (0..FREQUENCIES.len() as i16).contains(&self.0) {
+            Ok(PITCHES[self.0 as usize])
+        } else {
+            Err(Error::PitchNotInRange)
+        }
+    }
+}
+
+impl From<Pitch> for ChromaticIndex {
+    fn from(pitch: Pitch) -> Self {
+        ChromaticIndex(pitch.index() as i16)
+    }
+}
+
+impl TryFrom<ChromaticIndex> for Pitch {
+    type Error = Error;
+
+    fn try_from(value: ChromaticIndex) -> Result<Self, Self::Error> {
+        value.try_into_pitch()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Accidental, Letter, NamedInterval, Note, Octave};
+
+    #[test]
+    fn boundaries_of_pitch_table() {
+        let c0 = Note::new(Letter::C, Octave::First, Accidental::None).unwrap();
+        let c_flat_0 = Note::new_unchecked_spelling(Letter::C, Octave::First, Accidental::Flat);
+        let b8 = Note::new(Letter::B, Octave::Ninth, Accidental::None).unwrap();
+        let b_sharp_8 = Note::new_unchecked_spelling(Letter::B, Octave::Ninth, Accidental::Sharp);
+
+        assert_eq!(c0.chromatic_index(), ChromaticIndex(0));
+        assert_eq!(c0.chromatic_index().try_into_pitch(), Ok(PITCHES[0]));
+        assert_eq!(c_flat_0.chromatic_index(), ChromaticIndex(-1));
+        assert_eq!(
+            c_flat_0.chromatic_index().try_into_pitch(),
+            Err(Error::PitchNotInRange)
+        );
+        assert_eq!(c_flat_0.chromatic_index().class(), PitchClass::new(11));
+
+        assert_eq!(b8.chromatic_index().try_into_pitch(), Ok(PITCHES[107]));
+        assert_eq!(b_sharp_8.chromatic_index(), ChromaticIndex(108));
+        assert_eq!(
+            Pitch::try_from(b_sharp_8.chromatic_index()),
+            Err(Error::PitchNotInRange)
+        );
+        assert!(c_flat_0 < c0 && b8 < b_sharp_8);
+    }
+
+    #[test]
+    fn arithmetic_below_and_above_pitch_table() {
+        let c0 = ChromaticIndex(0);
+        assert_eq!(c0.transpose(-13), ChromaticIndex(-13));
+        assert_eq!(c0.transpose(-13).class(), PitchClass::new(11));
+        assert_eq!(ChromaticIndex(-13).semitones_to(ChromaticIndex(107)), 120);
+        assert_eq!(
+            ChromaticIndex(i16::MAX).transpose(1),
+            ChromaticIndex(i16::MAX)
+        );
+        assert_eq!(c0.cents_from_a4().value(), -5700.0);
+
+        // major third above A₉ is C♯₁₀ - not representable, but its position is
+        let a9 = Note::new(Letter::A, Octave::Tenth, Accidental::None).unwrap();
+        assert_eq!(a9.apply_interval(NamedInterval::MajorThird), None);
+        assert_eq!(a9.chromatic_index().transpose(4), ChromaticIndex(121));
+    }
+}
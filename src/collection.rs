@@ -0,0 +1,345 @@
+//! Queries on collections of pitches.
+
+use crate::{Interval, Pitch, PitchClass, FREQUENCIES};
+
+/// Get subslice of sorted (ascending) pitches lying between `low` and `high` (inclusive).
+///
+/// Uses binary search. Result is empty if `low` is higher than `high`.
+#[must_use]
+pub fn sorted_pitch_range(sorted: &[Pitch], low: Pitch, high: Pitch) -> &[Pitch] {
+    if low > high {
+        return &[];
+    }
+
+    let start = sorted.partition_point(|pitch| *pitch < low);
+    let end = sorted.partition_point(|pitch| *pitch <= high);
+    &sorted[start..end]
+}
+
+/// Get pitch from sorted (ascending) pitches nearest (in semitones) to `target`.
+///
+/// Uses binary search. When two pitches are equally near, the lower one is chosen.
+/// Returns `None` if `sorted` is empty.
+#[must_use]
+pub fn nearest_in_sorted(sorted: &[Pitch], target: Pitch) -> Option<Pitch> {
+    let position = sorted.partition_point(|pitch| *pitch < target);
+    let above = sorted.get(position);
+    let below = position.checked_sub(1).map(|position| sorted[position]);
+    match (below, above) {
+        (Some(below), Some(above)) => {
+            if target.index() - below.index() <= above.index() - target.index() {
+                Some(below)
+            } else {
+                Some(*above)
+            }
+        }
+        (Some(below), None) => Some(below),
+        (None, above) => above.copied(),
+    }
+}
+
+/// Count pitches of given class.
+///
+/// Pitches of one class are spread across the whole collection,
+/// so unlike other functions of this module it's a linear scan
+/// (`sorted` doesn't actually need to be sorted).
+#[must_use]
+pub fn count_in_class(sorted: &[Pitch], class: PitchClass) -> usize {
+    sorted.iter().filter(|pitch| pitch.class() == class).count()
+}
+
+/// Estimate virtual fundamental (in Hz) of pitches - the highest frequency having
+/// all of them (approximately) as its harmonics.
+///
+/// Candidates are subharmonics of the lowest pitch (its frequency divided by 1, 2, ... 32),
+/// the first one having every pitch within 20 cents of one of its harmonics is chosen
+/// (for example: C₂ for C₄-E₄-G₄ major triad, harmonics 4, 5 and 6).
+/// If there's no such candidate, the one with the smallest maximal deviation is chosen.
+/// Returns `None` if `pitches` is empty.
+#[must_use]
+pub fn virtual_fundamental(pitches: &[Pitch]) -> Option<f64> {
+    const MAX_DIVISOR: u32 = 32;
+    const TOLERANCE_CENTS: f64 = 20.0;
+
+    let frequencies: Vec<f64> = pitches.iter().map(Pitch::exact_frequency).collect();
+    let lowest = frequencies.iter().copied().reduce(f64::min)?;
+    let max_deviation = |fundamental: f64| {
+        frequencies
+            .iter()
+            .map(|frequency| {
+                let harmonic = (frequency / fundamental).round();
+                (1200.0 * (frequency / (harmonic * fundamental)).log2()).abs()
+            })
+            .fold(0.0, f64::max)
+    };
+
+    let candidates = (1..=MAX_DIVISOR).map(|divisor| lowest / divisor as f64);
+    candidates
+        .clone()
+        .find(|fundamental| max_deviation(*fundamental) <= TOLERANCE_CENTS)
+        .or_else(|| candidates.min_by(|a, b| max_deviation(*a).total_cmp(&max_deviation(*b))))
+}
+
+/// Get pitch from `palette` nearest (in cents) to frequency.
+///
+/// When two pitches are equally near, the one coming first in `palette` is chosen.
+/// Returns `None` if `palette` is empty or frequency isn't positive and finite.
+#[must_use]
+pub fn snap_to_palette(frequency: f64, palette: &[Pitch]) -> Option<Pitch> {
+    if !(frequency.is_finite() && frequency > 0.0) {
+        return None;
+    }
+    palette
+        .iter()
+        .min_by_key(|pitch| Interval::new(pitch.frequency(), frequency).cents().abs())
+        .copied()
+}
+
+/// Number of octaves containing supported pitches (C₀ - B₈).
+const PITCH_OCTAVES: usize = FREQUENCIES.len() / 12;
+
+/// Count pitches in every octave (index 0 for octave 0, C₀ - B₀ and so on).
+#[must_use]
+pub fn octave_histogram(pitches: impl IntoIterator<Item = Pitch>) -> [usize; PITCH_OCTAVES] {
+    let mut histogram = [0; PITCH_OCTAVES];
+    for pitch in pitches {
+        histogram[pitch.index() as usize / 12] += 1;
+    }
+    histogram
+}
+
+/// Summary of register of pitches - see [register_summary].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RegisterSummary {
+    /// The lowest pitch.
+    pub lowest: Pitch,
+    /// The highest pitch.
+    pub highest: Pitch,
+    /// Lower median - for even number of pitches the lower of two middle ones.
+    pub median_pitch: Pitch,
+    /// Difference between octave numbers of the highest and the lowest pitch
+    /// (0 when all pitches are in the same octave).
+    pub octave_span: u8,
+}
+
+/// Summarize register of pitches.
+///
+/// Returns `None` if there are no pitches.
+#[must_use]
+pub fn register_summary(pitches: impl IntoIterator<Item = Pitch>) -> Option<RegisterSummary> {
+    let mut pitches: Vec<Pitch> = pitches.into_iter().collect();
+    pitches.sort();
+    let lowest = *pitches.first()?;
+    let highest = *pitches.last()?;
+    Some(RegisterSummary {
+        lowest,
+        highest,
+        median_pitch: pitches[(pitches.len() - 1) / 2],
+        octave_span: highest.index() / 12 - lowest.index() / 12,
+    })
+}
+
+/// Tessitura of pitches - the lowest and the highest pitch of the middle
+/// `percentile` percent of them (for example: 80 to skip 10% of the lowest and 10% of the highest notes).
+///
+/// Upper bound is [nearest-rank](https://en.wikipedia.org/wiki/Percentile#The_nearest-rank_method)
+/// percentile `50 + percentile / 2` of the pitches (counting repeated ones), lower bound is
+/// the pitch of the same rank counting from the lowest one, so the same number of pitches
+/// is skipped at both ends (for example: ranks 2 to 9 of 10 pitches for `percentile` 80).
+/// With `percentile` 100 tessitura spans from the lowest to the highest pitch, with small
+/// `percentile` it's the median pitch (or two middle pitches, for even number of pitches).
+///
+/// Returns `None` if there are no pitches or `percentile` isn't within 0 - 100.
+#[must_use]
+pub fn tessitura(
+    pitches: impl IntoIterator<Item = Pitch>,
+    percentile: f64,
+) -> Option<(Pitch, Pitch)> {
+    if !(0.0..=100.0).contains(&percentile) {
+        return None;
+    }
+    let mut pitches: Vec<Pitch> = pitches.into_iter().collect();
+    if pitches.is_empty() {
+        return None;
+    }
+    pitches.sort();
+    let count = pitches.len();
+    let high_rank =
+        (((50.0 + percentile / 2.0) / 100.0 * count as f64).ceil() as usize).clamp(1, count);
+    let low_rank = count + 1 - high_rank;
+    Some((
+        pitches[low_rank.min(high_rank) - 1],
+        pitches[low_rank.max(high_rank) - 1],
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PITCHES;
+
+    /// Deterministic sorted collections with duplicates, of lengths 0 to 39.
+    fn sorted_collections() -> impl Iterator<Item = Vec<Pitch>> {
+        let mut state = 0x2545_f491_u32;
+        (0..200).map(move |round| {
+            let mut next = || {
+                state ^= state << 13;
+                state ^= state >> 17;
+                state ^= state << 5;
+                state
+            };
+            let span = [3, 12, 108][round % 3];
+            let length = next() % 40;
+            let mut pitches: Vec<Pitch> = (0..length)
+                .map(|_| PITCHES[(next() % span) as usize])
+                .collect();
+            pitches.sort();
+            pitches
+        })
+    }
+
+    #[test]
+    fn sorted_range_matches_scan() {
+        for sorted in sorted_collections() {
+            for (low, high) in [(0, 107), (0, 0), (1, 2), (5, 60), (60, 5), (107, 107)] {
+                let (low, high) = (PITCHES[low], PITCHES[high]);
+                let expected: Vec<Pitch> = sorted
+                    .iter()
+                    .copied()
+                    .filter(|pitch| low <= *pitch && *pitch <= high)
+                    .collect();
+                assert_eq!(sorted_pitch_range(&sorted, low, high), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn nearest_matches_scan() {
+        for sorted in sorted_collections() {
+            for target in PITCHES.iter() {
+                let expected = sorted
+                    .iter()
+                    .copied()
+                    .min_by_key(|pitch| (target.semitones_to(*pitch).abs(), *pitch));
+                assert_eq!(nearest_in_sorted(&sorted, *target), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn nearest_ties_go_down() {
+        let sorted = [PITCHES[10], PITCHES[14], PITCHES[14]];
+        assert_eq!(nearest_in_sorted(&sorted, PITCHES[12]), Some(PITCHES[10]));
+        assert_eq!(nearest_in_sorted(&sorted, PITCHES[13]), Some(PITCHES[14]));
+        assert_eq!(nearest_in_sorted(&sorted, PITCHES[0]), Some(PITCHES[10]));
+        assert_eq!(nearest_in_sorted(&sorted, PITCHES[107]), Some(PITCHES[14]));
+        assert_eq!(nearest_in_sorted(&[], PITCHES[12]), None);
+    }
+
+    #[test]
+    fn class_count_matches_scan() {
+        for sorted in sorted_collections() {
+            let total: usize = (0..PitchClass::COUNT)
+                .map(|class| count_in_class(&sorted, PitchClass::new(class)))
+                .sum();
+            assert_eq!(total, sorted.len());
+            for class in (0..PitchClass::COUNT).map(PitchClass::new) {
+                let expected = sorted
+                    .iter()
+                    .filter(|pitch| pitch.index() % 12 == class.number())
+                    .count();
+                assert_eq!(count_in_class(&sorted, class), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn virtual_fundamental_of_major_triad() {
+        // C4 E4 G4 - harmonics 4, 5 and 6 of C2
+        let triad = [PITCHES[48], PITCHES[52], PITCHES[55]];
+        let fundamental = virtual_fundamental(&triad).unwrap();
+        let octaves_below = (triad[0].exact_frequency() / fundamental).log2();
+        assert!((octaves_below - 2.0).abs() < 1e-9, "{}", octaves_below);
+        assert_eq!(Pitch::from_frequency(fundamental), Some(PITCHES[24]));
+
+        // inversion has the same fundamental
+        let inversion = [PITCHES[52], PITCHES[55], PITCHES[60]];
+        let fundamental = virtual_fundamental(&inversion).unwrap();
+        assert_eq!(Pitch::from_frequency(fundamental), Some(PITCHES[24]));
+
+        let a4 = PITCHES[57];
+        assert_eq!(virtual_fundamental(&[a4]), Some(a4.exact_frequency()));
+        assert_eq!(virtual_fundamental(&[]), None);
+    }
+
+    #[test]
+    fn snapping_to_three_pitch_palette() {
+        // C4 E4 G4
+        let palette = [PITCHES[48], PITCHES[52], PITCHES[55]];
+        assert_eq!(snap_to_palette(270.0, &palette), Some(PITCHES[48]));
+        assert_eq!(snap_to_palette(300.0, &palette), Some(PITCHES[52]));
+        assert_eq!(snap_to_palette(360.0, &palette), Some(PITCHES[55]));
+        assert_eq!(snap_to_palette(20.0, &palette), Some(PITCHES[48]));
+        assert_eq!(snap_to_palette(5000.0, &palette), Some(PITCHES[55]));
+        assert_eq!(snap_to_palette(270.0, &[]), None);
+        assert_eq!(snap_to_palette(0.0, &palette), None);
+        assert_eq!(snap_to_palette(f64::NAN, &palette), None);
+    }
+
+    #[test]
+    fn register_statistics() {
+        // E2 C3 C4 D4 E4 F4 G4 A4 C5 C7 (unsorted)
+        let pitches: Vec<Pitch> = [48, 28, 50, 52, 36, 53, 55, 57, 60, 84]
+            .iter()
+            .map(|index| PITCHES[*index])
+            .collect();
+        assert_eq!(
+            octave_histogram(pitches.iter().copied()),
+            [0, 0, 1, 1, 6, 1, 0, 1, 0]
+        );
+        assert_eq!(octave_histogram([]), [0; 9]);
+
+        assert_eq!(
+            register_summary(pitches.iter().copied()),
+            Some(RegisterSummary {
+                lowest: PITCHES[28],
+                highest: PITCHES[84],
+                // lower of E4 and F4
+                median_pitch: PITCHES[52],
+                octave_span: 5,
+            })
+        );
+        let single = register_summary([PITCHES[57]]).unwrap();
+        assert_eq!(single.median_pitch, PITCHES[57]);
+        assert_eq!(single.octave_span, 0);
+        assert_eq!(register_summary([]), None);
+
+        let tessitura = |percentile| tessitura(pitches.iter().copied(), percentile);
+        assert_eq!(tessitura(100.0), Some((PITCHES[28], PITCHES[84])));
+        // ranks 2 and 9 of 10
+        assert_eq!(tessitura(80.0), Some((PITCHES[36], PITCHES[60])));
+        // ranks 3 and 8 of 10
+        assert_eq!(tessitura(60.0), Some((PITCHES[48], PITCHES[57])));
+        // two middle pitches
+        assert_eq!(tessitura(20.0), Some((PITCHES[52], PITCHES[53])));
+        assert_eq!(tessitura(0.0), Some((PITCHES[52], PITCHES[53])));
+
+        // C4 D4 E4 F4 G4
+        let odd = [48, 50, 52, 53, 55].map(|index| PITCHES[index]);
+        // ranks 1 and 5 of 5
+        assert_eq!(
+            super::tessitura(odd, 80.0),
+            Some((PITCHES[48], PITCHES[55]))
+        );
+        // ranks 2 and 4 of 5
+        assert_eq!(
+            super::tessitura(odd, 60.0),
+            Some((PITCHES[50], PITCHES[53]))
+        );
+        assert_eq!(super::tessitura(odd, 0.0), Some((PITCHES[52], PITCHES[52])));
+        for percentile in [-1.0, 100.5, f64::NAN] {
+            assert_eq!(tessitura(percentile), None);
+        }
+        assert_eq!(super::tessitura([], 80.0), None);
+    }
+}
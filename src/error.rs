@@ -0,0 +1,83 @@
+//! Error type.
+
+use std::fmt::Display;
+
+/// Error that can occur in this crate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    /// Incorrect note letter.
+    IncorrectLetter,
+    /// Incorrect note accidental.
+    IncorrectAccidental,
+    /// Octave is not in supported range.
+    OctaveNotInRange,
+    /// Pitch is not in supported range.
+    PitchNotInRange,
+    /// Scale steps are not positive or don't add up to an octave.
+    IncorrectScaleSteps,
+    /// Lower bound of range is higher than upper bound.
+    IncorrectRange,
+    /// Frequency is not positive and finite.
+    IncorrectFrequency,
+    /// Wrong number of tuning offsets or offset out of allowed bounds.
+    IncorrectOffsets,
+    /// Duration is not positive and finite.
+    IncorrectDuration,
+    /// Key signature has more than seven sharps or flats.
+    IncorrectKeySignature,
+    /// Pitch bend range is not positive and finite.
+    IncorrectBendRange,
+    /// MIDI note needed for pitch at given index (of converted sequence) is not in range 0 - 127.
+    MidiNoteNotInRange { index: usize },
+    /// Pitch bend needed for pitch at given index (of converted sequence) exceeds bend range.
+    BendNotInRange { index: usize },
+    /// Note at given index (of transposed melody) doesn't belong to the scale.
+    NoteNotInScale { index: usize },
+    /// Note at given index (of transposed melody) would be transposed out of supported range.
+    TranspositionNotInRange { index: usize },
+    /// Tuning curve has no points or its times are not finite and strictly increasing.
+    IncorrectTuningCurve,
+    /// Harmonic partial number is zero.
+    IncorrectPartial,
+    /// Number of divisions of an octave is zero.
+    IncorrectDivisions,
+    /// Frequency doesn't match any value of [FREQUENCIES](crate::FREQUENCIES).
+    FrequencyNotInTable,
+    /// More notes than fixed-capacity list can hold.
+    TooManyNotes,
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::IncorrectLetter => write!(f, "incorrect letter"),
+            Error::IncorrectAccidental => write!(f, "incorrect accidental"),
+            Error::OctaveNotInRange => write!(f, "octave not in range"),
+            Error::PitchNotInRange => write!(f, "pitch not in range"),
+            Error::IncorrectScaleSteps => write!(f, "incorrect scale steps"),
+            Error::IncorrectRange => write!(f, "incorrect range"),
+            Error::IncorrectFrequency => write!(f, "incorrect frequency"),
+            Error::IncorrectOffsets => write!(f, "incorrect offsets"),
+            Error::IncorrectDuration => write!(f, "incorrect duration"),
+            Error::IncorrectKeySignature => write!(f, "incorrect key signature"),
+            Error::IncorrectBendRange => write!(f, "incorrect bend range"),
+            Error::MidiNoteNotInRange { index } => {
+                write!(f, "MIDI note not in range (pitch at index {})", index)
+            }
+            Error::BendNotInRange { index } => {
+                write!(f, "pitch bend not in range (pitch at index {})", index)
+            }
+            Error::NoteNotInScale { index } => {
+                write!(f, "note not in scale (note at index {})", index)
+            }
+            Error::TranspositionNotInRange { index } => {
+                write!(f, "transposition not in range (note at index {})", index)
+            }
+            Error::IncorrectTuningCurve => write!(f, "incorrect tuning curve"),
+            Error::IncorrectPartial => write!(f, "incorrect partial"),
+            Error::IncorrectDivisions => write!(f, "incorrect divisions"),
+            Error::FrequencyNotInTable => write!(f, "frequency not in table"),
+            Error::TooManyNotes => write!(f, "too many notes"),
+        }
+    }
+}
@@ -0,0 +1,422 @@
+//! Extraction of notes, chords and frequencies from arbitrary text
+//! (for example: pasted by user into an input box).
+
+use std::ops::Range;
+
+use crate::{Accidental, Chord, ChordQuality, Letter, Note, Octave};
+
+/// Heuristics of [extracting](extract_first_note_with) notes, chords and frequencies from text.
+///
+/// Candidates are only considered at word boundaries - they must neither be preceded
+/// nor followed by a letter or a digit (so "Bb" is found in "play Bb now", but not in "Bbq").
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExtractOptions {
+    /// Accept single letters without accidental or octave number as notes
+    /// and major chords (for example: "A" in "play A now").
+    ///
+    /// Off by default, as it matches words like "a" or "I'm a"
+    /// (with [lowercase_letters](ExtractOptions::lowercase_letters)).
+    pub bare_letters: bool,
+    /// Accept lowercase note letters (for example: "f#4").
+    ///
+    /// Off by default.
+    pub lowercase_letters: bool,
+    /// Accept numbers without unit (`Hz` or `kHz`) as frequencies.
+    ///
+    /// Numbers followed by a period are still skipped, so parts of version numbers
+    /// like "1.2.3" aren't matched (at the cost of numbers ending a sentence).
+    ///
+    /// Off by default.
+    pub unitless_frequencies: bool,
+    /// Octave of notes and chord roots written without octave number.
+    ///
+    /// By default: 4th octave (C₄ - B₄).
+    pub default_octave: Octave,
+}
+
+impl Default for ExtractOptions {
+    fn default() -> Self {
+        ExtractOptions {
+            bare_letters: false,
+            lowercase_letters: false,
+            unitless_frequencies: false,
+            default_octave: Octave::Fifth,
+        }
+    }
+}
+
+/// Find the first note in text, with [default](ExtractOptions::default) options -
+/// see [extract_first_note_with].
+#[must_use]
+pub fn extract_first_note(text: &str) -> Option<(Note, Range<usize>)> {
+    extract_first_note_with(text, &ExtractOptions::default())
+}
+
+/// Find the first note in text, along with its span (range of byte positions).
+///
+/// Note is a letter, optional accidental and optional octave number (ASCII or subscript digit),
+/// for example: "F#", "E♭₄" or "C4". Accidental is one of `#`, `♯`, `b`, `♭`, `♮`
+/// or `n` - the last one only when followed by octave number (so "An" isn't a note).
+/// Letter without accidental and octave number is accepted only with
+/// [bare_letters](ExtractOptions::bare_letters) option.
+#[must_use]
+pub fn extract_first_note_with(
+    text: &str,
+    options: &ExtractOptions,
+) -> Option<(Note, Range<usize>)> {
+    candidates(text).find_map(|start| {
+        let (letter, rest) = letter_at(&text[start..], options)?;
+        let ascii_natural = rest.starts_with('n');
+        let (accidental, rest) = accidental_at(rest, true);
+        let (octave, rest) = match octave_at(rest) {
+            Some((octave, rest)) => (Some(octave), rest),
+            None => (None, rest),
+        };
+        let accidental = match (accidental, octave) {
+            (Some(Accidental::Natural), None) if ascii_natural => return None,
+            (Some(accidental), _) => accidental,
+            (None, Some(_)) => Accidental::None,
+            (None, None) if options.bare_letters => Accidental::None,
+            (None, None) => return None,
+        };
+        let end = text.len() - rest.len();
+        at_boundary(rest).then(|| {
+            let note = Note::new_unchecked_spelling(
+                letter,
+                octave.unwrap_or(options.default_octave),
+                accidental,
+            );
+            (note, start..end)
+        })
+    })
+}
+
+/// Find the first chord symbol in text, with [default](ExtractOptions::default) options -
+/// see [extract_first_chord_with].
+#[must_use]
+pub fn extract_first_chord(text: &str) -> Option<(Chord, Range<usize>)> {
+    extract_first_chord_with(text, &ExtractOptions::default())
+}
+
+/// Find the first chord symbol in text, along with its span (range of byte positions).
+///
+/// Chord symbol is a root letter, optional accidental (`#`, `♯`, `b` or `♭`)
+/// and [chord quality](ChordQuality) symbol (for example: "F#m7", "B♭°7" or "Cmaj7"),
+/// root is placed in [default octave](ExtractOptions::default_octave).
+/// Major chord written as a letter alone is accepted only with
+/// [bare_letters](ExtractOptions::bare_letters) option.
+///
+/// Chord symbols that are also words (like "Am") are found as well.
+#[must_use]
+pub fn extract_first_chord_with(
+    text: &str,
+    options: &ExtractOptions,
+) -> Option<(Chord, Range<usize>)> {
+    candidates(text).find_map(|start| {
+        let (letter, rest) = letter_at(&text[start..], options)?;
+        let (accidental, rest) = accidental_at(rest, false);
+        let (quality, rest) = ChordQuality::ALL
+            .into_iter()
+            .filter_map(|quality| {
+                let rest = rest.strip_prefix(quality.to_string().as_str())?;
+                at_boundary(rest).then_some((quality, rest))
+            })
+            .min_by_key(|(_, rest)| rest.len())?;
+        if accidental.is_none() && quality == ChordQuality::Major && !options.bare_letters {
+            return None;
+        }
+        let root = Note::new_unchecked_spelling(
+            letter,
+            options.default_octave,
+            accidental.unwrap_or(Accidental::None),
+        );
+        let end = text.len() - rest.len();
+        Some((Chord::new(root, quality), start..end))
+    })
+}
+
+/// Find the first frequency in text, with [default](ExtractOptions::default) options -
+/// see [extract_first_frequency_with].
+#[must_use]
+pub fn extract_first_frequency(text: &str) -> Option<(f64, Range<usize>)> {
+    extract_first_frequency_with(text, &ExtractOptions::default())
+}
+
+/// Find the first frequency (in Hz) in text, along with its span (range of byte positions).
+///
+/// Frequency is a positive decimal number (with `.` as decimal separator) followed by unit -
+/// `Hz` or `kHz` (case-insensitive, optionally separated by whitespace),
+/// for example: "440 Hz", "261.63Hz" or "1.5 kHz". Number without unit is accepted only with
+/// [unitless_frequencies](ExtractOptions::unitless_frequencies) option.
+#[must_use]
+pub fn extract_first_frequency_with(
+    text: &str,
+    options: &ExtractOptions,
+) -> Option<(f64, Range<usize>)> {
+    candidates(text).find_map(|start| {
+        let candidate = &text[start..];
+        let integer = candidate.len()
+            - candidate
+                .trim_start_matches(|c: char| c.is_ascii_digit())
+                .len();
+        if integer == 0 {
+            return None;
+        }
+        let mut length = integer;
+        if let Some(fraction) = candidate[integer..].strip_prefix('.') {
+            let digits = fraction.len()
+                - fraction
+                    .trim_start_matches(|c: char| c.is_ascii_digit())
+                    .len();
+            if digits > 0 {
+                length += 1 + digits;
+            }
+        }
+        let number: f64 = candidate[..length].parse().ok()?;
+        let rest = &candidate[length..];
+
+        let unit = rest.trim_start();
+        let with_unit =
+            [("khz", 1000.0), ("hz", 1.0)]
+                .into_iter()
+                .find_map(|(unit_name, multiplier)| {
+                    let prefix = unit.get(..unit_name.len())?;
+                    let rest = &unit[unit_name.len()..];
+                    (prefix.eq_ignore_ascii_case(unit_name) && at_boundary(rest))
+                        .then_some((number * multiplier, rest))
+                });
+        let (frequency, rest) = match with_unit {
+            Some(with_unit) => with_unit,
+            None if options.unitless_frequencies && at_boundary(rest) && !rest.starts_with('.') => {
+                (number, rest)
+            }
+            None => return None,
+        };
+        (frequency.is_finite() && frequency > 0.0)
+            .then(|| (frequency, start..text.len() - rest.len()))
+    })
+}
+
+/// Byte positions in text at which a word starts.
+fn candidates(text: &str) -> impl Iterator<Item = usize> + '_ {
+    text.char_indices()
+        .filter(|(position, _)| {
+            !text[..*position]
+                .chars()
+                .next_back()
+                .is_some_and(|previous| previous.is_alphanumeric() || previous == '.')
+        })
+        .map(|(position, _)| position)
+}
+
+/// Check if text following a candidate doesn't continue the word.
+fn at_boundary(rest: &str) -> bool {
+    !rest
+        .chars()
+        .next()
+        .is_some_and(|next| next.is_alphanumeric() || matches!(next, '#' | '♯' | '♭' | '♮'))
+}
+
+fn letter_at<'a>(text: &'a str, options: &ExtractOptions) -> Option<(Letter, &'a str)> {
+    let character = text.chars().next()?;
+    if character.is_lowercase() && !options.lowercase_letters {
+        return None;
+    }
+    let letter = Letter::try_from(character).ok()?;
+    Some((letter, &text[character.len_utf8()..]))
+}
+
+fn accidental_at(text: &str, with_natural: bool) -> (Option<Accidental>, &str) {
+    let accidental = match text.chars().next() {
+        Some('#' | '♯') => Accidental::Sharp,
+        Some('b' | '♭') => Accidental::Flat,
+        Some('n' | '♮') if with_natural => Accidental::Natural,
+        _ => return (None, text),
+    };
+    let character = text.chars().next().expect("invariant");
+    (Some(accidental), &text[character.len_utf8()..])
+}
+
+fn octave_at(text: &str) -> Option<(Octave, &str)> {
+    let character = text.chars().next()?;
+    let number = match character {
+        digit @ '0'..='9' => digit as u32 - '0' as u32,
+        digit @ '₀'..='₉' => digit as u32 - '₀' as u32,
+        _ => return None,
+    };
+    let octave = Octave::try_from(number as u8).ok()?;
+    Some((octave, &text[character.len_utf8()..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn note(letter: Letter, accidental: Accidental, octave: u8) -> Note {
+        let octave = Octave::try_from(octave).unwrap();
+        Note::new_unchecked_spelling(letter, octave, accidental)
+    }
+
+    fn note_in<'a>(text: &'a str, options: &ExtractOptions) -> Option<(Note, &'a str)> {
+        extract_first_note_with(text, options).map(|(note, span)| (note, &text[span]))
+    }
+
+    fn chord_in<'a>(text: &'a str, options: &ExtractOptions) -> Option<(String, &'a str)> {
+        extract_first_chord_with(text, options)
+            .map(|(chord, span)| (chord.to_string(), &text[span]))
+    }
+
+    fn frequency_in<'a>(text: &'a str, options: &ExtractOptions) -> Option<(f64, &'a str)> {
+        extract_first_frequency_with(text, options)
+            .map(|(frequency, span)| (frequency, &text[span]))
+    }
+
+    #[test]
+    fn notes_in_pasted_text() {
+        let default = ExtractOptions::default();
+        let corpus = [
+            (
+                "tune the low string to E2 please",
+                Some((note(Letter::E, Accidental::None, 2), "E2")),
+            ),
+            (
+                "play Bb now",
+                Some((note(Letter::B, Accidental::Flat, 4), "Bb")),
+            ),
+            (
+                "E♭₄ then G",
+                Some((note(Letter::E, Accidental::Flat, 4), "E♭₄")),
+            ),
+            (
+                "(F#3), then A",
+                Some((note(Letter::F, Accidental::Sharp, 3), "F#3")),
+            ),
+            (
+                "An4 is A natural",
+                Some((note(Letter::A, Accidental::Natural, 4), "An4")),
+            ),
+            (
+                "it's a B♯3, really",
+                Some((note(Letter::B, Accidental::Sharp, 3), "B♯3")),
+            ),
+            ("the chord is F#m7 I think", None),
+            ("A is the note", None),
+            ("An apple", None),
+            ("Bbq tonight", None),
+            ("sing a c4 please", None),
+            ("model XC4 or C44", None),
+            ("", None),
+        ];
+        for (text, expected) in corpus {
+            assert_eq!(note_in(text, &default), expected, "{:?}", text);
+        }
+        assert_eq!(
+            extract_first_note("play Bb now"),
+            Some((note(Letter::B, Accidental::Flat, 4), 5..7))
+        );
+        // multi-byte characters before the note
+        assert_eq!(
+            extract_first_note("♪♪ G♯5"),
+            Some((note(Letter::G, Accidental::Sharp, 5), 7..12))
+        );
+    }
+
+    #[test]
+    fn note_heuristics_are_configurable() {
+        let bare = ExtractOptions {
+            bare_letters: true,
+            ..ExtractOptions::default()
+        };
+        assert_eq!(
+            note_in("A is the note", &bare),
+            Some((note(Letter::A, Accidental::None, 4), "A"))
+        );
+        assert_eq!(note_in("An apple", &bare), None);
+
+        let lowercase = ExtractOptions {
+            lowercase_letters: true,
+            default_octave: Octave::try_from(3).unwrap(),
+            ..ExtractOptions::default()
+        };
+        assert_eq!(
+            note_in("sing a c4 please", &lowercase),
+            Some((note(Letter::C, Accidental::None, 4), "c4"))
+        );
+        assert_eq!(
+            note_in("then f# again", &lowercase),
+            Some((note(Letter::F, Accidental::Sharp, 3), "f#"))
+        );
+        // with both, articles are mistaken for notes
+        let both = ExtractOptions {
+            bare_letters: true,
+            ..lowercase
+        };
+        assert_eq!(
+            note_in("sing a c4 please", &both),
+            Some((note(Letter::A, Accidental::None, 3), "a"))
+        );
+    }
+
+    #[test]
+    fn chords_in_pasted_text() {
+        let default = ExtractOptions::default();
+        let corpus = [
+            ("the chord is F#m7 I think", Some(("F♯m7", "F#m7"))),
+            ("Cmaj7 voicing, then Dm", Some(("Cmaj7", "Cmaj7"))),
+            ("try B♭°7 here", Some(("B♭°7", "B♭°7"))),
+            ("vamp on G7sus4?", None),
+            ("Esus4 -> E", Some(("Esus4", "Esus4"))),
+            ("Ab+ sounds odd", Some(("A♭+", "Ab+"))),
+            // chord symbols that are also words
+            ("Am I right?", Some(("Am", "Am"))),
+            ("C then G", None),
+            ("Bbq tonight", None),
+            ("see Fig. 4", None),
+        ];
+        for (text, expected) in corpus {
+            let expected = expected.map(|(chord, span)| (chord.to_string(), span));
+            assert_eq!(chord_in(text, &default), expected, "{:?}", text);
+        }
+
+        let bare = ExtractOptions {
+            bare_letters: true,
+            ..default
+        };
+        assert_eq!(chord_in("C then G", &bare), Some(("C".to_string(), "C")));
+        let (chord, _) = extract_first_chord("the chord is F#m7 I think").unwrap();
+        assert_eq!(chord.root(), note(Letter::F, Accidental::Sharp, 4));
+    }
+
+    #[test]
+    fn frequencies_in_pasted_text() {
+        let default = ExtractOptions::default();
+        let corpus = [
+            ("tuner says 440 Hz", Some((440.0, "440 Hz"))),
+            ("around 1.5 kHz, I guess", Some((1500.0, "1.5 kHz"))),
+            ("C4 is 261.63Hz", Some((261.63, "261.63Hz"))),
+            ("hum at 60HZ.", Some((60.0, "60HZ"))),
+            ("I have 3 strings", None),
+            ("version 1.2.3", None),
+            ("0 Hz", None),
+            ("440hzz", None),
+            ("440 Hertz", None),
+        ];
+        for (text, expected) in corpus {
+            assert_eq!(frequency_in(text, &default), expected, "{:?}", text);
+        }
+
+        let unitless = ExtractOptions {
+            unitless_frequencies: true,
+            ..default
+        };
+        assert_eq!(
+            frequency_in("I have 3 strings", &unitless),
+            Some((3.0, "3"))
+        );
+        assert_eq!(frequency_in("version 1.2.3", &unitless), None);
+        assert_eq!(frequency_in("A=442 today", &unitless), Some((442.0, "442")));
+        // number followed by a period can't be told apart from a version number
+        assert_eq!(frequency_in("A=442.", &unitless), None);
+        assert_eq!(frequency_in("0 or 110", &unitless), Some((110.0, "110")));
+    }
+}
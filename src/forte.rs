@@ -0,0 +1,347 @@
+//! Forte numbers of pitch class set classes.
+
+use std::fmt::Display;
+
+use lazy_static::lazy_static;
+
+use crate::{PitchClass, PitchClassSet};
+
+/// Prime forms of set classes of 3 to 6 pitch classes, in order of Forte's list.
+///
+/// Remaining set classes are numbered after their complements
+/// (for example: 7-1 is complement of 5-1), Z-relations are computed from interval vectors.
+const FORTE_PRIME_FORMS: [&[&[u8]]; 4] = [
+    &[
+        &[0, 1, 2],
+        &[0, 1, 3],
+        &[0, 1, 4],
+        &[0, 1, 5],
+        &[0, 1, 6],
+        &[0, 2, 4],
+        &[0, 2, 5],
+        &[0, 2, 6],
+        &[0, 2, 7],
+        &[0, 3, 6],
+        &[0, 3, 7],
+        &[0, 4, 8],
+    ],
+    &[
+        &[0, 1, 2, 3],
+        &[0, 1, 2, 4],
+        &[0, 1, 3, 4],
+        &[0, 1, 2, 5],
+        &[0, 1, 2, 6],
+        &[0, 1, 2, 7],
+        &[0, 1, 4, 5],
+        &[0, 1, 5, 6],
+        &[0, 1, 6, 7],
+        &[0, 2, 3, 5],
+        &[0, 1, 3, 5],
+        &[0, 2, 3, 6],
+        &[0, 1, 3, 6],
+        &[0, 2, 3, 7],
+        &[0, 1, 4, 6],
+        &[0, 1, 5, 7],
+        &[0, 3, 4, 7],
+        &[0, 1, 4, 7],
+        &[0, 1, 4, 8],
+        &[0, 1, 5, 8],
+        &[0, 2, 4, 6],
+        &[0, 2, 4, 7],
+        &[0, 2, 5, 7],
+        &[0, 2, 4, 8],
+        &[0, 2, 6, 8],
+        &[0, 3, 5, 8],
+        &[0, 2, 5, 8],
+        &[0, 3, 6, 9],
+        &[0, 1, 3, 7],
+    ],
+    &[
+        &[0, 1, 2, 3, 4],
+        &[0, 1, 2, 3, 5],
+        &[0, 1, 2, 4, 5],
+        &[0, 1, 2, 3, 6],
+        &[0, 1, 2, 3, 7],
+        &[0, 1, 2, 5, 6],
+        &[0, 1, 2, 6, 7],
+        &[0, 2, 3, 4, 6],
+        &[0, 1, 2, 4, 6],
+        &[0, 1, 3, 4, 6],
+        &[0, 2, 3, 4, 7],
+        &[0, 1, 3, 5, 6],
+        &[0, 1, 2, 4, 8],
+        &[0, 1, 2, 5, 7],
+        &[0, 1, 2, 6, 8],
+        &[0, 1, 3, 4, 7],
+        &[0, 1, 3, 4, 8],
+        &[0, 1, 4, 5, 7],
+        &[0, 1, 3, 6, 7],
+        &[0, 1, 3, 7, 8],
+        &[0, 1, 4, 5, 8],
+        &[0, 1, 4, 7, 8],
+        &[0, 2, 3, 5, 7],
+        &[0, 1, 3, 5, 7],
+        &[0, 2, 3, 5, 8],
+        &[0, 2, 4, 5, 8],
+        &[0, 1, 3, 5, 8],
+        &[0, 2, 3, 6, 8],
+        &[0, 1, 3, 6, 8],
+        &[0, 1, 4, 6, 8],
+        &[0, 1, 3, 6, 9],
+        &[0, 1, 4, 6, 9],
+        &[0, 2, 4, 6, 8],
+        &[0, 2, 4, 6, 9],
+        &[0, 2, 4, 7, 9],
+        &[0, 1, 2, 4, 7],
+        &[0, 3, 4, 5, 8],
+        &[0, 1, 2, 5, 8],
+    ],
+    &[
+        &[0, 1, 2, 3, 4, 5],
+        &[0, 1, 2, 3, 4, 6],
+        &[0, 1, 2, 3, 5, 6],
+        &[0, 1, 2, 4, 5, 6],
+        &[0, 1, 2, 3, 6, 7],
+        &[0, 1, 2, 5, 6, 7],
+        &[0, 1, 2, 6, 7, 8],
+        &[0, 2, 3, 4, 5, 7],
+        &[0, 1, 2, 3, 5, 7],
+        &[0, 1, 3, 4, 5, 7],
+        &[0, 1, 2, 4, 5, 7],
+        &[0, 1, 2, 4, 6, 7],
+        &[0, 1, 3, 4, 6, 7],
+        &[0, 1, 3, 4, 5, 8],
+        &[0, 1, 2, 4, 5, 8],
+        &[0, 1, 4, 5, 6, 8],
+        &[0, 1, 2, 4, 7, 8],
+        &[0, 1, 2, 5, 7, 8],
+        &[0, 1, 3, 4, 7, 8],
+        &[0, 1, 4, 5, 8, 9],
+        &[0, 2, 3, 4, 6, 8],
+        &[0, 1, 2, 4, 6, 8],
+        &[0, 2, 3, 5, 6, 8],
+        &[0, 1, 3, 4, 6, 8],
+        &[0, 1, 3, 5, 6, 8],
+        &[0, 1, 3, 5, 7, 8],
+        &[0, 1, 3, 4, 6, 9],
+        &[0, 1, 3, 5, 6, 9],
+        &[0, 1, 3, 6, 8, 9],
+        &[0, 1, 3, 6, 7, 9],
+        &[0, 1, 3, 5, 8, 9],
+        &[0, 2, 4, 5, 7, 9],
+        &[0, 2, 3, 5, 7, 9],
+        &[0, 1, 3, 5, 7, 9],
+        &[0, 2, 4, 6, 8, 10],
+        &[0, 1, 2, 3, 4, 7],
+        &[0, 1, 2, 3, 4, 8],
+        &[0, 1, 2, 3, 7, 8],
+        &[0, 2, 3, 4, 5, 8],
+        &[0, 1, 2, 3, 5, 8],
+        &[0, 1, 2, 3, 6, 8],
+        &[0, 1, 2, 3, 6, 9],
+        &[0, 1, 2, 5, 6, 8],
+        &[0, 1, 2, 5, 6, 9],
+        &[0, 2, 3, 4, 6, 9],
+        &[0, 1, 2, 4, 6, 9],
+        &[0, 1, 2, 4, 7, 9],
+        &[0, 1, 2, 5, 7, 9],
+        &[0, 1, 3, 4, 7, 9],
+        &[0, 1, 4, 6, 7, 9],
+    ],
+];
+
+lazy_static! {
+    /// Prime forms and Forte numbers of all 224 set classes.
+    static ref FORTE_NUMBERS: Vec<(PitchClassSet, ForteNumber)> = forte_numbers();
+}
+
+/// Forte number - name of set class in Allen Forte's classification
+/// (for example: 3-11 for major and minor triads).
+///
+/// Set classes of 0 - 2 and 10 - 12 pitch classes (not listed by Forte)
+/// are numbered in common extension of the list - 0-1, 1-1, 2-1 to 2-6
+/// (by interval class), 10-1 to 10-6, 11-1 and 12-1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ForteNumber {
+    cardinality: u8,
+    ordinal: u8,
+    z: bool,
+}
+
+impl ForteNumber {
+    /// Number of pitch classes in sets of the class.
+    #[must_use]
+    pub fn cardinality(&self) -> u8 {
+        self.cardinality
+    }
+
+    /// Position of the set class in Forte's list of classes with the same cardinality (counting from 1).
+    #[must_use]
+    pub fn ordinal(&self) -> u8 {
+        self.ordinal
+    }
+
+    /// Check if set class is Z-related to another one - they have the same
+    /// [interval vector](PitchClassSet::interval_vector), but aren't transpositions or inversions of each other.
+    #[must_use]
+    pub fn is_z(&self) -> bool {
+        self.z
+    }
+}
+
+impl Display for ForteNumber {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let z = if self.z { "Z" } else { "" };
+        write!(f, "{}-{}{}", self.cardinality, z, self.ordinal)
+    }
+}
+
+impl PitchClassSet {
+    /// Forte number of set class of the set (for example: 4-Z15 for {0, 1, 4, 6}).
+    #[must_use]
+    pub fn forte_number(&self) -> ForteNumber {
+        let prime_form = self.prime_form();
+        FORTE_NUMBERS
+            .iter()
+            .find(|(set, _)| *set == prime_form)
+            .map(|(_, number)| *number)
+            .expect("invariant")
+    }
+
+    /// Prime form of set class Z-related to set class of the set
+    /// (for example: {0, 1, 3, 7} of 4-Z29 for {0, 1, 4, 6} of 4-Z15).
+    ///
+    /// Returns `None` if set class isn't Z-related to any other.
+    #[must_use]
+    pub fn z_partner(&self) -> Option<PitchClassSet> {
+        let prime_form = self.prime_form();
+        let vector = prime_form.interval_vector();
+        FORTE_NUMBERS
+            .iter()
+            .find(|(set, _)| {
+                *set != prime_form
+                    && set.len() == prime_form.len()
+                    && set.interval_vector() == vector
+            })
+            .map(|(set, _)| *set)
+    }
+}
+
+fn forte_numbers() -> Vec<(PitchClassSet, ForteNumber)> {
+    let number = |set: PitchClassSet, ordinal: usize| {
+        (
+            set.prime_form(),
+            ForteNumber {
+                cardinality: set.len() as u8,
+                ordinal: ordinal as u8,
+                z: false,
+            },
+        )
+    };
+    let dyads = (1..=6).map(|interval_class| {
+        PitchClassSet::from_iter([PitchClass::new(0), PitchClass::new(interval_class)])
+    });
+    let mut numbers: Vec<(PitchClassSet, ForteNumber)> = vec![
+        number(PitchClassSet::new(), 1),
+        number(PitchClassSet::from_iter([PitchClass::new(0)]), 1),
+    ];
+    numbers.extend(dyads.enumerate().map(|(i, set)| number(set, i + 1)));
+    for prime_forms in FORTE_PRIME_FORMS {
+        numbers.extend(prime_forms.iter().enumerate().map(|(i, prime_form)| {
+            let set = prime_form
+                .iter()
+                .map(|class| PitchClass::new(*class))
+                .collect();
+            number(set, i + 1)
+        }));
+    }
+    // hexachords are complements of hexachords, already listed
+    let complements: Vec<_> = numbers
+        .iter()
+        .filter(|(set, _)| set.len() < 6)
+        .map(|(set, forte)| number(set.complement(), forte.ordinal as usize))
+        .collect();
+    numbers.extend(complements);
+
+    let vectors: Vec<_> = numbers
+        .iter()
+        .map(|(set, _)| (set.len(), set.interval_vector()))
+        .collect();
+    for (i, (_, forte)) in numbers.iter_mut().enumerate() {
+        forte.z = vectors
+            .iter()
+            .enumerate()
+            .any(|(j, vector)| i != j && *vector == vectors[i]);
+    }
+    numbers.sort_by_key(|(_, forte)| *forte);
+    numbers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PitchClass;
+
+    fn set(classes: &[u8]) -> PitchClassSet {
+        classes
+            .iter()
+            .map(|class| PitchClass::new(*class))
+            .collect()
+    }
+
+    #[test]
+    fn published_entries() {
+        for (classes, expected) in [
+            (&[0, 4, 7][..], "3-11"),
+            (&[0, 3, 7], "3-11"),
+            (&[0, 1, 4, 6], "4-Z15"),
+            (&[0, 1, 3, 7], "4-Z29"),
+            (&[0, 1, 2, 5, 6, 9], "6-Z44"),
+            (&[0, 1, 2, 3, 4, 5], "6-1"),
+            (&[0, 2, 4, 5, 7, 9, 11], "7-35"),
+        ] {
+            assert_eq!(set(classes).forte_number().to_string(), expected);
+        }
+        assert_eq!(set(&[0, 1, 4, 6]).z_partner(), Some(set(&[0, 1, 3, 7])));
+        assert_eq!(set(&[0, 1, 3, 7]).z_partner(), Some(set(&[0, 1, 4, 6])));
+        assert_eq!(set(&[0, 4, 7]).z_partner(), None);
+    }
+
+    #[test]
+    fn every_set_has_number() {
+        let mut numbers = vec![];
+        for mask in 0..1 << PitchClass::COUNT {
+            let set = PitchClassSet::from_mask(mask);
+            let number = set.forte_number();
+            assert_eq!(number.cardinality() as usize, set.len());
+            assert_eq!(number.is_z(), set.z_partner().is_some());
+            if !numbers.contains(&number) {
+                numbers.push(number);
+            }
+        }
+        assert_eq!(numbers.len(), 224);
+
+        let counts: Vec<usize> = (0..=12)
+            .map(|cardinality| {
+                numbers
+                    .iter()
+                    .filter(|number| number.cardinality() == cardinality)
+                    .count()
+            })
+            .collect();
+        assert_eq!(counts, [1, 1, 6, 12, 29, 38, 50, 38, 29, 12, 6, 1, 1]);
+    }
+
+    #[test]
+    fn complements_share_ordinal() {
+        for mask in 0..1 << PitchClass::COUNT {
+            let set = PitchClassSet::from_mask(mask);
+            if (3..=5).contains(&set.len()) {
+                let number = set.forte_number();
+                let complement = set.complement().forte_number();
+                assert_eq!(number.ordinal(), complement.ordinal(), "{:?}", set);
+                assert_eq!(number.is_z(), complement.is_z(), "{:?}", set);
+            }
+        }
+    }
+}
@@ -0,0 +1,89 @@
+//! Fretted string instruments.
+
+use crate::{Pitch, PITCHES};
+
+/// Pitch sounding when string tuned to `open_string` is pressed at `fret`
+/// (for example: A₂ at 5th fret of low E₂ string).
+///
+/// Returns `None` if pitch is out of supported range.
+#[must_use]
+pub fn fretted_pitch(open_string: Pitch, fret: u8) -> Option<Pitch> {
+    PITCHES
+        .get(open_string.index() as usize + fret as usize)
+        .copied()
+}
+
+/// Find all positions `(string index, fret)` producing `pitch` on instrument with open strings
+/// tuned to `tuning`, up to `max_fret` (for example: A₂ at 5th fret of E₂ string and open A₂ string).
+///
+/// Positions are ordered by string index.
+#[must_use]
+pub fn positions_for(pitch: Pitch, tuning: &[Pitch], max_fret: u8) -> Vec<(usize, u8)> {
+    tuning
+        .iter()
+        .enumerate()
+        .filter_map(|(string, open_string)| {
+            let fret = open_string.semitones_to(pitch);
+            (0..=max_fret as i16)
+                .contains(&fret)
+                .then_some((string, fret as u8))
+        })
+        .collect()
+}
+
+/// Open string pitches of instrument tuned to `tuning` with capo at `fret` - every string
+/// [pressed](fretted_pitch) at that fret (for example: F♯₂ B₂ E₃ A₃ C♯₄ F♯₄ for standard
+/// guitar tuning with capo at 2nd fret).
+///
+/// Strings whose pitch would be out of supported range are `None`.
+#[must_use]
+pub fn capo(tuning: &[Pitch], fret: u8) -> Vec<Option<Pitch>> {
+    tuning
+        .iter()
+        .map(|open_string| fretted_pitch(*open_string, fret))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fifth_fret_of_low_e_string_is_a2() {
+        let (e2, a2) = (PITCHES[28], PITCHES[33]);
+        assert_eq!(fretted_pitch(e2, 5), Some(a2));
+        assert_eq!(fretted_pitch(e2, 0), Some(e2));
+        assert_eq!(fretted_pitch(e2, 12), Some(PITCHES[40]));
+        assert_eq!(fretted_pitch(PITCHES[100], 7), Some(PITCHES[107]));
+        assert_eq!(fretted_pitch(PITCHES[100], 8), None);
+        assert_eq!(fretted_pitch(e2, u8::MAX), None);
+    }
+
+    #[test]
+    fn a2_on_low_e_and_a_strings() {
+        // E2 A2 D3 G3 B3 E4
+        let guitar = [28, 33, 38, 43, 47, 52].map(|index| PITCHES[index]);
+        assert_eq!(positions_for(PITCHES[33], &guitar, 24), [(0, 5), (1, 0)]);
+        assert_eq!(positions_for(PITCHES[33], &guitar, 4), [(1, 0)]);
+        assert_eq!(
+            positions_for(PITCHES[52], &guitar, 24),
+            [(0, 24), (1, 19), (2, 14), (3, 9), (4, 5), (5, 0)]
+        );
+        assert_eq!(positions_for(PITCHES[27], &guitar, 24), []);
+    }
+
+    #[test]
+    fn capo_on_second_fret_raises_by_whole_step() {
+        // E2 A2 D3 G3 B3 E4
+        let guitar = [28, 33, 38, 43, 47, 52].map(|index| PITCHES[index]);
+        // F♯2 B2 E3 A3 C♯4 F♯4
+        let expected = [30, 35, 40, 45, 49, 54].map(|index| Some(PITCHES[index]));
+        assert_eq!(capo(&guitar, 2), expected);
+        assert_eq!(capo(&guitar, 0), guitar.map(Some));
+        assert_eq!(
+            capo(&[PITCHES[100], PITCHES[104]], 5),
+            [Some(PITCHES[105]), None]
+        );
+        assert!(capo(&[], 2).is_empty());
+    }
+}
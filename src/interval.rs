@@ -1,9 +1,81 @@
 //! Interval between frequencies.
 
-use std::fmt::Display;
+use std::{
+    fmt::Display,
+    iter::Sum,
+    ops::{Add, Sub},
+};
 
 use ordered_float::NotNan;
 
+use crate::Pitch;
+
+/// Coordinates `(fifths, thirds)` of just intonation ratio on 5-limit lattice -
+/// exponents of 3 and 5 in its prime factorization (for example: `(0, 1)` for 5/4,
+/// `(1, 0)` for 3/2, `(2, -1)` for 9/5 ... ).
+///
+/// Powers of 2 are ignored (octaves are equivalent).
+/// Returns `None` if either number is zero or has prime factors other than 2, 3 and 5.
+#[must_use]
+pub fn lattice_coordinates(numerator: u32, denominator: u32) -> Option<(i32, i32)> {
+    let factorize = |mut number: u32| -> Option<(i32, i32)> {
+        if number == 0 {
+            return None;
+        }
+        let mut exponents = [0; 3];
+        for (exponent, prime) in exponents.iter_mut().zip([2, 3, 5]) {
+            while number % prime == 0 {
+                number /= prime;
+                *exponent += 1;
+            }
+        }
+        (number == 1).then_some((exponents[1], exponents[2]))
+    };
+    let (numerator_threes, numerator_fives) = factorize(numerator)?;
+    let (denominator_threes, denominator_fives) = factorize(denominator)?;
+    Some((
+        numerator_threes - denominator_threes,
+        numerator_fives - denominator_fives,
+    ))
+}
+
+/// Tenney height of ratio - base-2 logarithm of product of its numerator and denominator,
+/// measure of its harmonic complexity (for example: about 2.58 for 3/2, about 5.13 for 7/5).
+///
+/// Ratio is taken as given - pass it in lowest terms (6/4 has higher height than 3/2).
+/// Returns infinity if either number is zero.
+#[must_use]
+pub fn tenney_height(numerator: u32, denominator: u32) -> f64 {
+    if numerator == 0 || denominator == 0 {
+        return f64::INFINITY;
+    }
+    ((numerator as u64 * denominator as u64) as f64).log2()
+}
+
+/// Geometric midpoint of interval between two pitches - geometric mean of their frequencies
+/// (for example: about 622 Hz, near D♯₅/E♭₅, for A₄ and A₅),
+/// along with its deviation from the [nearest](Pitch::from_frequency) pitch.
+#[must_use]
+pub fn bisect(a: Pitch, b: Pitch) -> (f64, Cents) {
+    let midpoint = (a.frequency() * b.frequency()).sqrt();
+    let nearest = Pitch::from_frequency(midpoint).expect("invariant");
+    (
+        midpoint,
+        Interval::new(nearest.frequency(), midpoint).cents(),
+    )
+}
+
+/// Frequency of a monochord string stopped so that `division` of its length
+/// (fraction between 0 and 1) vibrates - frequency is inversely proportional to length
+/// (for example: 1/2 sounds an octave, 2/3 a just fifth above open string).
+///
+/// Open string frequency must be positive and division must be between 0 (exclusive) and 1,
+/// otherwise result is meaningless.
+#[must_use]
+pub fn monochord_frequency(open_freq: f64, division: f64) -> f64 {
+    open_freq / division
+}
+
 /// Interval between pitches.
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Interval {
@@ -17,30 +89,265 @@ impl Interval {
     ///
     /// Positive when `frequency_0` < `frequency_1`.<br>
     /// Negative when `frequency_0` > `frequency_1`.
+    #[must_use]
+    #[track_caller]
     pub fn new(frequency_0: f64, frequency_1: f64) -> Self {
         let cents = 1200.0 * (frequency_1 / frequency_0).ln() / (2.0_f64).ln();
-        let cents = Cents(NotNan::new(cents).unwrap());
+        let cents = Cents::new(cents).expect("interval between frequencies is not finite");
+        Interval { cents }
+    }
+
+    /// Create new interval of given size.
+    #[must_use]
+    pub fn from_cents(cents: Cents) -> Self {
         Interval { cents }
     }
 
+    /// Syntonic comma - difference between four pure fifths and two octaves plus pure major third
+    /// (ratio 81:80, about 21.5 cents).
+    #[must_use]
+    pub fn syntonic_comma() -> Self {
+        Interval::new(80.0, 81.0)
+    }
+
+    /// Pythagorean comma - difference between twelve pure fifths and seven octaves
+    /// (ratio 531441:524288, about 23.5 cents).
+    #[must_use]
+    pub fn pythagorean_comma() -> Self {
+        Interval::new(524288.0, 531441.0)
+    }
+
     /// Get interval in cents.
     ///
     /// 100 cents = 1 semitone.
+    #[must_use]
     pub fn cents(&self) -> Cents {
         self.cents
     }
+
+    /// Get [spelling-free name](NamedInterval::from_semitones) of interval
+    /// nearest to this one (ignoring direction), along with deviation from it
+    /// (or `None` if interval is larger than an octave).
+    #[must_use]
+    #[track_caller]
+    pub fn nearest_named(&self) -> Option<(NamedInterval, Cents)> {
+        let cents = self.cents.abs().value();
+        let semitones = (cents / 100.0).round();
+        let named = NamedInterval::from_semitones(semitones as u8)?;
+        Some((
+            named,
+            Cents::new(cents - 100.0 * semitones).expect("interval is infinite"),
+        ))
+    }
+
+    /// Check if interval (ignoring direction) is within `tolerance` from a tritone (600 cents).
+    #[must_use]
+    pub fn is_tritone(&self, tolerance: Cents) -> bool {
+        (self.cents.abs().value() - 600.0).abs() <= tolerance.value()
+    }
+
+    /// Describe interval in English (for example: "up a perfect fifth",
+    /// "down a minor third and 2 octaves" or "up 3 semitones").
+    ///
+    /// Interval is split into whole octaves and [spelling-free](NamedInterval::from_semitones)
+    /// named interval smaller than an octave (parts of zero size are omitted),
+    /// deviation from them is rounded to whole cents (for example: "up a perfect fifth plus 4 cents").
+    /// Interval smaller than half of a cent is "unison", regardless of direction.
+    #[must_use]
+    pub fn to_prose(&self, options: ProseOptions) -> String {
+        let cents = self.cents.value();
+        let semitones = (cents.abs() / 100.0).round();
+        let residual = (cents.abs() - 100.0 * semitones).round();
+        let semitones = semitones as u64;
+
+        let mut size = if semitones == 0 {
+            String::new()
+        } else if options.semitones {
+            plural(semitones, "semitone")
+        } else {
+            let octaves = match semitones / 12 {
+                0 => None,
+                1 => Some("an octave".to_string()),
+                octaves => Some(plural(octaves, "octave")),
+            };
+            let simple = NamedInterval::from_semitones((semitones % 12) as u8)
+                .filter(|named| *named != NamedInterval::PerfectUnison)
+                .map(|named| {
+                    if options.abbreviated {
+                        named.to_string()
+                    } else {
+                        with_article(named.full_name())
+                    }
+                });
+            match (simple, octaves) {
+                (Some(simple), Some(octaves)) => format!("{} and {}", simple, octaves),
+                (Some(part), None) | (None, Some(part)) => part,
+                (None, None) => unreachable!(),
+            }
+        };
+        if options.cents && residual != 0.0 {
+            let residual_cents = plural(residual.abs() as u64, "cent");
+            size = match (size.is_empty(), residual > 0.0) {
+                (true, _) => residual_cents,
+                (false, true) => format!("{} plus {}", size, residual_cents),
+                (false, false) => format!("{} minus {}", size, residual_cents),
+            };
+        }
+        if size.is_empty() {
+            return "unison".to_string();
+        }
+
+        let ascending = cents > 0.0;
+        match (options.direction, ascending) {
+            (ProseDirection::UpDown, true) => format!("up {}", size),
+            (ProseDirection::UpDown, false) => format!("down {}", size),
+            (ProseDirection::AboveBelow, true) => format!("{} above", size),
+            (ProseDirection::AboveBelow, false) => format!("{} below", size),
+            (ProseDirection::Omit, _) => size,
+        }
+    }
+}
+
+/// Options of describing interval [in English](Interval::to_prose).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProseOptions {
+    /// Use abbreviated interval names (for example: "m3" instead of "a minor third").
+    ///
+    /// Off by default.
+    pub abbreviated: bool,
+    /// Describe size in semitones instead of octaves and named intervals
+    /// (for example: "15 semitones" instead of "a minor third and an octave").
+    ///
+    /// Off by default.
+    pub semitones: bool,
+    /// Include deviation from whole semitones in cents (for example: "plus 4 cents").
+    ///
+    /// On by default.
+    pub cents: bool,
+    /// Phrasing of interval direction.
+    ///
+    /// By default: [ProseDirection::UpDown].
+    pub direction: ProseDirection,
+}
+
+impl Default for ProseOptions {
+    fn default() -> Self {
+        ProseOptions {
+            abbreviated: false,
+            semitones: false,
+            cents: true,
+            direction: ProseDirection::UpDown,
+        }
+    }
+}
+
+/// Phrasing of interval direction in [prose](Interval::to_prose).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ProseDirection {
+    /// "up" or "down" before size (for example: "up a minor third").
+    #[default]
+    UpDown,
+    /// "above" or "below" after size (for example: "a minor third below").
+    AboveBelow,
+    /// Direction is omitted (for example: "a minor third").
+    Omit,
+}
+
+/// Count with noun in singular or plural form (for example: "1 cent", "2 cents").
+fn plural(count: u64, noun: &str) -> String {
+    if count == 1 {
+        format!("{} {}", count, noun)
+    } else {
+        format!("{} {}s", count, noun)
+    }
+}
+
+/// Phrase with indefinite article (for example: "a minor third", "an augmented fifth").
+fn with_article(phrase: &str) -> String {
+    if phrase.starts_with(['a', 'e', 'i', 'o', 'u']) {
+        format!("an {}", phrase)
+    } else {
+        format!("a {}", phrase)
+    }
+}
+
+impl Sum for Interval {
+    /// Stack intervals - sum of their sizes in cents, starting from unison.
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(
+            Interval::from_cents(Cents::new_unchecked(0.0)),
+            |sum, interval| Interval::from_cents(sum.cents + interval.cents),
+        )
+    }
+}
+
+impl<'a> Sum<&'a Interval> for Interval {
+    /// Stack intervals - sum of their sizes in cents, starting from unison.
+    fn sum<I: Iterator<Item = &'a Interval>>(iter: I) -> Self {
+        iter.map(|interval| Interval::from_cents(interval.cents))
+            .sum()
+    }
 }
 
 /// Interval in cents.
 ///
 /// 100 cents = 1 semitone.
+///
+/// Value is always finite, so cents are totally ordered ([Eq] and [Ord])
+/// and arithmetic on them never panics.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
-pub struct Cents(pub NotNan<f64>);
+pub struct Cents(NotNan<f64>);
 
 impl Cents {
+    /// Create cents of given value.
+    ///
+    /// Returns `None` if value is NaN or infinite.
+    #[must_use]
+    pub fn new(value: f64) -> Option<Cents> {
+        if value.is_finite() {
+            NotNan::new(value).ok().map(Cents)
+        } else {
+            None
+        }
+    }
+
+    /// Create cents of value known to be finite.
+    ///
+    /// Panics if value is NaN or infinite.
+    #[must_use]
+    #[track_caller]
+    pub(crate) fn new_unchecked(value: f64) -> Cents {
+        Cents::new(value).expect("invariant")
+    }
+
+    /// Get value.
+    #[must_use]
+    pub fn value(&self) -> f64 {
+        *self.0
+    }
+
     /// Get absolute (non-negative) value.
+    #[must_use]
     pub fn abs(self) -> Cents {
-        Cents(NotNan::new(self.0.abs()).unwrap())
+        Cents(NotNan::new(self.0.abs()).expect("absolute value of a number is a number"))
+    }
+}
+
+impl Add for Cents {
+    type Output = Cents;
+
+    /// Add cents, saturating at the largest finite values.
+    fn add(self, rhs: Self) -> Self::Output {
+        Cents::new_unchecked((self.value() + rhs.value()).clamp(f64::MIN, f64::MAX))
+    }
+}
+
+impl Sub for Cents {
+    type Output = Cents;
+
+    /// Subtract cents, saturating at the largest finite values.
+    fn sub(self, rhs: Self) -> Self::Output {
+        Cents::new_unchecked((self.value() - rhs.value()).clamp(f64::MIN, f64::MAX))
     }
 }
 
@@ -49,3 +356,487 @@ impl Display for Cents {
         write!(f, "{}", self.0)
     }
 }
+
+/// Named (diatonic) interval, up to an octave.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NamedInterval {
+    /// Perfect unison - P1.
+    PerfectUnison,
+    /// Minor second - m2.
+    MinorSecond,
+    /// Major second - M2.
+    MajorSecond,
+    /// Augmented second - A2.
+    AugmentedSecond,
+    /// Minor third - m3.
+    MinorThird,
+    /// Major third - M3.
+    MajorThird,
+    /// Diminished fourth - d4.
+    DiminishedFourth,
+    /// Perfect fourth - P4.
+    PerfectFourth,
+    /// Tritone (6 semitones) - TT.
+    ///
+    /// Used where interval spelling isn't known - see [NamedInterval::from_semitones].
+    /// When spelling is needed it's treated as [augmented fourth](NamedInterval::AugmentedFourth).
+    Tritone,
+    /// Augmented fourth - A4.
+    AugmentedFourth,
+    /// Diminished fifth - d5.
+    DiminishedFifth,
+    /// Perfect fifth - P5.
+    PerfectFifth,
+    /// Augmented fifth - A5.
+    AugmentedFifth,
+    /// Minor sixth - m6.
+    MinorSixth,
+    /// Major sixth - M6.
+    MajorSixth,
+    /// Diminished seventh - d7.
+    DiminishedSeventh,
+    /// Minor seventh - m7.
+    MinorSeventh,
+    /// Major seventh - M7.
+    MajorSeventh,
+    /// Perfect octave - P8.
+    PerfectOctave,
+}
+
+impl NamedInterval {
+    /// All named intervals, in ascending order.
+    pub const ALL: [NamedInterval; 19] = [
+        NamedInterval::PerfectUnison,
+        NamedInterval::MinorSecond,
+        NamedInterval::MajorSecond,
+        NamedInterval::AugmentedSecond,
+        NamedInterval::MinorThird,
+        NamedInterval::MajorThird,
+        NamedInterval::DiminishedFourth,
+        NamedInterval::PerfectFourth,
+        NamedInterval::Tritone,
+        NamedInterval::AugmentedFourth,
+        NamedInterval::DiminishedFifth,
+        NamedInterval::PerfectFifth,
+        NamedInterval::AugmentedFifth,
+        NamedInterval::MinorSixth,
+        NamedInterval::MajorSixth,
+        NamedInterval::DiminishedSeventh,
+        NamedInterval::MinorSeventh,
+        NamedInterval::MajorSeventh,
+        NamedInterval::PerfectOctave,
+    ];
+
+    /// Size of interval in semitones.
+    #[must_use]
+    pub fn semitones(&self) -> u8 {
+        match self {
+            NamedInterval::PerfectUnison => 0,
+            NamedInterval::MinorSecond => 1,
+            NamedInterval::MajorSecond => 2,
+            NamedInterval::AugmentedSecond => 3,
+            NamedInterval::MinorThird => 3,
+            NamedInterval::MajorThird => 4,
+            NamedInterval::DiminishedFourth => 4,
+            NamedInterval::PerfectFourth => 5,
+            NamedInterval::Tritone => 6,
+            NamedInterval::AugmentedFourth => 6,
+            NamedInterval::DiminishedFifth => 6,
+            NamedInterval::PerfectFifth => 7,
+            NamedInterval::AugmentedFifth => 8,
+            NamedInterval::MinorSixth => 8,
+            NamedInterval::MajorSixth => 9,
+            NamedInterval::DiminishedSeventh => 9,
+            NamedInterval::MinorSeventh => 10,
+            NamedInterval::MajorSeventh => 11,
+            NamedInterval::PerfectOctave => 12,
+        }
+    }
+
+    /// Get spelling-free name of interval spanning given number of semitones
+    /// (or `None` if it's larger than an octave).
+    ///
+    /// Intervals are minor, major or perfect, except 6 semitones which is [Tritone](NamedInterval::Tritone).
+    /// Result is consistent with [inversion](NamedInterval::invert):
+    /// `from_semitones(12 - n)` is `from_semitones(n)` inverted.
+    #[must_use]
+    pub fn from_semitones(semitones: u8) -> Option<NamedInterval> {
+        match semitones {
+            0 => Some(NamedInterval::PerfectUnison),
+            1 => Some(NamedInterval::MinorSecond),
+            2 => Some(NamedInterval::MajorSecond),
+            3 => Some(NamedInterval::MinorThird),
+            4 => Some(NamedInterval::MajorThird),
+            5 => Some(NamedInterval::PerfectFourth),
+            6 => Some(NamedInterval::Tritone),
+            7 => Some(NamedInterval::PerfectFifth),
+            8 => Some(NamedInterval::MinorSixth),
+            9 => Some(NamedInterval::MajorSixth),
+            10 => Some(NamedInterval::MinorSeventh),
+            11 => Some(NamedInterval::MajorSeventh),
+            12 => Some(NamedInterval::PerfectOctave),
+            _ => None,
+        }
+    }
+
+    /// Get inverted interval - the one that added to this one makes an octave.
+    ///
+    /// Unison and octave invert to each other, [Tritone](NamedInterval::Tritone) inverts to itself.
+    #[must_use]
+    pub fn invert(&self) -> NamedInterval {
+        match self {
+            NamedInterval::PerfectUnison => NamedInterval::PerfectOctave,
+            NamedInterval::MinorSecond => NamedInterval::MajorSeventh,
+            NamedInterval::MajorSecond => NamedInterval::MinorSeventh,
+            NamedInterval::AugmentedSecond => NamedInterval::DiminishedSeventh,
+            NamedInterval::MinorThird => NamedInterval::MajorSixth,
+            NamedInterval::MajorThird => NamedInterval::MinorSixth,
+            NamedInterval::DiminishedFourth => NamedInterval::AugmentedFifth,
+            NamedInterval::PerfectFourth => NamedInterval::PerfectFifth,
+            NamedInterval::Tritone => NamedInterval::Tritone,
+            NamedInterval::AugmentedFourth => NamedInterval::DiminishedFifth,
+            NamedInterval::DiminishedFifth => NamedInterval::AugmentedFourth,
+            NamedInterval::PerfectFifth => NamedInterval::PerfectFourth,
+            NamedInterval::AugmentedFifth => NamedInterval::DiminishedFourth,
+            NamedInterval::MinorSixth => NamedInterval::MajorThird,
+            NamedInterval::MajorSixth => NamedInterval::MinorThird,
+            NamedInterval::DiminishedSeventh => NamedInterval::AugmentedSecond,
+            NamedInterval::MinorSeventh => NamedInterval::MajorSecond,
+            NamedInterval::MajorSeventh => NamedInterval::MinorSecond,
+            NamedInterval::PerfectOctave => NamedInterval::PerfectUnison,
+        }
+    }
+
+    /// Check if interval spans a tritone (augmented fourth, diminished fifth or [Tritone](NamedInterval::Tritone)).
+    #[must_use]
+    pub fn is_tritone(&self) -> bool {
+        self.semitones() == 6
+    }
+
+    /// Frequency ratio `(numerator, denominator)` of the interval in just intonation (5-limit).
+    #[must_use]
+    pub fn just_ratio(&self) -> (u32, u32) {
+        match self {
+            NamedInterval::PerfectUnison => (1, 1),
+            NamedInterval::MinorSecond => (16, 15),
+            NamedInterval::MajorSecond => (9, 8),
+            NamedInterval::AugmentedSecond => (75, 64),
+            NamedInterval::MinorThird => (6, 5),
+            NamedInterval::MajorThird => (5, 4),
+            NamedInterval::DiminishedFourth => (32, 25),
+            NamedInterval::PerfectFourth => (4, 3),
+            NamedInterval::Tritone | NamedInterval::AugmentedFourth => (45, 32),
+            NamedInterval::DiminishedFifth => (64, 45),
+            NamedInterval::PerfectFifth => (3, 2),
+            NamedInterval::AugmentedFifth => (25, 16),
+            NamedInterval::MinorSixth => (8, 5),
+            NamedInterval::MajorSixth => (5, 3),
+            NamedInterval::DiminishedSeventh => (128, 75),
+            NamedInterval::MinorSeventh => (9, 5),
+            NamedInterval::MajorSeventh => (15, 8),
+            NamedInterval::PerfectOctave => (2, 1),
+        }
+    }
+
+    /// Cents that have to be added to equal-tempered interval to make it [just](NamedInterval::just_ratio).
+    ///
+    /// For example: about -13.7 cents for major third, about +2 cents for perfect fifth.
+    #[must_use]
+    pub fn just_adjustment(&self) -> Cents {
+        let (numerator, denominator) = self.just_ratio();
+        let just = Interval::new(denominator as f64, numerator as f64).cents();
+        Cents::new(just.value() - 100.0 * self.semitones() as f64)
+            .expect("just interval sizes are finite")
+    }
+
+    /// Interval number - number of note letters spanned by the interval
+    /// (1 for unison, 2 for second, ..., 8 for octave).
+    ///
+    /// [Tritone](NamedInterval::Tritone) is treated as augmented fourth.
+    #[must_use]
+    pub fn number(&self) -> u8 {
+        match self {
+            NamedInterval::PerfectUnison => 1,
+            NamedInterval::MinorSecond
+            | NamedInterval::MajorSecond
+            | NamedInterval::AugmentedSecond => 2,
+            NamedInterval::MinorThird | NamedInterval::MajorThird => 3,
+            NamedInterval::DiminishedFourth
+            | NamedInterval::PerfectFourth
+            | NamedInterval::Tritone
+            | NamedInterval::AugmentedFourth => 4,
+            NamedInterval::DiminishedFifth
+            | NamedInterval::PerfectFifth
+            | NamedInterval::AugmentedFifth => 5,
+            NamedInterval::MinorSixth | NamedInterval::MajorSixth => 6,
+            NamedInterval::DiminishedSeventh
+            | NamedInterval::MinorSeventh
+            | NamedInterval::MajorSeventh => 7,
+            NamedInterval::PerfectOctave => 8,
+        }
+    }
+
+    /// Full name of the interval (for example: "minor third").
+    fn full_name(&self) -> &'static str {
+        match self {
+            NamedInterval::PerfectUnison => "perfect unison",
+            NamedInterval::MinorSecond => "minor second",
+            NamedInterval::MajorSecond => "major second",
+            NamedInterval::AugmentedSecond => "augmented second",
+            NamedInterval::MinorThird => "minor third",
+            NamedInterval::MajorThird => "major third",
+            NamedInterval::DiminishedFourth => "diminished fourth",
+            NamedInterval::PerfectFourth => "perfect fourth",
+            NamedInterval::Tritone => "tritone",
+            NamedInterval::AugmentedFourth => "augmented fourth",
+            NamedInterval::DiminishedFifth => "diminished fifth",
+            NamedInterval::PerfectFifth => "perfect fifth",
+            NamedInterval::AugmentedFifth => "augmented fifth",
+            NamedInterval::MinorSixth => "minor sixth",
+            NamedInterval::MajorSixth => "major sixth",
+            NamedInterval::DiminishedSeventh => "diminished seventh",
+            NamedInterval::MinorSeventh => "minor seventh",
+            NamedInterval::MajorSeventh => "major seventh",
+            NamedInterval::PerfectOctave => "perfect octave",
+        }
+    }
+}
+
+impl Display for NamedInterval {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let quality = match self {
+            NamedInterval::PerfectUnison
+            | NamedInterval::PerfectFourth
+            | NamedInterval::PerfectFifth
+            | NamedInterval::PerfectOctave => 'P',
+            NamedInterval::MinorSecond
+            | NamedInterval::MinorThird
+            | NamedInterval::MinorSixth
+            | NamedInterval::MinorSeventh => 'm',
+            NamedInterval::MajorSecond
+            | NamedInterval::MajorThird
+            | NamedInterval::MajorSixth
+            | NamedInterval::MajorSeventh => 'M',
+            NamedInterval::AugmentedSecond
+            | NamedInterval::AugmentedFourth
+            | NamedInterval::AugmentedFifth => 'A',
+            NamedInterval::DiminishedFourth
+            | NamedInterval::DiminishedFifth
+            | NamedInterval::DiminishedSeventh => 'd',
+            NamedInterval::Tritone => return write!(f, "TT"),
+        };
+        write!(f, "{}{}", quality, self.number())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PITCHES;
+
+    #[test]
+    fn lattice_coordinates_of_just_ratios() {
+        assert_eq!(lattice_coordinates(5, 4), Some((0, 1)));
+        assert_eq!(lattice_coordinates(3, 2), Some((1, 0)));
+        assert_eq!(lattice_coordinates(9, 5), Some((2, -1)));
+        assert_eq!(lattice_coordinates(2, 1), Some((0, 0)));
+        assert_eq!(lattice_coordinates(7, 4), None);
+        assert_eq!(lattice_coordinates(0, 1), None);
+    }
+
+    #[test]
+    fn just_adjustments_of_third_and_fifth() {
+        let third = NamedInterval::MajorThird.just_adjustment().value();
+        assert!((third - -13.686).abs() < 0.001, "{}", third);
+        assert_eq!(third.round(), -14.0);
+        let fifth = NamedInterval::PerfectFifth.just_adjustment().value();
+        assert!((fifth - 1.955).abs() < 0.001, "{}", fifth);
+        assert_eq!(NamedInterval::PerfectOctave.just_adjustment().value(), 0.0);
+    }
+
+    #[test]
+    fn tritone_is_symmetric() {
+        let tolerance = Cents::new(0.05).unwrap();
+        let tritone = |cents: f64| Interval::from_cents(Cents::new(cents).unwrap());
+        assert!(tritone(600.0).is_tritone(tolerance));
+        assert!(tritone(-600.0).is_tritone(tolerance));
+        assert!(!tritone(599.9).is_tritone(tolerance));
+        assert!(!tritone(600.1).is_tritone(tolerance));
+        assert!(!tritone(-600.1).is_tritone(tolerance));
+        let tolerance = Cents::new(0.2).unwrap();
+        assert!(tritone(599.9).is_tritone(tolerance));
+        assert!(tritone(600.1).is_tritone(tolerance));
+        assert!(tritone(600.0).is_tritone(Cents::new(0.0).unwrap()));
+
+        for cents in [600.0, 599.9, 600.1, -600.0, -599.9, -600.1] {
+            let (named, deviation) = tritone(cents).nearest_named().unwrap();
+            assert_eq!(named, NamedInterval::Tritone);
+            assert!((deviation.value() - (cents.abs() - 600.0)).abs() < 1e-9);
+        }
+
+        assert_eq!(NamedInterval::Tritone.invert(), NamedInterval::Tritone);
+        assert_eq!(
+            NamedInterval::PerfectUnison.invert(),
+            NamedInterval::PerfectOctave
+        );
+        assert_eq!(
+            NamedInterval::PerfectOctave.invert(),
+            NamedInterval::PerfectUnison
+        );
+        for interval in NamedInterval::ALL {
+            assert_eq!(interval.invert().invert(), interval);
+            assert_eq!(interval.semitones() + interval.invert().semitones(), 12);
+            assert_eq!(interval.is_tritone(), interval.invert().is_tritone());
+        }
+        for semitones in 0..=12 {
+            let named = NamedInterval::from_semitones(semitones).unwrap();
+            assert_eq!(named.semitones(), semitones);
+            assert_eq!(
+                NamedInterval::from_semitones(12 - semitones),
+                Some(named.invert())
+            );
+        }
+        assert_eq!(NamedInterval::from_semitones(13), None);
+    }
+
+    #[test]
+    fn commas() {
+        let syntonic = Interval::syntonic_comma().cents().value();
+        assert!((syntonic - 21.506).abs() < 0.001, "{}", syntonic);
+        let pythagorean = Interval::pythagorean_comma().cents().value();
+        assert!((pythagorean - 23.460).abs() < 0.001, "{}", pythagorean);
+        // twelve pure fifths exceed seven octaves by Pythagorean comma
+        let fifths = 12.0 * Interval::new(2.0, 3.0).cents().value();
+        assert!((fifths - 8400.0 - pythagorean).abs() < 1e-9);
+    }
+
+    #[test]
+    fn third_and_fifth_minus_third_sum_to_fifth() {
+        let third = Interval::new(4.0, 5.0);
+        let fifth = Interval::new(2.0, 3.0);
+        let rest = Interval::from_cents(fifth.cents() - third.cents());
+        let intervals = [third, rest];
+        let sum: Interval = intervals.iter().sum();
+        assert!((sum.cents().value() - fifth.cents().value()).abs() < 1e-9);
+        let sum: Interval = intervals.into_iter().sum();
+        assert!((sum.cents().value() - fifth.cents().value()).abs() < 1e-9);
+        assert_eq!(
+            std::iter::empty::<Interval>()
+                .sum::<Interval>()
+                .cents()
+                .value(),
+            0.0
+        );
+    }
+
+    #[test]
+    fn prose_descriptions() {
+        let prose = |cents: f64, options: ProseOptions| {
+            Interval::from_cents(Cents::new(cents).unwrap()).to_prose(options)
+        };
+        let default = ProseOptions::default();
+        let snapshots = [
+            (0.0, "unison"),
+            (-0.4, "unison"),
+            (0.6, "up 1 cent"),
+            (-30.0, "down 30 cents"),
+            (100.0, "up a minor second"),
+            (600.0, "up a tritone"),
+            (704.0, "up a perfect fifth plus 4 cents"),
+            (650.0, "up a perfect fifth minus 50 cents"),
+            (1200.0, "up an octave"),
+            (-2400.0, "down 2 octaves"),
+            (-1500.0, "down a minor third and an octave"),
+            (-2715.0, "down a minor third and 2 octaves plus 15 cents"),
+        ];
+        for (cents, expected) in snapshots {
+            assert_eq!(prose(cents, default), expected, "{} cents", cents);
+        }
+
+        let abbreviated = ProseOptions {
+            abbreviated: true,
+            ..default
+        };
+        assert_eq!(prose(1500.0, abbreviated), "up m3 and an octave");
+        assert_eq!(prose(-600.0, abbreviated), "down TT");
+        let semitones = ProseOptions {
+            semitones: true,
+            ..default
+        };
+        assert_eq!(prose(300.0, semitones), "up 3 semitones");
+        assert_eq!(prose(-1496.0, semitones), "down 15 semitones minus 4 cents");
+        assert_eq!(prose(100.0, semitones), "up 1 semitone");
+        let without_cents = ProseOptions {
+            cents: false,
+            ..default
+        };
+        assert_eq!(prose(704.0, without_cents), "up a perfect fifth");
+        assert_eq!(prose(4.0, without_cents), "unison");
+        let above_below = ProseOptions {
+            direction: ProseDirection::AboveBelow,
+            ..default
+        };
+        assert_eq!(prose(-300.0, above_below), "a minor third below");
+        assert_eq!(prose(1200.0, above_below), "an octave above");
+        let omit = ProseOptions {
+            direction: ProseDirection::Omit,
+            ..default
+        };
+        assert_eq!(prose(-704.0, omit), "a perfect fifth plus 4 cents");
+    }
+
+    #[test]
+    fn bisected_octave_lands_on_tritone() {
+        let (midpoint, deviation) = bisect(PITCHES[57], PITCHES[69]);
+        // pitch frequencies are rounded to 0.01 Hz
+        assert!((midpoint - 440.0 * std::f64::consts::SQRT_2).abs() < 0.01);
+        assert_eq!(Pitch::from_frequency(midpoint), Some(PITCHES[63]));
+        assert!(deviation.value().abs() < 0.05);
+        assert_eq!(bisect(PITCHES[69], PITCHES[57]).0, midpoint);
+
+        // C₄ and E₄ - midpoint is D₄
+        let (midpoint, deviation) = bisect(PITCHES[48], PITCHES[52]);
+        assert_eq!(Pitch::from_frequency(midpoint), Some(PITCHES[50]));
+        assert!(deviation.value().abs() < 0.05);
+        // C₄ and G₄ - midpoint is 350 cents above C₄, half-way between D♯₄ and E₄
+        let (midpoint, deviation) = bisect(PITCHES[48], PITCHES[55]);
+        assert!((deviation.value().abs() - 50.0).abs() < 0.05);
+        assert!(
+            (Interval::new(PITCHES[48].frequency(), midpoint)
+                .cents()
+                .value()
+                - 350.0)
+                .abs()
+                < 0.05
+        );
+    }
+
+    #[test]
+    fn monochord_divisions() {
+        assert_eq!(monochord_frequency(220.0, 0.5), 440.0);
+        assert_eq!(monochord_frequency(220.0, 1.0), 220.0);
+        let fifth = Interval::new(220.0, monochord_frequency(220.0, 2.0 / 3.0));
+        assert!((fifth.cents().value() - 701.955).abs() < 0.001);
+        assert_eq!(
+            fifth.nearest_named().unwrap().0,
+            NamedInterval::PerfectFifth
+        );
+        let fourth = Interval::new(220.0, monochord_frequency(220.0, 3.0 / 4.0));
+        assert!((fourth.cents().value() - 498.045).abs() < 0.001);
+    }
+
+    #[test]
+    fn tenney_heights() {
+        let height = tenney_height;
+        assert!(height(3, 2) < height(7, 5));
+        assert!((height(3, 2) - 6.0_f64.log2()).abs() < 1e-12);
+        assert!((height(7, 5) - 35.0_f64.log2()).abs() < 1e-12);
+        assert_eq!(height(1, 1), 0.0);
+        assert_eq!(height(2, 1), 1.0);
+        // ratio isn't reduced to lowest terms
+        assert!((height(6, 4) - 24.0_f64.log2()).abs() < 1e-12);
+        assert!((height(u32::MAX, u32::MAX) - 64.0).abs() < 1e-9);
+        assert_eq!(height(0, 1), f64::INFINITY);
+        assert_eq!(height(1, 0), f64::INFINITY);
+    }
+}
@@ -0,0 +1,527 @@
+//! Keys and key signatures.
+
+use std::fmt::Display;
+
+use crate::{Accidental, Error, Letter, PitchClass};
+
+/// Key signature - set of sharps or flats, stored as position on the circle of fifths.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeySignature {
+    fifths: i8,
+}
+
+impl KeySignature {
+    /// Create key signature with given number of sharps (positive) or flats (negative).
+    ///
+    /// Fails if there are more than seven of them.
+    pub fn new(fifths: i8) -> Result<Self, Error> {
+        if !(-7..=7).contains(&fifths) {
+            return Err(Error::IncorrectKeySignature);
+        }
+        Ok(KeySignature { fifths })
+    }
+
+    /// Key signature of major key with given tonic (for example: 2 sharps for D major).
+    ///
+    /// Fails if key would need more than seven sharps or flats (like D♯ major).
+    pub fn from_major_tonic(letter: Letter, accidental: Accidental) -> Result<Self, Error> {
+        KeySignature::new(fifths_position(letter) + 7 * accidental.alteration())
+    }
+
+    /// Key signature of minor key with given tonic (for example: 1 flat for D minor).
+    ///
+    /// Fails if key would need more than seven sharps or flats (like G♭ minor).
+    pub fn from_minor_tonic(letter: Letter, accidental: Accidental) -> Result<Self, Error> {
+        KeySignature::new(fifths_position(letter) + 7 * accidental.alteration() - 3)
+    }
+
+    /// Get number of sharps (positive) or flats (negative).
+    #[must_use]
+    pub fn fifths(&self) -> i8 {
+        self.fifths
+    }
+
+    /// Number of sharps or flats (for example: 0 for C major, 6 for F♯ major).
+    #[must_use]
+    pub fn accidental_count(&self) -> u8 {
+        self.fifths.unsigned_abs()
+    }
+
+    /// Tonic of major key with this signature.
+    #[must_use]
+    pub fn major_tonic(&self) -> (Letter, Accidental) {
+        letter_at_position(self.fifths)
+    }
+
+    /// Tonic of minor key with this signature.
+    #[must_use]
+    pub fn minor_tonic(&self) -> (Letter, Accidental) {
+        letter_at_position(self.fifths + 3)
+    }
+
+    /// Accidental the signature puts on notes with given letter.
+    #[must_use]
+    pub fn accidental(&self, letter: Letter) -> Accidental {
+        // sharps are added in order F C G D A E B, flats in reverse order
+        let position = fifths_position(letter);
+        if position + 1 < self.fifths {
+            Accidental::Sharp
+        } else if 5 - position < -self.fifths {
+            Accidental::Flat
+        } else {
+            Accidental::None
+        }
+    }
+
+    /// Get signature of enharmonically equivalent keys
+    /// (for example: 5 flats of D♭ major for 7 sharps of C♯ major).
+    ///
+    /// Returns `None` if equivalent keys would need more than seven sharps or flats
+    /// (for example: for C major).
+    #[must_use]
+    pub fn enharmonic_equivalent(&self) -> Option<KeySignature> {
+        let fifths = if self.fifths > 0 {
+            self.fifths - 12
+        } else {
+            self.fifths + 12
+        };
+        KeySignature::new(fifths).ok()
+    }
+}
+
+impl Display for KeySignature {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.fifths {
+            0 => write!(f, "♮"),
+            fifths if fifths > 0 => write!(f, "{}{}", fifths, Accidental::Sharp),
+            fifths => write!(f, "{}{}", -fifths, Accidental::Flat),
+        }
+    }
+}
+
+/// Mode of a key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    /// Major key.
+    Major,
+    /// Minor key.
+    Minor,
+}
+
+impl Display for Mode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Mode::Major => write!(f, "major"),
+            Mode::Minor => write!(f, "minor"),
+        }
+    }
+}
+
+/// Major or minor key - one of 30 keys with up to seven sharps or flats.
+///
+/// Enharmonically equivalent keys (like C♯ major and D♭ major) are distinct keys.
+///
+/// Key is a small immutable [Copy] value, safe to share between threads.
+/// Its queries (apart from ones returning [Vec]) neither allocate nor lock.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Key {
+    signature: KeySignature,
+    mode: Mode,
+}
+
+impl Key {
+    /// Create key with given signature and mode.
+    #[must_use]
+    pub fn new(signature: KeySignature, mode: Mode) -> Self {
+        Key { signature, mode }
+    }
+
+    /// Create key with given tonic and mode.
+    ///
+    /// Fails if key would need more than seven sharps or flats (like D♯ major).
+    pub fn from_tonic(letter: Letter, accidental: Accidental, mode: Mode) -> Result<Self, Error> {
+        let signature = match mode {
+            Mode::Major => KeySignature::from_major_tonic(letter, accidental)?,
+            Mode::Minor => KeySignature::from_minor_tonic(letter, accidental)?,
+        };
+        Ok(Key { signature, mode })
+    }
+
+    /// Get key signature.
+    #[must_use]
+    pub fn signature(&self) -> KeySignature {
+        self.signature
+    }
+
+    /// Get mode.
+    #[must_use]
+    pub fn mode(&self) -> Mode {
+        self.mode
+    }
+
+    /// Get tonic.
+    #[must_use]
+    pub fn tonic(&self) -> (Letter, Accidental) {
+        match self.mode {
+            Mode::Major => self.signature.major_tonic(),
+            Mode::Minor => self.signature.minor_tonic(),
+        }
+    }
+
+    /// Pitch class of tonic.
+    #[must_use]
+    pub fn tonic_class(&self) -> PitchClass {
+        let fifths = match self.mode {
+            Mode::Major => self.signature.fifths(),
+            Mode::Minor => self.signature.fifths() + 3,
+        };
+        PitchClass::new((7 * fifths as i16).rem_euclid(12) as u8)
+    }
+
+    /// All keys of given mode with tonic of given pitch class (for example: F♯ major and G♭ major for 6),
+    /// ordered by number of sharps or flats, fewest first.
+    ///
+    /// When two keys have the same number of them (F♯ major and G♭ major,
+    /// D♯ minor and E♭ minor), the one with sharps goes first.
+    #[must_use]
+    pub fn spellings_for_pitch_class(class: PitchClass, mode: Mode) -> Vec<Key> {
+        let mut keys: Vec<Key> = (-7..=7)
+            .map(|fifths| Key::new(KeySignature { fifths }, mode))
+            .filter(|key| key.tonic_class() == class)
+            .collect();
+        keys.sort_by_key(|key| (key.signature.accidental_count(), -key.signature.fifths()));
+        keys
+    }
+
+    /// Key of the same mode with tonic transposed by given number of semitones,
+    /// spelled the way that stays on the same side of the circle of fifths as the original key
+    /// (sharps for keys with sharps, flats for keys with flats) when possible
+    /// (for example: G♭ major for F major transposed by 1, but F♯ major for D major transposed by 4).
+    ///
+    /// Otherwise (and for original keys without sharps and flats) the key with
+    /// the fewest sharps or flats is chosen - see [Key::spellings_for_pitch_class].
+    #[must_use]
+    pub fn preferred_after_transposition(original: &Key, semitones: i8) -> Key {
+        let class = original.tonic_class().number() as i16 + semitones as i16;
+        let spellings = Key::spellings_for_pitch_class(
+            PitchClass::new(class.rem_euclid(12) as u8),
+            original.mode,
+        );
+        let side = original.signature.fifths().signum();
+        spellings
+            .iter()
+            .find(|key| side != 0 && key.signature.fifths().signum() == side)
+            .or_else(|| spellings.first())
+            .copied()
+            .expect("invariant")
+    }
+
+    /// Distance between keys on the circle of fifths - difference of numbers of sharps/flats
+    /// of their signatures, going around the circle the shorter way (at most 6).
+    ///
+    /// So relative keys (like C major and A minor) and enharmonically equivalent keys
+    /// (like C♯ major and D♭ major) are 0 apart, C major and E major are 4 apart.
+    #[must_use]
+    pub fn distance(&self, other: &Key) -> u8 {
+        let difference = (self.signature.fifths() - other.signature.fifths()).rem_euclid(12) as u8;
+        difference.min(12 - difference)
+    }
+
+    /// Closely related keys - keys with one sharp/flat more or less (in the same mode),
+    /// relative key and parallel key (in this order, skipping keys with more than seven sharps or flats).
+    #[must_use]
+    pub fn neighbours(&self) -> Vec<Key> {
+        self.neighbours_iter().collect()
+    }
+
+    /// Iterator over [closely related keys](Key::neighbours), without collecting them.
+    pub fn neighbours_iter(&self) -> impl Iterator<Item = Key> {
+        let fifths = self.signature.fifths();
+        let (other_mode, parallel_fifths) = match self.mode {
+            Mode::Major => (Mode::Minor, fifths - 3),
+            Mode::Minor => (Mode::Major, fifths + 3),
+        };
+        [
+            (fifths - 1, self.mode),
+            (fifths + 1, self.mode),
+            (fifths, other_mode),
+            (parallel_fifths, other_mode),
+        ]
+        .into_iter()
+        .filter_map(|(fifths, mode)| Some(Key::new(KeySignature::new(fifths).ok()?, mode)))
+    }
+
+    /// Find the shortest path of modulations to `target` through [closely related keys](Key::neighbours),
+    /// starting with this key and ending with `target`.
+    ///
+    /// Enharmonically equivalent keys are distinct keys, so path never jumps between them.
+    /// When there are multiple shortest paths, one taking neighbours in order
+    /// of [Key::neighbours] earliest is chosen.
+    /// Returns `None` if path would need more than `max_steps` modulations.
+    #[must_use]
+    pub fn modulation_path(&self, target: &Key, max_steps: usize) -> Option<Vec<Key>> {
+        let mut previous: Vec<(Key, Option<Key>)> = vec![(*self, None)];
+        let mut frontier = vec![*self];
+        let mut steps = 0;
+        while !previous.iter().any(|(key, _)| key == target) {
+            if steps == max_steps || frontier.is_empty() {
+                return None;
+            }
+            let mut next = vec![];
+            for key in frontier {
+                for neighbour in key.neighbours_iter() {
+                    if !previous.iter().any(|(visited, _)| *visited == neighbour) {
+                        previous.push((neighbour, Some(key)));
+                        next.push(neighbour);
+                    }
+                }
+            }
+            frontier = next;
+            steps += 1;
+        }
+
+        let mut path = vec![*target];
+        while let Some((_, Some(key))) = previous
+            .iter()
+            .find(|(key, _)| key == path.last().expect("path is never empty"))
+        {
+            path.push(*key);
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+impl Display for Key {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (letter, accidental) = self.tonic();
+        write!(f, "{}{} {}", letter, accidental, self.mode)
+    }
+}
+
+/// Position of natural note on the circle of fifths, relative to C (from -1 for F to 5 for B).
+fn fifths_position(letter: Letter) -> i8 {
+    match letter {
+        Letter::F => -1,
+        Letter::C => 0,
+        Letter::G => 1,
+        Letter::D => 2,
+        Letter::A => 3,
+        Letter::E => 4,
+        Letter::B => 5,
+    }
+}
+
+/// Note (letter and accidental) at given position on the circle of fifths, relative to C.
+fn letter_at_position(position: i8) -> (Letter, Accidental) {
+    let alteration = (position + 1).div_euclid(7);
+    let letter = match position - 7 * alteration {
+        -1 => Letter::F,
+        0 => Letter::C,
+        1 => Letter::G,
+        2 => Letter::D,
+        3 => Letter::A,
+        4 => Letter::E,
+        _ => Letter::B,
+    };
+    let accidental = match alteration {
+        -1 => Accidental::Flat,
+        0 => Accidental::None,
+        _ => Accidental::Sharp,
+    };
+    (letter, accidental)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn enharmonic_equivalent_of_c_sharp_major() {
+        let c_sharp = KeySignature::from_major_tonic(Letter::C, Accidental::Sharp).unwrap();
+        let d_flat = KeySignature::from_major_tonic(Letter::D, Accidental::Flat).unwrap();
+        assert_eq!(c_sharp.enharmonic_equivalent(), Some(d_flat));
+        assert_eq!(d_flat.enharmonic_equivalent(), Some(c_sharp));
+        assert_eq!(d_flat.major_tonic(), (Letter::D, Accidental::Flat));
+
+        let c = KeySignature::from_major_tonic(Letter::C, Accidental::None).unwrap();
+        assert_eq!(c.enharmonic_equivalent(), None);
+        for fifths in -7..=7 {
+            let signature = KeySignature::new(fifths).unwrap();
+            let expected = (fifths.abs() >= 5)
+                .then(|| KeySignature::new(fifths - 12 * fifths.signum()).unwrap());
+            assert_eq!(signature.enharmonic_equivalent(), expected, "{}", signature);
+        }
+    }
+
+    #[test]
+    fn accidental_counts() {
+        let count = |letter, accidental| {
+            KeySignature::from_major_tonic(letter, accidental)
+                .unwrap()
+                .accidental_count()
+        };
+        assert_eq!(count(Letter::C, Accidental::None), 0);
+        assert_eq!(count(Letter::F, Accidental::Sharp), 6);
+        assert_eq!(count(Letter::G, Accidental::Flat), 6);
+        assert_eq!(count(Letter::D, Accidental::Flat), 5);
+        assert_eq!(count(Letter::C, Accidental::Sharp), 7);
+    }
+
+    fn key(letter: Letter, accidental: Accidental, mode: Mode) -> Key {
+        Key::from_tonic(letter, accidental, mode).unwrap()
+    }
+
+    #[test]
+    fn distances_on_circle_of_fifths() {
+        let c_major = key(Letter::C, Accidental::None, Mode::Major);
+        assert_eq!(
+            c_major.distance(&key(Letter::E, Accidental::None, Mode::Major)),
+            4
+        );
+        assert_eq!(
+            c_major.distance(&key(Letter::A, Accidental::None, Mode::Minor)),
+            0
+        );
+        assert_eq!(
+            c_major.distance(&key(Letter::F, Accidental::Sharp, Mode::Major)),
+            6
+        );
+        assert_eq!(
+            c_major.distance(&key(Letter::G, Accidental::Flat, Mode::Major)),
+            6
+        );
+        assert_eq!(
+            c_major.distance(&key(Letter::C, Accidental::None, Mode::Minor)),
+            3
+        );
+        let c_sharp_major = key(Letter::C, Accidental::Sharp, Mode::Major);
+        let d_flat_major = key(Letter::D, Accidental::Flat, Mode::Major);
+        assert_eq!(c_sharp_major.distance(&d_flat_major), 0);
+        assert_eq!(
+            d_flat_major.distance(&key(Letter::B, Accidental::None, Mode::Major)),
+            2
+        );
+        assert_eq!(c_major.distance(&c_major), 0);
+    }
+
+    #[test]
+    fn modulation_paths() {
+        let c_major = key(Letter::C, Accidental::None, Mode::Major);
+        let e_major = key(Letter::E, Accidental::None, Mode::Major);
+        let path = c_major.modulation_path(&e_major, 6).unwrap();
+        // one sharp more, relative key, parallel key
+        assert_eq!(
+            path,
+            [
+                c_major,
+                key(Letter::G, Accidental::None, Mode::Major),
+                key(Letter::E, Accidental::None, Mode::Minor),
+                e_major,
+            ]
+        );
+        for pair in path.windows(2) {
+            assert!(pair[0].neighbours().contains(&pair[1]));
+            assert!(pair[0].neighbours_iter().eq(pair[0].neighbours()));
+        }
+        assert_eq!(c_major.modulation_path(&e_major, 3), Some(path.clone()));
+        assert_eq!(c_major.modulation_path(&e_major, 2), None);
+        assert_eq!(c_major.modulation_path(&c_major, 0), Some(vec![c_major]));
+
+        // enharmonic keys are distinct, so they're far apart
+        let c_sharp_major = key(Letter::C, Accidental::Sharp, Mode::Major);
+        let d_flat_major = key(Letter::D, Accidental::Flat, Mode::Major);
+        assert_eq!(c_sharp_major.modulation_path(&d_flat_major, 1), None);
+        assert!(c_sharp_major.modulation_path(&d_flat_major, 30).is_some());
+    }
+
+    #[test]
+    fn ranked_spellings_of_every_pitch_class() {
+        let names = |class, mode| -> Vec<String> {
+            Key::spellings_for_pitch_class(PitchClass::new(class), mode)
+                .iter()
+                .map(ToString::to_string)
+                .collect()
+        };
+        // ambiguous classes
+        assert_eq!(names(11, Mode::Major), ["B major", "C♭ major"]);
+        assert_eq!(names(6, Mode::Major), ["F♯ major", "G♭ major"]);
+        assert_eq!(names(1, Mode::Major), ["D♭ major", "C♯ major"]);
+        assert_eq!(names(8, Mode::Minor), ["G♯ minor", "A♭ minor"]);
+        assert_eq!(names(3, Mode::Minor), ["D♯ minor", "E♭ minor"]);
+        assert_eq!(names(10, Mode::Minor), ["B♭ minor", "A♯ minor"]);
+        for mode in [Mode::Major, Mode::Minor] {
+            for class in 0..PitchClass::COUNT {
+                let spellings = Key::spellings_for_pitch_class(PitchClass::new(class), mode);
+                let ambiguous = match mode {
+                    Mode::Major => [11, 6, 1],
+                    Mode::Minor => [8, 3, 10],
+                };
+                let expected = if ambiguous.contains(&class) { 2 } else { 1 };
+                assert_eq!(spellings.len(), expected, "{} {}", class, mode);
+                for key in spellings {
+                    assert_eq!(key.tonic_class(), PitchClass::new(class));
+                    assert_eq!(key.mode(), mode);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn transposition_stays_on_same_side_of_circle() {
+        let transposed = |letter, accidental, mode, semitones| {
+            Key::preferred_after_transposition(&key(letter, accidental, mode), semitones)
+                .to_string()
+        };
+        assert_eq!(
+            transposed(Letter::F, Accidental::None, Mode::Major, 1),
+            "G♭ major"
+        );
+        assert_eq!(
+            transposed(Letter::D, Accidental::None, Mode::Major, 4),
+            "F♯ major"
+        );
+        assert_eq!(
+            transposed(Letter::D, Accidental::None, Mode::Major, -8),
+            "F♯ major"
+        );
+        assert_eq!(
+            transposed(Letter::E, Accidental::None, Mode::Major, 7),
+            "B major"
+        );
+        assert_eq!(
+            transposed(Letter::A, Accidental::Flat, Mode::Major, 3),
+            "C♭ major"
+        );
+        // no spelling with sharps
+        assert_eq!(
+            transposed(Letter::G, Accidental::None, Mode::Major, 1),
+            "A♭ major"
+        );
+        // without sharps and flats, the fewest are chosen
+        assert_eq!(
+            transposed(Letter::C, Accidental::None, Mode::Major, 1),
+            "D♭ major"
+        );
+        assert_eq!(
+            transposed(Letter::C, Accidental::None, Mode::Major, 6),
+            "F♯ major"
+        );
+        assert_eq!(
+            transposed(Letter::A, Accidental::None, Mode::Minor, 6),
+            "D♯ minor"
+        );
+        assert_eq!(
+            transposed(Letter::D, Accidental::None, Mode::Minor, 1),
+            "E♭ minor"
+        );
+        assert_eq!(
+            transposed(Letter::E, Accidental::None, Mode::Minor, 11),
+            "D♯ minor"
+        );
+        assert_eq!(
+            transposed(Letter::F, Accidental::Sharp, Mode::Major, -12),
+            "F♯ major"
+        );
+    }
+}
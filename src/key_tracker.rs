@@ -0,0 +1,293 @@
+//! Live estimation of key from a stream of pitches.
+
+use crate::{ChromaticIndex, Key, KeySignature, Mode, Pitch};
+
+/// Krumhansl-Kessler key profile of major keys (tonic first).
+const MAJOR_PROFILE: [f64; 12] = [
+    6.35, 2.23, 3.48, 2.33, 4.38, 4.09, 2.52, 5.19, 2.39, 3.66, 2.29, 2.88,
+];
+
+/// Krumhansl-Kessler key profile of minor keys (tonic first).
+const MINOR_PROFILE: [f64; 12] = [
+    6.33, 2.68, 3.52, 5.38, 2.60, 3.53, 2.54, 4.75, 3.98, 2.69, 3.34, 3.17,
+];
+
+/// Options of [KeyTracker].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct KeyTrackerOptions {
+    /// Number of pushed pitches after which weight of a pitch falls to half.
+    ///
+    /// Default: 16 pitches.
+    pub half_life: f64,
+    /// Total (decayed) weight of pushed pitches needed before tracker estimates key.
+    ///
+    /// Default: 4.
+    pub min_evidence: f64,
+    /// Score (correlation with key profile) by which another key has to lead
+    /// current key before tracker switches to it.
+    ///
+    /// Default: 0.1.
+    pub hysteresis: f64,
+}
+
+impl Default for KeyTrackerOptions {
+    fn default() -> Self {
+        KeyTrackerOptions {
+            half_life: 16.0,
+            min_evidence: 4.0,
+            hysteresis: 0.1,
+        }
+    }
+}
+
+/// Stateful key estimator turning stream of pitches (for example: notes played live) into key.
+///
+/// Tracker accumulates weights of pitch classes of pushed pitches (chroma),
+/// fading older ones exponentially with [half-life](KeyTrackerOptions::half_life),
+/// and scores all 24 major and minor keys by correlation of the chroma
+/// with their Krumhansl-Kessler profiles.
+///
+/// Once decided on a key, tracker stays on it until another key scores higher
+/// by more than [hysteresis](KeyTrackerOptions::hysteresis), so keys with
+/// nearly equal scores don't flicker. Keys with 6 sharps or flats are reported
+/// with sharps (for example: F♯ major, not G♭ major).
+///
+/// Pushing and estimating neither allocate nor lock.
+#[derive(Debug, Clone)]
+pub struct KeyTracker {
+    options: KeyTrackerOptions,
+    chroma: [f64; 12],
+    /// Index of current key (see [KeyTracker::scores]) and its margin.
+    current: Option<(usize, f64)>,
+}
+
+impl KeyTracker {
+    /// Create new tracker.
+    #[must_use]
+    pub fn new(options: KeyTrackerOptions) -> Self {
+        KeyTracker {
+            options,
+            chroma: [0.0; 12],
+            current: None,
+        }
+    }
+
+    /// Get tracker options.
+    #[must_use]
+    pub fn options(&self) -> KeyTrackerOptions {
+        self.options
+    }
+
+    /// Feed next pitch to the tracker, with given weight (for example: its duration or velocity).
+    ///
+    /// Weights of previously pushed pitches decay, even if `weight` is ignored
+    /// for not being positive and finite.
+    pub fn push(&mut self, pitch: Pitch, weight: f64) {
+        let decay = 0.5_f64.powf(1.0 / self.options.half_life);
+        for value in &mut self.chroma {
+            *value *= decay;
+        }
+        if weight.is_finite() && weight > 0.0 {
+            let class = ChromaticIndex::from(pitch).class();
+            self.chroma[class.number() as usize] += weight;
+        }
+
+        let evidence: f64 = self.chroma.iter().sum();
+        if evidence < self.options.min_evidence {
+            self.current = None;
+            return;
+        }
+
+        let mut leader = (0, f64::NEG_INFINITY);
+        let mut runner_up = f64::NEG_INFINITY;
+        let mut current_score = None;
+        for (index, score) in self.scores().into_iter().enumerate() {
+            if self.current.is_some_and(|(current, _)| current == index) {
+                current_score = Some(score);
+            }
+            if score > leader.1 {
+                runner_up = leader.1;
+                leader = (index, score);
+            } else if score > runner_up {
+                runner_up = score;
+            }
+        }
+        if !leader.1.is_finite() {
+            self.current = None;
+            return;
+        }
+
+        let (index, score) = match current_score {
+            Some(score) if leader.1 - score <= self.options.hysteresis => {
+                (self.current.expect("invariant").0, score)
+            }
+            _ => leader,
+        };
+        let best_other = if index == leader.0 {
+            runner_up
+        } else {
+            leader.1
+        };
+        self.current = Some((index, score - best_other));
+    }
+
+    /// Current key and margin of its score over the best scoring other key.
+    ///
+    /// Margin is negative when another key leads, but not by more than
+    /// [hysteresis](KeyTrackerOptions::hysteresis).
+    ///
+    /// Returns `None` until pushed pitches reach [minimal evidence](KeyTrackerOptions::min_evidence)
+    /// (and when they decay below it), or while all pitch classes have equal weights.
+    #[must_use]
+    pub fn current_estimate(&self) -> Option<(Key, f64)> {
+        self.current
+            .map(|(index, margin)| (key_of_index(index), margin))
+    }
+
+    /// Forget all pushed pitches and current key.
+    pub fn reset(&mut self) {
+        self.chroma = [0.0; 12];
+        self.current = None;
+    }
+
+    /// Scores of keys - major keys with tonics C to B, then minor ones.
+    fn scores(&self) -> [f64; 24] {
+        std::array::from_fn(|index| {
+            let profile = if index < 12 {
+                &MAJOR_PROFILE
+            } else {
+                &MINOR_PROFILE
+            };
+            let tonic = index % 12;
+            let rotated: [f64; 12] =
+                std::array::from_fn(|class| profile[(class + 12 - tonic) % 12]);
+            correlation(&self.chroma, &rotated)
+        })
+    }
+}
+
+impl Default for KeyTracker {
+    fn default() -> Self {
+        KeyTracker::new(KeyTrackerOptions::default())
+    }
+}
+
+/// Key with tonic of pitch class `index % 12`, major for indices below 12, minor for others -
+/// the spelling with the fewest sharps or flats (sharps when tied).
+fn key_of_index(index: usize) -> Key {
+    let (mode, offset) = if index < 12 {
+        (Mode::Major, 0)
+    } else {
+        (Mode::Minor, 3)
+    };
+    // 7 is its own inverse modulo 12, so tonic class times 7 is position on the circle of fifths
+    let fifths = (7 * (index % 12) as i8 - offset).rem_euclid(12);
+    let fifths = if fifths > 6 { fifths - 12 } else { fifths };
+    Key::new(KeySignature::new(fifths).expect("invariant"), mode)
+}
+
+/// Pearson correlation coefficient (NaN if either of values is constant).
+fn correlation(a: &[f64; 12], b: &[f64; 12]) -> f64 {
+    let mean_a = a.iter().sum::<f64>() / 12.0;
+    let mean_b = b.iter().sum::<f64>() / 12.0;
+    let (mut covariance, mut variance_a, mut variance_b) = (0.0, 0.0, 0.0);
+    for (a, b) in a.iter().zip(b) {
+        covariance += (a - mean_a) * (b - mean_b);
+        variance_a += (a - mean_a) * (a - mean_a);
+        variance_b += (b - mean_b) * (b - mean_b);
+    }
+    covariance / (variance_a * variance_b).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PITCHES;
+
+    const C_MAJOR: [usize; 11] = [48, 50, 52, 53, 55, 57, 59, 60, 55, 52, 48];
+    const G_MAJOR: [usize; 11] = [55, 57, 59, 60, 62, 64, 66, 67, 62, 59, 55];
+
+    /// Estimated keys after every pitch of C major stream followed by G major stream.
+    fn modulation(options: KeyTrackerOptions) -> Vec<Option<String>> {
+        let mut tracker = KeyTracker::new(options);
+        C_MAJOR
+            .repeat(4)
+            .into_iter()
+            .chain(G_MAJOR.repeat(4))
+            .map(|index| {
+                tracker.push(PITCHES[index], 1.0);
+                tracker.current_estimate().map(|(key, _)| key.to_string())
+            })
+            .collect()
+    }
+
+    fn first_estimate_of(estimates: &[Option<String>], key: &str) -> Option<usize> {
+        estimates
+            .iter()
+            .position(|estimate| estimate.as_deref() == Some(key))
+    }
+
+    #[test]
+    fn modulation_switches_estimate_once() {
+        let estimates = modulation(KeyTrackerOptions::default());
+        assert!(estimates[..4].iter().all(Option::is_none));
+        let switch = first_estimate_of(&estimates, "G major").unwrap();
+        assert_eq!(switch, 4 * C_MAJOR.len() + 13);
+        assert!(estimates[4..switch]
+            .iter()
+            .all(|estimate| estimate.as_deref() == Some("C major")));
+        assert!(estimates[switch..]
+            .iter()
+            .all(|estimate| estimate.as_deref() == Some("G major")));
+    }
+
+    #[test]
+    fn hysteresis_delays_switch() {
+        let without = modulation(KeyTrackerOptions {
+            hysteresis: 0.0,
+            ..Default::default()
+        });
+        let with_default = modulation(KeyTrackerOptions::default());
+        assert!(
+            first_estimate_of(&without, "G major").unwrap()
+                < first_estimate_of(&with_default, "G major").unwrap()
+        );
+
+        let mut tracker = KeyTracker::new(KeyTrackerOptions {
+            hysteresis: 10.0,
+            ..Default::default()
+        });
+        for index in C_MAJOR.repeat(4).into_iter().chain(G_MAJOR.repeat(4)) {
+            tracker.push(PITCHES[index], 1.0);
+        }
+        let (key, margin) = tracker.current_estimate().unwrap();
+        assert_eq!(key.to_string(), "C major");
+        assert!(margin < 0.0);
+    }
+
+    #[test]
+    fn evidence_decays() {
+        let mut tracker = KeyTracker::default();
+        for index in C_MAJOR {
+            tracker.push(PITCHES[index], 1.0);
+        }
+        let (key, margin) = tracker.current_estimate().unwrap();
+        assert_eq!(key.to_string(), "C major");
+        assert!(margin > 0.0);
+
+        // weight of C major stream is about 9, halves every 16 pitches
+        for _ in 0..16 {
+            tracker.push(PITCHES[48], 0.0);
+            assert!(tracker.current_estimate().is_some());
+        }
+        for _ in 0..16 {
+            tracker.push(PITCHES[48], f64::NAN);
+        }
+        assert_eq!(tracker.current_estimate(), None);
+
+        tracker.push(PITCHES[48], 100.0);
+        assert!(tracker.current_estimate().is_some());
+        tracker.reset();
+        assert_eq!(tracker.current_estimate(), None);
+    }
+}
@@ -1,53 +1,275 @@
 //! Structured representation of musical pitches, notes and intervals
-//! for for equal-tempered scale, A₄ = 440 Hz.
+//! for equal-tempered scale, A₄ = 440 Hz by default,
+//! with other reference frequencies of A₄ available through [Tuning].
 
+mod bark;
+mod chord;
+mod chromatic_index;
+mod collection;
+mod error;
+mod extract;
+mod forte;
+mod fretboard;
 mod interval;
+mod key;
+mod key_tracker;
+mod melody;
+mod midi;
 mod note;
+mod note_array;
+mod pitch_class;
+mod range;
+mod rhythm;
+mod scale;
+mod spelling;
+mod table;
+mod transcription;
+mod tuner;
+mod tuning;
 
+#[cfg(feature = "test-support")]
+pub mod verify;
+
+/// Compile tests of `#[must_use]` attributes - ignoring results is an error
+/// when `unused_must_use` is denied, using them compiles.
+///
+/// ```
+/// #![deny(unused_must_use)]
+/// use pitches::*;
+/// let chord = Chord::new(Note::from(PITCHES[48]), ChordQuality::Major);
+/// let _ = chord.tension();
+/// let _ = virtual_fundamental(&PITCHES[48..52]);
+/// let _ = Interval::new(440.0, 660.0);
+/// let _ = PITCHES[57].frequency();
+/// let _ = Note::from(PITCHES[57]).chromatic_index();
+/// ```
+///
+/// ```compile_fail
+/// #![deny(unused_must_use)]
+/// use pitches::*;
+/// let chord = Chord::new(Note::from(PITCHES[48]), ChordQuality::Major);
+/// chord.tension();
+/// ```
+///
+/// ```compile_fail
+/// #![deny(unused_must_use)]
+/// use pitches::*;
+/// virtual_fundamental(&PITCHES[48..52]);
+/// ```
+///
+/// ```compile_fail
+/// #![deny(unused_must_use)]
+/// use pitches::*;
+/// Interval::new(440.0, 660.0);
+/// ```
+///
+/// ```compile_fail
+/// #![deny(unused_must_use)]
+/// use pitches::*;
+/// PITCHES[57].frequency();
+/// ```
+///
+/// ```compile_fail
+/// #![deny(unused_must_use)]
+/// use pitches::*;
+/// Note::from(PITCHES[57]).chromatic_index();
+/// ```
+#[cfg(doctest)]
+pub struct MustUse;
+
+pub use bark::*;
+pub use chord::*;
+pub use chromatic_index::*;
+pub use collection::*;
+pub use error::*;
+pub use extract::*;
+pub use forte::*;
+pub use fretboard::*;
 pub use interval::*;
+pub use key::*;
+pub use key_tracker::*;
+pub use melody::*;
+pub use midi::*;
 pub use note::*;
+pub use note_array::*;
+pub use pitch_class::*;
+pub use range::*;
+pub use rhythm::*;
+pub use scale::*;
+pub use spelling::*;
+pub use table::*;
+pub use transcription::*;
+pub use tuner::*;
+pub use tuning::*;
 
 use lazy_static::lazy_static;
-use std::fmt::Display;
+use std::{borrow::Cow, fmt::Display, str::FromStr};
 
-/// Frequencies of pitches for equal-tempered scale, A₄ = 440 Hz.
+/// Frequencies of pitches for equal-tempered scale, A₄ = 440 Hz,
+/// rounded to 0.01 Hz (see [FREQUENCIES_EXACT] for exact ones).
 ///
-/// See [table](https://pages.mtu.edu/~suits/notefreqs.html).
+/// See [table](https://pages.mtu.edu/~suits/notefreqs.html) - its values of E₅ and D₈
+/// are truncated rather than rounded, here they're rounded.
 pub const FREQUENCIES: [f64; 108] = [
     16.35, 17.32, 18.35, 19.45, 20.60, 21.83, 23.12, 24.50, 25.96, 27.50, 29.14, 30.87, 32.70,
     34.65, 36.71, 38.89, 41.20, 43.65, 46.25, 49.00, 51.91, 55.00, 58.27, 61.74, 65.41, 69.30,
     73.42, 77.78, 82.41, 87.31, 92.50, 98.00, 103.83, 110.00, 116.54, 123.47, 130.81, 138.59,
     146.83, 155.56, 164.81, 174.61, 185.00, 196.00, 207.65, 220.00, 233.08, 246.94, 261.63, 277.18,
     293.66, 311.13, 329.63, 349.23, 369.99, 392.00, 415.30, 440.00, 466.16, 493.88, 523.25, 554.37,
-    587.33, 622.25, 659.25, 698.46, 739.99, 783.99, 830.61, 880.00, 932.33, 987.77, 1046.50,
+    587.33, 622.25, 659.26, 698.46, 739.99, 783.99, 830.61, 880.00, 932.33, 987.77, 1046.50,
     1108.73, 1174.66, 1244.51, 1318.51, 1396.91, 1479.98, 1567.98, 1661.22, 1760.00, 1864.66,
     1975.53, 2093.00, 2217.46, 2349.32, 2489.02, 2637.02, 2793.83, 2959.96, 3135.96, 3322.44,
-    3520.00, 3729.31, 3951.07, 4186.01, 4434.92, 4698.63, 4978.03, 5274.04, 5587.65, 5919.91,
+    3520.00, 3729.31, 3951.07, 4186.01, 4434.92, 4698.64, 4978.03, 5274.04, 5587.65, 5919.91,
     6271.93, 6644.88, 7040.00, 7458.62, 7902.13,
 ];
 
 lazy_static! {
+    /// Exact (not rounded to 0.01 Hz like [FREQUENCIES]) frequencies of pitches
+    /// for equal-tempered scale, A₄ = 440 Hz - computed from the formula.
+    pub static ref FREQUENCIES_EXACT: [f64; 108] = std::array::from_fn(|index| {
+        Tuning::default().frequency_of_index(ChromaticIndex(index as i16))
+    });
+
     /// All available pitches of equal-tempered scale, A₄ = 440 Hz.
     pub static ref PITCHES: Vec<Pitch> = (0..FREQUENCIES.len())
-        .into_iter()
         .map(|index| Pitch { index: index as u8 })
         .collect();
 }
 
 /// Struct representing pitch.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+///
+/// Alternate [Debug] format (`{:#?}`) prints [diagnostic](Pitch::diagnostic) string.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Pitch {
     index: u8,
 }
 
 impl Pitch {
+    /// Get pitch nearest to given frequency.
+    ///
+    /// Returns `None` if frequency isn't positive or is more than
+    /// a half of a semitone outside of supported range.
+    #[must_use]
+    pub fn from_frequency(frequency: f64) -> Option<Pitch> {
+        if !(frequency.is_finite() && frequency > 0.0) {
+            return None;
+        }
+
+        let index = (57.0 + 12.0 * (frequency / 440.0).log2()).round();
+        if (0.0..FREQUENCIES.len() as f64).contains(&index) {
+            Some(PITCHES[index as usize])
+        } else {
+            None
+        }
+    }
+
     /// Frequency of pitch.
+    #[must_use]
     pub fn frequency(&self) -> f64 {
         FREQUENCIES[self.index as usize]
     }
 
+    /// Exact (not rounded like values in [FREQUENCIES]) frequency of pitch
+    /// in equal-tempered scale, A₄ = 440 Hz.
+    #[must_use]
+    pub fn exact_frequency(&self) -> f64 {
+        Tuning::default().frequency(*self)
+    }
+
+    /// Check if frequency is within `tolerance_cents` of pitch frequency
+    /// (for example: 441 Hz matches A₄ within 5 cents).
+    ///
+    /// Frequency that isn't positive and finite never matches.
+    #[must_use]
+    pub fn matches_frequency(&self, frequency: f64, tolerance_cents: f64) -> bool {
+        frequency.is_finite()
+            && frequency > 0.0
+            && Interval::new(self.frequency(), frequency)
+                .cents()
+                .abs()
+                .value()
+                <= tolerance_cents
+    }
+
+    /// Frequency of pitch retuned by given number of cents.
+    ///
+    /// Positive `cents` raise the pitch, negative lower it.
+    #[must_use]
+    pub fn retune_hz(&self, cents: f64) -> f64 {
+        self.frequency() * 2.0_f64.powf(cents / 1200.0)
+    }
+
+    /// Difference (in Hz) between [retuned](Pitch::retune_hz) frequency and frequency of pitch.
+    ///
+    /// Positive when retuned frequency is higher.
+    #[must_use]
+    pub fn retune_delta_hz(&self, cents: f64) -> f64 {
+        self.retune_hz(cents) - self.frequency()
+    }
+
+    /// Consonance of the pitch sounding against a drone pitch, in range (0, 1].
+    ///
+    /// Based on simplicity of [just](NamedInterval::just_ratio) frequency ratio `n/d` approximated
+    /// by the interval between pitches - it's equal to `1 / (n * d)`
+    /// (for example: 1 for unison, 1/6 for perfect fifth, 1/1440 for tritone).
+    /// Pitches an octave apart are treated as equal, so every octave scores 1.
+    #[must_use]
+    pub fn consonance_against(&self, drone: Pitch) -> f64 {
+        let semitones = drone.semitones_to(*self).rem_euclid(12);
+        let (numerator, denominator) = NamedInterval::from_semitones(semitones as u8)
+            .expect("invariant")
+            .just_ratio();
+        1.0 / (numerator * denominator) as f64
+    }
+
+    /// Name of the pitch - [Unicode](NoteStyle::Unicode) name of its [Note] (for example: A₄, C♯₄).
+    ///
+    /// Name is borrowed from a precomputed table, so it doesn't allocate.
+    #[must_use]
+    pub fn name(&self) -> Cow<'static, str> {
+        Note::from(*self).format_in(NoteStyle::Unicode)
+    }
+
+    /// Human-readable diagnostic description of the pitch,
+    /// for example: `index 57 = A₄ = 440 Hz = MIDI 69`.
+    ///
+    /// Format is meant for debugging only and may change between versions.
+    #[must_use]
+    pub fn diagnostic(&self) -> String {
+        format!(
+            "index {} = {} = {} Hz = MIDI {}",
+            self.index,
+            Note::from(*self),
+            self.frequency(),
+            self.midi_number()
+        )
+    }
+
+    /// Position of the pitch in cents relative to A₄ (for example: -900 for C₄, 1200 for A₅).
+    #[must_use]
+    pub fn cents_from_a4(&self) -> Cents {
+        ChromaticIndex::from(*self).cents_from_a4()
+    }
+
+    /// Get pitch nearest to position in cents relative to A₄, along with deviation from it
+    /// (or `None` if nearest pitch is out of supported range).
+    ///
+    /// Rounds the same way as [Pitch::from_frequency].
+    #[must_use]
+    pub fn from_cents_from_a4(cents: Cents) -> Option<(Pitch, Cents)> {
+        // rounding index (not semitones from A4), so positions exactly between
+        // two pitches go to the higher one, like in Pitch::from_frequency
+        let index = (57.0 + cents.value() / 100.0).round();
+        if !(0.0..FREQUENCIES.len() as f64).contains(&index) {
+            return None;
+        }
+        let pitch = PITCHES[index as usize];
+        Some((pitch, cents - pitch.cents_from_a4()))
+    }
+
     /// Get index in [FREQUENCIES] array.
-    pub fn index(&self) -> u8 {
+    #[must_use]
+    pub const fn index(&self) -> u8 {
         self.index
     }
 
@@ -67,13 +289,164 @@ impl Pitch {
     ///| 9      | A     |
     ///| 10     | A♯/B♭ |
     ///| 11     | B     |
-    pub fn number(&self) -> u8 {
+    #[must_use]
+    pub const fn number(&self) -> u8 {
         self.index % 12
     }
 
+    /// All notes spelling this pitch (naturals first, then sharps, then flats).
+    ///
+    /// Only common spellings (accepted by [Note::new]) are included.
+    #[must_use]
+    pub fn spellings(&self) -> Vec<Note> {
+        self.spellings_iter().collect()
+    }
+
+    /// Iterator over [spellings](Pitch::spellings) of the pitch, without collecting them.
+    pub fn spellings_iter(&self) -> impl Iterator<Item = Note> {
+        let index = ChromaticIndex::from(*self);
+        let octave = self.index / 12;
+        [Accidental::None, Accidental::Sharp, Accidental::Flat]
+            .into_iter()
+            .flat_map(move |accidental| {
+                (octave.saturating_sub(1)..=octave + 1)
+                    .filter_map(|octave| Octave::try_from(octave).ok())
+                    .flat_map(move |octave| {
+                        Letter::ALL
+                            .into_iter()
+                            .filter_map(move |letter| Note::new(letter, octave, accidental).ok())
+                    })
+            })
+            .filter(move |note| note.chromatic_index() == index)
+    }
+
+    /// Key for sorting pitches, consistent with [Note::sort_key]
+    /// (pitches are ordered by octave, then by pitch class starting with C).
+    ///
+    /// This ordering is stable and won't change in future versions.
+    #[must_use]
+    pub fn sort_key(&self) -> impl Ord {
+        self.index
+    }
+
+    /// Get pitch with given MIDI note number (or `None` if it's out of supported range).
+    #[must_use]
+    pub fn from_midi_number(number: u8) -> Option<Pitch> {
+        number
+            .checked_sub(12)
+            .and_then(|index| PITCHES.get(index as usize))
+            .copied()
+    }
+
+    /// MIDI note number of pitch (C₄ is 60).
+    #[must_use]
+    pub const fn midi_number(&self) -> u8 {
+        self.index + 12
+    }
+
     /// Octave of the pitch.
-    pub fn octave(&self) -> Octave {
-        (self.index / 12).try_into().unwrap()
+    #[must_use]
+    pub const fn octave(&self) -> Octave {
+        self.into_parts().1
+    }
+
+    /// Pitch class of the pitch.
+    #[must_use]
+    pub const fn class(&self) -> PitchClass {
+        self.into_parts().0
+    }
+
+    // Index-space API - pure integer operations on pitch indices,
+    // never touching frequencies.
+
+    /// Number of semitones from this pitch to `other` (negative if `other` is lower).
+    #[must_use]
+    pub const fn semitones_to(&self, other: Pitch) -> i16 {
+        other.index as i16 - self.index as i16
+    }
+
+    /// Get pitch of given class in given octave (or `None` if it's out of supported range).
+    #[must_use]
+    pub const fn from_parts(class: PitchClass, octave: Octave) -> Option<Pitch> {
+        let index = octave.number() as usize * 12 + class.number() as usize;
+        if index < FREQUENCIES.len() {
+            Some(Pitch { index: index as u8 })
+        } else {
+            None
+        }
+    }
+
+    /// Split pitch into its class and octave.
+    #[must_use]
+    pub const fn into_parts(&self) -> (PitchClass, Octave) {
+        let octave = match Octave::from_number(self.index / 12) {
+            Some(octave) => octave,
+            // every supported pitch lies within supported octaves
+            None => Octave::Tenth,
+        };
+        (PitchClass::new(self.index % 12), octave)
+    }
+}
+
+impl From<Pitch> for u8 {
+    /// Convert pitch to its [index](Pitch::index) in [FREQUENCIES] (0 for C₀, 57 for A₄).
+    ///
+    /// This representation is stable and won't change in future versions.
+    fn from(pitch: Pitch) -> Self {
+        pitch.index
+    }
+}
+
+impl TryFrom<u8> for Pitch {
+    type Error = Error;
+
+    /// Get pitch with given index in [FREQUENCIES] (the same as the one pitch is converted into).
+    fn try_from(index: u8) -> Result<Self, Self::Error> {
+        PITCHES
+            .get(index as usize)
+            .copied()
+            .ok_or(Error::PitchNotInRange)
+    }
+}
+
+impl FromStr for Pitch {
+    type Err = Error;
+
+    /// Parse pitch from note name (any spelling, see [Note::from_str] - for example: "A4", "F#2" or "E♯₄")
+    /// or from frequency (for example: "440" or "261.63", as pitch is [displayed](Display)).
+    ///
+    /// Frequency has to match value of [FREQUENCIES] up to rounding of the table (0.005 Hz),
+    /// otherwise fails with [FrequencyNotInTable](Error::FrequencyNotInTable).
+    /// Note without corresponding pitch (like notes of octave 9) is [PitchNotInRange](Error::PitchNotInRange).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if !s.starts_with(|c: char| c.is_ascii_digit() || c == '.') {
+            return parse_note(s)?.chromatic_index().try_into_pitch();
+        }
+        let frequency: f64 = s.parse().map_err(|_| Error::IncorrectFrequency)?;
+        FREQUENCIES
+            .iter()
+            .position(|value| (value - frequency).abs() <= 0.005)
+            .map(|index| PITCHES[index])
+            .ok_or(Error::FrequencyNotInTable)
+    }
+}
+
+impl TryFrom<&str> for Pitch {
+    type Error = Error;
+
+    /// Same as [Pitch::from_str].
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl std::fmt::Debug for Pitch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if f.alternate() {
+            write!(f, "{}", self.diagnostic())
+        } else {
+            f.debug_struct("Pitch").field("index", &self.index).finish()
+        }
     }
 }
 
@@ -82,3 +455,217 @@ impl Display for Pitch {
         write!(f, "{}", FREQUENCIES[self.index as usize])
     }
 }
+
+// Value types meant to be shared with real-time threads stay small, immutable and thread-safe.
+const _: () = {
+    const fn assert_send_sync<T: Send + Sync + Copy>() {}
+    assert_send_sync::<Scale>();
+    assert_send_sync::<Key>();
+    assert_send_sync::<Tuning>();
+    assert_send_sync::<SpellingPolicy>();
+    assert!(std::mem::size_of::<Scale>() <= 4);
+    assert!(std::mem::size_of::<Key>() <= 2);
+    assert!(std::mem::size_of::<Tuning>() <= 8);
+    assert!(std::mem::size_of::<SpellingPolicy>() <= 2);
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spellings_of_pitches() {
+        let names = |index: usize| -> Vec<String> {
+            PITCHES[index]
+                .spellings_iter()
+                .map(|note| note.to_string())
+                .collect()
+        };
+        assert_eq!(names(48), ["C₄"]);
+        assert_eq!(names(56), ["G♯₄", "A♭₄"]);
+        for pitch in PITCHES.iter() {
+            assert!(pitch.spellings_iter().eq(pitch.spellings()));
+        }
+    }
+
+    #[test]
+    fn frequencies_are_rounded_exact_values() {
+        for (index, published) in FREQUENCIES.iter().enumerate() {
+            let exact = 440.0 * 2.0_f64.powf((index as f64 - 57.0) / 12.0);
+            assert!(
+                (published - exact).abs() <= 0.005 + 1e-9,
+                "{} differs from exact {} at index {}",
+                published,
+                exact,
+                index
+            );
+        }
+    }
+
+    #[test]
+    fn frequencies_exact_match_closed_form() {
+        for (index, exact) in FREQUENCIES_EXACT.iter().enumerate() {
+            let expected = 440.0 * 2.0_f64.powf((index as f64 - 57.0) / 12.0);
+            assert!((exact - expected).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn retuning_a4_by_half_semitone() {
+        let a4 = PITCHES[57];
+        assert!((a4.retune_hz(50.0) - 452.89).abs() < 0.01);
+        assert!((a4.retune_delta_hz(50.0) - 12.89).abs() < 0.01);
+        assert!((a4.retune_hz(-1200.0) - 220.0).abs() < 1e-9);
+        assert_eq!(a4.retune_delta_hz(0.0), 0.0);
+    }
+
+    #[test]
+    fn perfect_fifth_is_more_consonant_than_tritone() {
+        let c4 = PITCHES[48];
+        let fifth = PITCHES[55].consonance_against(c4);
+        let tritone = PITCHES[54].consonance_against(c4);
+        assert!(fifth > tritone);
+        assert_eq!(fifth, 1.0 / 6.0);
+        assert_eq!(c4.consonance_against(c4), 1.0);
+        assert_eq!(PITCHES[60].consonance_against(c4), 1.0);
+        assert_eq!(
+            PITCHES[43].consonance_against(c4),
+            PITCHES[67].consonance_against(c4)
+        );
+    }
+
+    /// Every pitch, split into parts and joined back at compile time -
+    /// index-space API can't reach lazily computed tables like [PITCHES].
+    const ROUND_TRIPS: [(Option<Pitch>, i16); 120] = {
+        let mut round_trips = [(None, 0); 120];
+        let mut index = 0;
+        while index < 120 {
+            let class = PitchClass::new(index as u8 % 12);
+            if let Some(octave) = Octave::from_number(index as u8 / 12) {
+                if let Some(pitch) = Pitch::from_parts(class, octave) {
+                    let (class, octave) = pitch.into_parts();
+                    let lowest = Pitch { index: 0 };
+                    round_trips[index] =
+                        (Pitch::from_parts(class, octave), lowest.semitones_to(pitch));
+                }
+            }
+            index += 1;
+        }
+        round_trips
+    };
+
+    #[test]
+    fn index_space_api_is_frequency_free() {
+        for (index, (pitch, semitones)) in ROUND_TRIPS.iter().enumerate() {
+            if index < FREQUENCIES.len() {
+                assert_eq!(*pitch, Some(PITCHES[index]));
+                assert_eq!(*semitones, index as i16);
+            } else {
+                assert_eq!(*pitch, None);
+            }
+        }
+        for pitch in PITCHES.iter() {
+            assert_eq!(pitch.into_parts(), (pitch.class(), pitch.octave()));
+            assert_eq!(
+                pitch.semitones_to(PITCHES[57]),
+                Interval::new(pitch.exact_frequency(), 440.0)
+                    .cents()
+                    .value()
+                    .round() as i16
+                    / 100
+            );
+        }
+    }
+
+    #[test]
+    fn a4_matches_441_hz_within_5_cents() {
+        let a4 = PITCHES[57];
+        assert!(a4.matches_frequency(441.0, 5.0));
+        assert!(!a4.matches_frequency(441.0, 3.0));
+        assert!(a4.matches_frequency(439.0, 5.0));
+        assert!(!a4.matches_frequency(466.16, 5.0));
+        for frequency in [0.0, -441.0, f64::NAN, f64::INFINITY] {
+            assert!(!a4.matches_frequency(frequency, 5.0));
+        }
+    }
+
+    #[test]
+    fn cents_from_a4_of_canonical_pitches() {
+        let cents = |pitch: Pitch| pitch.cents_from_a4().value();
+        assert_eq!(cents(PITCHES[0]), -5700.0);
+        assert_eq!(cents(PITCHES[48]), -900.0);
+        assert_eq!(cents(PITCHES[57]), 0.0);
+        assert_eq!(cents(PITCHES[69]), 1200.0);
+        assert_eq!(cents(PITCHES[107]), 5000.0);
+        assert_eq!(Note::from(PITCHES[48]).cents_from_a4().value(), -900.0);
+        let b_sharp_3 = Note::new_unchecked_spelling(Letter::B, Octave::Fourth, Accidental::Sharp);
+        assert_eq!(b_sharp_3.cents_from_a4().value(), -900.0);
+
+        for pitch in PITCHES.iter() {
+            let detuned = Detuned::new(*pitch, Cents::new(20.0).unwrap());
+            assert_eq!(detuned.cents_from_a4().value(), cents(*pitch) + 20.0);
+            let (nearest, residual) = Pitch::from_cents_from_a4(detuned.cents_from_a4()).unwrap();
+            assert_eq!(nearest, *pitch);
+            assert!((residual.value() - 20.0).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn from_cents_from_a4_rounds_like_from_frequency() {
+        for cents in [
+            -5750.0, -950.0, -50.0, 0.0, 49.9, 50.0, 150.0, 5049.9, 5050.0,
+        ] {
+            let frequency = 440.0 * 2.0_f64.powf(cents / 1200.0);
+            let from_cents = Pitch::from_cents_from_a4(Cents::new(cents).unwrap());
+            assert_eq!(
+                from_cents.map(|(pitch, _)| pitch),
+                Pitch::from_frequency(frequency),
+                "{}",
+                cents
+            );
+        }
+        let (pitch, residual) = Pitch::from_cents_from_a4(Cents::new(-50.0).unwrap()).unwrap();
+        assert_eq!((pitch, residual.value()), (PITCHES[57], -50.0));
+        assert_eq!(
+            Pitch::from_cents_from_a4(Cents::new(-5751.0).unwrap()),
+            None
+        );
+        assert_eq!(Pitch::from_cents_from_a4(Cents::new(5050.0).unwrap()), None);
+    }
+
+    #[test]
+    fn cents_from_a4_tracks_tuning() {
+        let baroque = Tuning::new(415.0).unwrap();
+        assert_eq!(baroque.cents_from_a4(415.0).value(), 0.0);
+        assert!((baroque.cents_from_a4(830.0).value() - 1200.0).abs() < 1e-9);
+        let c4 = baroque.frequency(PITCHES[48]);
+        assert!((baroque.cents_from_a4(c4).value() + 900.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn parsing_pitches() {
+        let cases = [
+            ("A4", Ok(PITCHES[57])),
+            ("F#2", Ok(PITCHES[30])),
+            ("E♯₄", Ok(PITCHES[53])),
+            ("16.35", Ok(PITCHES[0])),
+            ("16.354", Ok(PITCHES[0])),
+            ("7902.13", Ok(PITCHES[107])),
+            ("440", Ok(PITCHES[57])),
+            ("440.00", Ok(PITCHES[57])),
+            ("20000", Err(Error::FrequencyNotInTable)),
+            ("16", Err(Error::FrequencyNotInTable)),
+            ("445", Err(Error::FrequencyNotInTable)),
+            ("4.4.0", Err(Error::IncorrectFrequency)),
+            ("C9", Err(Error::PitchNotInRange)),
+            ("H4", Err(Error::IncorrectLetter)),
+        ];
+        for (name, expected) in cases {
+            assert_eq!(name.parse::<Pitch>(), expected, "{:?}", name);
+            assert_eq!(Pitch::try_from(name), expected, "{:?}", name);
+        }
+        for pitch in PITCHES.iter() {
+            assert_eq!(pitch.to_string().parse(), Ok(*pitch));
+        }
+    }
+}
@@ -0,0 +1,585 @@
+//! Analysis of melodies (sequences of notes and pitches).
+
+use crate::{Cents, Note, Pitch, PitchClass, PITCHES};
+
+/// Find suspected octave errors (for example: in imported scores) - indices of notes
+/// leaping from the previous one by more than `max_leap_semitones` (in either direction).
+#[must_use]
+pub fn flag_octave_outliers(notes: &[Note], max_leap_semitones: i32) -> Vec<usize> {
+    notes
+        .windows(2)
+        .enumerate()
+        .filter(|(_, pair)| {
+            let leap = pair[0]
+                .chromatic_index()
+                .semitones_to(pair[1].chromatic_index());
+            (leap as i32).abs() > max_leap_semitones
+        })
+        .map(|(i, _)| i + 1)
+        .collect()
+}
+
+/// Retrograde of a melody - its pitches in reverse order.
+#[must_use]
+pub fn retrograde(pitches: &[Pitch]) -> Vec<Pitch> {
+    pitches.iter().rev().copied().collect()
+}
+
+/// Inversion of a melody around `axis` - every pitch mirrored, so that it's
+/// as many semitones below `axis` as original was above it (and vice versa).
+///
+/// Pitches mirrored out of supported range are `None`.
+#[must_use]
+pub fn invert(pitches: &[Pitch], axis: Pitch) -> Vec<Option<Pitch>> {
+    pitches
+        .iter()
+        .map(|pitch| {
+            let index = axis.index() as i16 - axis.semitones_to(*pitch);
+            usize::try_from(index)
+                .ok()
+                .and_then(|index| PITCHES.get(index))
+                .copied()
+        })
+        .collect()
+}
+
+/// Find parallel fifths between two voices - indices of positions where the voices
+/// form a perfect fifth (or compound fifth) after forming one at the previous position,
+/// with both voices moving.
+///
+/// Voices are compared up to the length of the shorter one.
+#[must_use]
+pub fn has_parallel_fifths(voice_a: &[Pitch], voice_b: &[Pitch]) -> Vec<usize> {
+    let is_fifth = |a: Pitch, b: Pitch| a.semitones_to(b).unsigned_abs() % 12 == 7;
+    let positions: Vec<(Pitch, Pitch)> = voice_a
+        .iter()
+        .copied()
+        .zip(voice_b.iter().copied())
+        .collect();
+    positions
+        .windows(2)
+        .enumerate()
+        .filter(|(_, pair)| {
+            let ((a0, b0), (a1, b1)) = (pair[0], pair[1]);
+            a0 != a1 && b0 != b1 && is_fifth(a0, b0) && is_fifth(a1, b1)
+        })
+        .map(|(i, _)| i + 1)
+        .collect()
+}
+
+/// Find voice crossings - indices of positions where nominally upper voice
+/// is below the lower voice.
+///
+/// Voices are compared up to the length of the shorter one.
+#[must_use]
+pub fn voice_crossings(upper: &[Pitch], lower: &[Pitch]) -> Vec<usize> {
+    upper
+        .iter()
+        .zip(lower)
+        .enumerate()
+        .filter(|(_, (upper, lower))| upper < lower)
+        .map(|(i, _)| i)
+        .collect()
+}
+
+/// Voice-leading distance between two chords - minimal total motion (in semitones)
+/// of voices moving pitches of chord `a` to pitches of chord `b`, each voice to a different pitch
+/// (for example: 0 from C major to itself, 3 from C₄ E₄ G₄ to B₃ D₄ G₄).
+///
+/// With motion measured as distance on a line, pairing the pitches of both chords
+/// in ascending order is an optimal matching, so no search is needed.
+/// Returns `None` if chords have different numbers of pitches.
+#[must_use]
+pub fn voice_leading_distance(a: &[Pitch], b: &[Pitch]) -> Option<u32> {
+    if a.len() != b.len() {
+        return None;
+    }
+    let (mut a, mut b) = (a.to_vec(), b.to_vec());
+    a.sort();
+    b.sort();
+    Some(
+        a.iter()
+            .zip(&b)
+            .map(|(a, b)| a.semitones_to(*b).unsigned_abs() as u32)
+            .sum(),
+    )
+}
+
+/// Average size of intervals between consecutive pitches of a melody, in cents
+/// (for example: 100 for chromatic scale) - small values mean stepwise motion,
+/// large ones mean leaps.
+///
+/// Returns `None` for melodies of less than two pitches.
+#[must_use]
+pub fn mean_interval_cents(pitches: &[Pitch]) -> Option<Cents> {
+    if pitches.len() < 2 {
+        return None;
+    }
+    let total: f64 = pitches
+        .windows(2)
+        .map(|pair| (pair[1].cents_from_a4().value() - pair[0].cents_from_a4().value()).abs())
+        .sum();
+    let mean = total / (pitches.len() - 1) as f64;
+    Some(Cents::new_unchecked(mean))
+}
+
+/// Cheap estimate of tonic of a melody - its most frequent pitch class
+/// (for example: 0 for melody dwelling on C).
+///
+/// When several pitch classes are equally frequent, the one appearing first in melody wins.
+/// Returns `None` for empty melody.
+#[must_use]
+pub fn tonal_center(pitches: &[Pitch]) -> Option<PitchClass> {
+    let mut histogram = [0usize; PitchClass::COUNT as usize];
+    for pitch in pitches {
+        histogram[pitch.class().number() as usize] += 1;
+    }
+    let mut center: Option<PitchClass> = None;
+    for pitch in pitches {
+        let class = pitch.class();
+        if !center.is_some_and(|center| {
+            histogram[class.number() as usize] <= histogram[center.number() as usize]
+        }) {
+            center = Some(class);
+        }
+    }
+    center
+}
+
+/// Options of [diff_notes].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DiffOptions {
+    /// Treat different spellings of the same pitch (like C♯₄ and D♭₄) as correct.
+    ///
+    /// Default: `false`.
+    pub enharmonic_is_correct: bool,
+    /// Report notes with correct pitch class but in a wrong octave
+    /// as [WrongOctave](NoteDiff::WrongOctave) instead of [WrongPitch](NoteDiff::WrongPitch).
+    ///
+    /// Default: `true`.
+    pub separate_octave_errors: bool,
+}
+
+impl Default for DiffOptions {
+    fn default() -> Self {
+        DiffOptions {
+            enharmonic_is_correct: false,
+            separate_octave_errors: true,
+        }
+    }
+}
+
+/// Single position of [note sequence diff](diff_notes).
+///
+/// `expected` and `actual` are indices into compared sequences.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NoteDiff {
+    /// Notes are the same.
+    Correct { expected: usize, actual: usize },
+    /// Notes have the same pitch but are spelled differently.
+    Enharmonic { expected: usize, actual: usize },
+    /// Notes have the same pitch class but are in different octaves.
+    WrongOctave { expected: usize, actual: usize },
+    /// Notes have different pitches.
+    WrongPitch { expected: usize, actual: usize },
+    /// Expected note is missing.
+    Missing { expected: usize },
+    /// Actual note is not expected.
+    Extra { actual: usize },
+}
+
+/// Compare sequence of notes with expected one (for example: for grading transcription exercises).
+///
+/// Sequences are aligned with minimal edit cost (Levenshtein distance), where
+/// missing and extra notes cost 2, wrong pitches cost 3,
+/// enharmonic and octave errors (when they're not treated as correct or wrong pitches
+/// according to `options`) cost 1 and correct notes cost nothing.
+/// Notes with and without explicit natural sign (like C₄ and C♮₄) are the same spelling.
+/// Among alignments with equal cost, ones pairing notes are preferred,
+/// then ones reporting missing notes before extra ones.
+#[must_use]
+pub fn diff_notes(expected: &[Note], actual: &[Note], options: DiffOptions) -> Vec<NoteDiff> {
+    const INDEL_COST: u32 = 2;
+
+    let classify = |e: usize, a: usize| -> (NoteDiff, u32) {
+        let (expected_note, actual_note) = (expected[e], actual[a]);
+        let (expected_index, actual_index) = (
+            expected_note.chromatic_index(),
+            actual_note.chromatic_index(),
+        );
+        // explicit natural is the same spelling as plain note
+        let same_spelling = expected_note.letter() == actual_note.letter()
+            && expected_note.octave() == actual_note.octave()
+            && expected_note.accidental().alteration() == actual_note.accidental().alteration();
+        if same_spelling {
+            (
+                NoteDiff::Correct {
+                    expected: e,
+                    actual: a,
+                },
+                0,
+            )
+        } else if expected_index == actual_index {
+            if options.enharmonic_is_correct {
+                (
+                    NoteDiff::Correct {
+                        expected: e,
+                        actual: a,
+                    },
+                    0,
+                )
+            } else {
+                (
+                    NoteDiff::Enharmonic {
+                        expected: e,
+                        actual: a,
+                    },
+                    1,
+                )
+            }
+        } else if options.separate_octave_errors && expected_index.class() == actual_index.class() {
+            (
+                NoteDiff::WrongOctave {
+                    expected: e,
+                    actual: a,
+                },
+                1,
+            )
+        } else {
+            (
+                NoteDiff::WrongPitch {
+                    expected: e,
+                    actual: a,
+                },
+                3,
+            )
+        }
+    };
+
+    // costs[e][a] - cost of aligning expected[e..] with actual[a..]
+    let mut costs = vec![vec![0; actual.len() + 1]; expected.len() + 1];
+    for e in (0..=expected.len()).rev() {
+        for a in (0..=actual.len()).rev() {
+            costs[e][a] = if e == expected.len() {
+                (actual.len() - a) as u32 * INDEL_COST
+            } else if a == actual.len() {
+                (expected.len() - e) as u32 * INDEL_COST
+            } else {
+                let pair = classify(e, a).1 + costs[e + 1][a + 1];
+                let missing = INDEL_COST + costs[e + 1][a];
+                let extra = INDEL_COST + costs[e][a + 1];
+                pair.min(missing).min(extra)
+            };
+        }
+    }
+
+    let mut diff = vec![];
+    let (mut e, mut a) = (0, 0);
+    while e < expected.len() || a < actual.len() {
+        if e < expected.len() && a < actual.len() {
+            let (pair, cost) = classify(e, a);
+            if cost + costs[e + 1][a + 1] == costs[e][a] {
+                diff.push(pair);
+                e += 1;
+                a += 1;
+                continue;
+            }
+        }
+        if e < expected.len() && INDEL_COST + costs[e + 1][a] == costs[e][a] {
+            diff.push(NoteDiff::Missing { expected: e });
+            e += 1;
+        } else {
+            diff.push(NoteDiff::Extra { actual: a });
+            a += 1;
+        }
+    }
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pitches(indices: &[usize]) -> Vec<Pitch> {
+        indices.iter().map(|index| PITCHES[*index]).collect()
+    }
+
+    #[test]
+    fn tonal_center_of_melody_dwelling_on_c() {
+        // C4 D4 C4 E4 C4 G4 C5
+        let melody = pitches(&[48, 50, 48, 52, 48, 55, 60]);
+        assert_eq!(tonal_center(&melody), Some(PitchClass::new(0)));
+        // ties go to pitch class appearing first
+        assert_eq!(tonal_center(&pitches(&[50, 48])), Some(PitchClass::new(2)));
+        assert_eq!(tonal_center(&[]), None);
+    }
+
+    #[test]
+    fn octave_jump_is_flagged() {
+        // C4 D4 E4 E6 F4 G4
+        let notes: Vec<Note> = pitches(&[48, 50, 52, 76, 53, 55])
+            .into_iter()
+            .map(Note::from)
+            .collect();
+        assert_eq!(flag_octave_outliers(&notes, 12), [3, 4]);
+        assert_eq!(flag_octave_outliers(&notes, 23), [3]);
+        assert_eq!(flag_octave_outliers(&notes, 24), []);
+        assert_eq!(flag_octave_outliers(&notes, 1), [1, 2, 3, 4, 5]);
+        assert_eq!(flag_octave_outliers(&notes[..1], 0), []);
+    }
+
+    fn notes(names: &str) -> Vec<Note> {
+        names
+            .split_whitespace()
+            .map(|name| name.parse().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn diff_classifications() {
+        use NoteDiff::*;
+
+        let options = DiffOptions::default();
+        let expected = notes("C4 C#4 E4 G4 B4");
+        assert_eq!(
+            diff_notes(&expected, &expected, options),
+            (0..5)
+                .map(|i| Correct {
+                    expected: i,
+                    actual: i
+                })
+                .collect::<Vec<_>>()
+        );
+
+        let actual = notes("C4 Db4 E5 A4 B4");
+        assert_eq!(
+            diff_notes(&expected, &actual, options),
+            [
+                Correct {
+                    expected: 0,
+                    actual: 0
+                },
+                Enharmonic {
+                    expected: 1,
+                    actual: 1
+                },
+                WrongOctave {
+                    expected: 2,
+                    actual: 2
+                },
+                WrongPitch {
+                    expected: 3,
+                    actual: 3
+                },
+                Correct {
+                    expected: 4,
+                    actual: 4
+                },
+            ]
+        );
+
+        let options = DiffOptions {
+            enharmonic_is_correct: true,
+            separate_octave_errors: false,
+        };
+        assert_eq!(
+            diff_notes(&expected, &actual, options)[1..3],
+            [
+                Correct {
+                    expected: 1,
+                    actual: 1
+                },
+                WrongPitch {
+                    expected: 2,
+                    actual: 2
+                },
+            ]
+        );
+
+        // explicit natural isn't a spelling error
+        let options = DiffOptions::default();
+        assert_eq!(
+            diff_notes(&notes("C4 Fn4"), &notes("Cn4 F4"), options),
+            [
+                Correct {
+                    expected: 0,
+                    actual: 0
+                },
+                Correct {
+                    expected: 1,
+                    actual: 1
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn diff_missing_and_extra_notes() {
+        use NoteDiff::*;
+
+        let options = DiffOptions::default();
+        assert_eq!(
+            diff_notes(&notes("C4 D4 E4"), &notes("C4 E4"), options),
+            [
+                Correct {
+                    expected: 0,
+                    actual: 0
+                },
+                Missing { expected: 1 },
+                Correct {
+                    expected: 2,
+                    actual: 1
+                },
+            ]
+        );
+        assert_eq!(
+            diff_notes(&notes("C4 E4"), &notes("C4 D4 E4"), options),
+            [
+                Correct {
+                    expected: 0,
+                    actual: 0
+                },
+                Extra { actual: 1 },
+                Correct {
+                    expected: 1,
+                    actual: 2
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn diff_of_empty_sequences() {
+        let options = DiffOptions::default();
+        assert_eq!(diff_notes(&[], &[], options), []);
+        assert_eq!(
+            diff_notes(&notes("C4 D4"), &[], options),
+            [
+                NoteDiff::Missing { expected: 0 },
+                NoteDiff::Missing { expected: 1 }
+            ]
+        );
+        assert_eq!(
+            diff_notes(&[], &notes("C4"), options),
+            [NoteDiff::Extra { actual: 0 }]
+        );
+    }
+
+    #[test]
+    fn retrograde_of_motif() {
+        // C4 D4 E4 G4
+        let motif = pitches(&[48, 50, 52, 55]);
+        assert_eq!(retrograde(&motif), pitches(&[55, 52, 50, 48]));
+        assert_eq!(retrograde(&retrograde(&motif)), motif);
+        assert_eq!(retrograde(&[]), []);
+    }
+
+    #[test]
+    fn inversion_of_motif_around_middle_c() {
+        // C4 D4 E4 G4 B3 - C4 B♭3 G♯3 F3 C♯4
+        let motif = pitches(&[48, 50, 52, 55, 47]);
+        let middle_c = PITCHES[48];
+        let inverted = invert(&motif, middle_c);
+        assert_eq!(
+            inverted,
+            pitches(&[48, 46, 44, 41, 49])
+                .into_iter()
+                .map(Some)
+                .collect::<Vec<_>>()
+        );
+        let inverted: Vec<Pitch> = inverted.into_iter().flatten().collect();
+        assert_eq!(
+            invert(&inverted, middle_c),
+            motif.into_iter().map(Some).collect::<Vec<_>>()
+        );
+
+        // mirrored out of supported range
+        assert_eq!(invert(&pitches(&[2, 57]), PITCHES[100]), [None, None]);
+        assert_eq!(invert(&pitches(&[0]), PITCHES[60]), [None]);
+        assert_eq!(invert(&pitches(&[10]), PITCHES[5]), [Some(PITCHES[0])]);
+    }
+
+    #[test]
+    fn parallel_fifths() {
+        // C4 D4 E4 F4 over F3 G3 A3 D3 - fifths at positions 0, 1 and 2
+        let upper = pitches(&[48, 50, 52, 53]);
+        let lower = pitches(&[41, 43, 45, 38]);
+        assert_eq!(has_parallel_fifths(&upper, &lower), [1, 2]);
+        assert_eq!(has_parallel_fifths(&lower, &upper), [1, 2]);
+
+        // compound fifths count too
+        let lower = pitches(&[29, 31, 45, 38]);
+        assert_eq!(has_parallel_fifths(&upper, &lower), [1, 2]);
+
+        // contrary motion and repeated fifths are clean
+        let clean_upper = pitches(&[48, 50, 52, 52]);
+        let clean_lower = pitches(&[41, 38, 45, 45]);
+        assert_eq!(has_parallel_fifths(&clean_upper, &clean_lower), []);
+        assert_eq!(has_parallel_fifths(&upper, &lower[..1]), []);
+    }
+
+    #[test]
+    fn voice_crossings_of_lines() {
+        // E4 D4 C4 B3 C4 over C4 B3 A3 D4 E4 - upper voice drops below at positions 3 and 4
+        let upper = pitches(&[52, 50, 48, 47, 48]);
+        let lower = pitches(&[48, 47, 45, 50, 52]);
+        assert_eq!(voice_crossings(&upper, &lower), [3, 4]);
+        assert_eq!(voice_crossings(&lower, &upper), [0, 1, 2]);
+
+        // unison isn't a crossing
+        let lower = pitches(&[48, 47, 48, 43]);
+        assert!(voice_crossings(&upper, &lower).is_empty());
+        assert!(voice_crossings(&upper, &[]).is_empty());
+    }
+
+    #[test]
+    fn mean_interval_of_stepwise_and_leaping_melodies() {
+        let mean =
+            |indices: &[usize]| mean_interval_cents(&pitches(indices)).map(|cents| cents.value());
+        // chromatic C4 C♯4 D4 C♯4 C4
+        assert_eq!(mean(&[48, 49, 50, 49, 48]), Some(100.0));
+        // C4 D4 E4 D4 C4 B3 C4
+        let stepwise = mean(&[48, 50, 52, 50, 48, 47, 48]).unwrap();
+        assert!((stepwise - 1000.0 / 6.0).abs() < 1e-9);
+        // leaps of fifths C4 G4 D5 G4
+        assert!((mean(&[48, 55, 62, 55]).unwrap() - 700.0).abs() < 1e-9);
+        assert_eq!(mean(&[48]), None);
+        assert_eq!(mean(&[]), None);
+    }
+
+    #[test]
+    fn voice_leading_distances() {
+        let c_major = pitches(&[48, 52, 55]);
+        assert_eq!(voice_leading_distance(&c_major, &c_major), Some(0));
+        // G major as B3 D4 G4 - C4 to B3, E4 to D4, G4 stays
+        let g_major = pitches(&[55, 47, 50]);
+        assert_eq!(voice_leading_distance(&c_major, &g_major), Some(3));
+        assert_eq!(voice_leading_distance(&g_major, &c_major), Some(3));
+        assert_eq!(voice_leading_distance(&c_major, &pitches(&[48, 52])), None);
+        assert_eq!(voice_leading_distance(&[], &[]), Some(0));
+
+        // the same as the best of all pairings
+        let permutations = [
+            [0, 1, 2],
+            [0, 2, 1],
+            [1, 0, 2],
+            [1, 2, 0],
+            [2, 0, 1],
+            [2, 1, 0],
+        ];
+        for a in [[48, 52, 55], [40, 64, 67], [50, 53, 57]] {
+            for b in [[47, 50, 55], [36, 60, 76], [49, 49, 52], [57, 60, 64]] {
+                let (a, b) = (pitches(&a), pitches(&b));
+                let best = permutations
+                    .iter()
+                    .map(|permutation| {
+                        (0..3)
+                            .map(|i| a[i].semitones_to(b[permutation[i]]).unsigned_abs() as u32)
+                            .sum::<u32>()
+                    })
+                    .min();
+                assert_eq!(voice_leading_distance(&a, &b), best);
+            }
+        }
+    }
+}
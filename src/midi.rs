@@ -0,0 +1,375 @@
+//! MIDI helpers.
+
+use crate::{
+    Accidental, Cents, ChromaticIndex, Detuned, Error, Letter, Note, Octave, Tuning, PITCHES,
+};
+
+/// MIDI note number and pitch bend sounding a [detuned](Detuned) pitch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MidiPitchEvent {
+    /// MIDI note number.
+    pub note: u8,
+    /// 14-bit pitch bend value (0 - 16383, 8192 meaning no bend).
+    pub bend: u16,
+}
+
+/// Convert detuned pitches to MIDI note numbers and pitch bends,
+/// with pitch bend range of `bend_range` semitones (in either direction).
+///
+/// Each pitch is mapped to the MIDI note nearest to it (pitches exactly
+/// between two notes go to the higher one), so the bend never exceeds 50 cents.
+/// Bend is rounded to the nearest step, full upward bend is 16383.
+///
+/// Fails if bend range isn't positive and finite, or with index of the first pitch
+/// whose MIDI note isn't in range 0 - 127 or whose bend exceeds bend range.
+pub fn to_midi_events(pitches: &[Detuned], bend_range: f64) -> Result<Vec<MidiPitchEvent>, Error> {
+    if !(bend_range.is_finite() && bend_range > 0.0) {
+        return Err(Error::IncorrectBendRange);
+    }
+
+    pitches
+        .iter()
+        .enumerate()
+        .map(|(index, pitch)| {
+            let cents = pitch.cents().value();
+            let semitones = (cents / 100.0 + 0.5).floor();
+            let note = pitch.pitch().midi_number() as f64 + semitones;
+            if !(0.0..=127.0).contains(&note) {
+                return Err(Error::MidiNoteNotInRange { index });
+            }
+
+            let bend = (cents - 100.0 * semitones) / (100.0 * bend_range);
+            if bend.abs() > 1.0 {
+                return Err(Error::BendNotInRange { index });
+            }
+            let bend = (8192.0 + (bend * 8192.0).round()).min(16383.0);
+            Ok(MidiPitchEvent {
+                note: note as u8,
+                bend: bend as u16,
+            })
+        })
+        .collect()
+}
+
+/// Frequencies of all MIDI notes (0 - 127) in equal-tempered scale, A₄ = 440 Hz.
+///
+/// Computed from the formula (not taken from [FREQUENCIES](crate::FREQUENCIES)),
+/// so it also covers notes outside of supported range of [Pitch](crate::Pitch).
+#[must_use]
+pub fn midi_frequencies() -> [f64; 128] {
+    let tuning = Tuning::default();
+    std::array::from_fn(|note| tuning.frequency_of_index(ChromaticIndex(note as i16 - 12)))
+}
+
+/// Number of MIDI 2.0 pitch steps per semitone (9-bit fraction).
+const MIDI2_STEPS: f64 = 512.0;
+
+impl Detuned {
+    /// Convert to MIDI 2.0 per-note pitch - 7.9 fixed-point number
+    /// (7 bits of MIDI note number, 9 bits of fraction of a semitone).
+    ///
+    /// Rounded to the nearest 1/512 of a semitone (values exactly between two steps
+    /// go to the higher one), for example: A₄ detuned by 25 cents is 35456.
+    ///
+    /// Fails if pitch is outside of representable range (MIDI note 0 to 127 + 511/512).
+    pub fn to_midi2_pitch(&self) -> Result<u16, Error> {
+        midi2_pitch_from_semitones(69.0 + self.cents_from_a4().value() / 100.0)
+    }
+
+    /// Create detuned pitch from MIDI 2.0 per-note pitch (7.9 fixed-point number).
+    ///
+    /// Pitch is the one nearest to the value (values exactly between two pitches
+    /// go to the higher one), so deviation never exceeds 50 cents -
+    /// except for values outside of supported range of [Pitch](crate::Pitch),
+    /// which are expressed as deviation from the lowest or the highest pitch.
+    #[must_use]
+    pub fn from_midi2_pitch(value: u16) -> Detuned {
+        let semitones = value as f64 / MIDI2_STEPS;
+        let lowest = PITCHES[0].midi_number() as f64;
+        let highest = PITCHES[PITCHES.len() - 1].midi_number() as f64;
+        let note = (semitones + 0.5).floor().clamp(lowest, highest);
+        let pitch = PITCHES[(note - lowest) as usize];
+        let cents = Cents::new_unchecked(100.0 * (semitones - note));
+        Detuned::new(pitch, cents)
+    }
+}
+
+/// Convert frequency to MIDI 2.0 per-note pitch (7.9 fixed-point number)
+/// in standard tuning (A₄ = 440 Hz) - see [Detuned::to_midi2_pitch].
+///
+/// Fails if frequency isn't positive and finite, or is outside of representable range.
+pub fn midi2_pitch_from_frequency(frequency: f64) -> Result<u16, Error> {
+    if !(frequency.is_finite() && frequency > 0.0) {
+        return Err(Error::IncorrectFrequency);
+    }
+    let cents = Tuning::default().cents_from_a4(frequency);
+    midi2_pitch_from_semitones(69.0 + cents.value() / 100.0)
+}
+
+/// Frequency of MIDI 2.0 per-note pitch (7.9 fixed-point number)
+/// in standard tuning (A₄ = 440 Hz).
+#[must_use]
+pub fn frequency_from_midi2_pitch(value: u16) -> f64 {
+    let semitones = value as f64 / MIDI2_STEPS;
+    Tuning::default().a4() * 2.0_f64.powf((semitones - 69.0) / 12.0)
+}
+
+fn midi2_pitch_from_semitones(semitones: f64) -> Result<u16, Error> {
+    let value = (semitones * MIDI2_STEPS + 0.5).floor();
+    if (0.0..=u16::MAX as f64).contains(&value) {
+        Ok(value as u16)
+    } else {
+        Err(Error::PitchNotInRange)
+    }
+}
+
+/// MIDI note number carrying preferred spelling of the note, so it can be
+/// recovered when converting back (MIDI note numbers alone lose it - C♯₄ and D♭₄ are both 61).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SpelledMidi {
+    /// MIDI note number.
+    pub midi: u8,
+    /// Preferred accidental of the note.
+    pub prefer: Accidental,
+}
+
+impl TryFrom<Note> for SpelledMidi {
+    type Error = Error;
+
+    /// Fails if note has no MIDI note number (like notes above G₉).
+    fn try_from(note: Note) -> Result<Self, Self::Error> {
+        let midi = note.chromatic_index().value() + 12;
+        Ok(SpelledMidi {
+            midi: u8::try_from(midi)
+                .ok()
+                .filter(|midi| *midi <= 127)
+                .ok_or(Error::PitchNotInRange)?,
+            prefer: note.accidental(),
+        })
+    }
+}
+
+impl TryFrom<SpelledMidi> for Note {
+    type Error = Error;
+
+    /// Note is spelled with preferred accidental if possible (so notes converted to [SpelledMidi]
+    /// convert back to themselves), otherwise the same way as [ChromaticIndex] is.
+    ///
+    /// Fails if note is outside of supported octaves (MIDI note numbers below 12).
+    fn try_from(value: SpelledMidi) -> Result<Self, Self::Error> {
+        let index = ChromaticIndex(value.midi as i16 - 12);
+        let natural = index.transpose(-value.prefer.alteration() as i16);
+        let octave = u8::try_from(natural.value().div_euclid(12))
+            .ok()
+            .and_then(|octave| Octave::try_from(octave).ok());
+        let letter = Letter::ALL.into_iter().find(|letter| {
+            Note::new_unchecked_spelling(*letter, Octave::First, Accidental::None)
+                .chromatic_index()
+                .class()
+                == natural.class()
+        });
+        match (letter, octave) {
+            (Some(letter), Some(octave)) => {
+                Ok(Note::new_unchecked_spelling(letter, octave, value.prefer))
+            }
+            _ => index.try_into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detuned(index: usize, cents: f64) -> Detuned {
+        Detuned::new(PITCHES[index], Cents::new(cents).unwrap())
+    }
+
+    fn event(note: u8, bend: u16) -> MidiPitchEvent {
+        MidiPitchEvent { note, bend }
+    }
+
+    #[test]
+    fn midi_events_of_detuned_pitches() {
+        let pitches = [
+            detuned(57, 0.0),
+            detuned(57, 25.0),
+            detuned(57, -25.0),
+            detuned(57, 50.0),
+            detuned(57, -50.0),
+            detuned(57, 99.0),
+            detuned(48, -1150.0),
+            detuned(0, 0.0),
+            detuned(107, 0.0),
+        ];
+        assert_eq!(
+            to_midi_events(&pitches, 2.0),
+            Ok(vec![
+                event(69, 8192),
+                // 25 cents of 200 - 1/8 of full bend (8192 steps)
+                event(69, 9216),
+                event(69, 7168),
+                // exactly between A4 and A♯4 - higher note, bent down
+                event(70, 6144),
+                event(69, 6144),
+                // -1 cent: -40.96 steps
+                event(70, 8151),
+                // 11.5 semitones below C4 - exactly between C3 and C♯3
+                event(49, 6144),
+                event(12, 8192),
+                event(119, 8192),
+            ])
+        );
+    }
+
+    #[test]
+    fn full_bends() {
+        let pitches = [detuned(57, 25.0), detuned(57, -25.0)];
+        assert_eq!(
+            to_midi_events(&pitches, 0.25),
+            Ok(vec![event(69, 16383), event(69, 0)])
+        );
+    }
+
+    #[test]
+    fn midi_event_errors() {
+        let pitches = [detuned(57, 0.0), detuned(57, 30.0), detuned(57, 40.0)];
+        assert_eq!(
+            to_midi_events(&pitches, 0.25),
+            Err(Error::BendNotInRange { index: 1 })
+        );
+        let pitches = [detuned(57, 0.0), detuned(107, 850.0), detuned(0, -1300.0)];
+        assert_eq!(
+            to_midi_events(&pitches, 2.0),
+            Err(Error::MidiNoteNotInRange { index: 1 })
+        );
+        assert_eq!(
+            to_midi_events(&pitches[2..], 2.0),
+            Err(Error::MidiNoteNotInRange { index: 0 })
+        );
+        for bend_range in [0.0, -2.0, f64::NAN, f64::INFINITY] {
+            assert_eq!(
+                to_midi_events(&pitches, bend_range),
+                Err(Error::IncorrectBendRange)
+            );
+        }
+        assert_eq!(to_midi_events(&[], 2.0), Ok(vec![]));
+    }
+
+    #[test]
+    fn midi_frequencies_cover_full_range() {
+        let frequencies = midi_frequencies();
+        assert_eq!(frequencies[69], 440.0);
+        assert_eq!(frequencies[57], 220.0);
+        assert!((frequencies[0] - 8.175_798_9).abs() < 1e-6);
+        assert!((frequencies[127] - 12_543.853_951).abs() < 1e-6);
+        for pitch in PITCHES.iter() {
+            let frequency = frequencies[pitch.midi_number() as usize];
+            assert!((frequency - pitch.exact_frequency()).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn midi2_pitch_of_detuned_a4() {
+        // (69 + 25/100) * 512
+        assert_eq!(detuned(57, 25.0).to_midi2_pitch(), Ok(35456));
+        assert_eq!(detuned(57, 0.0).to_midi2_pitch(), Ok(69 * 512));
+        assert_eq!(Detuned::from_midi2_pitch(35456), detuned(57, 25.0));
+        assert_eq!(midi2_pitch_from_frequency(440.0), Ok(69 * 512));
+        assert_eq!(frequency_from_midi2_pitch(69 * 512), 440.0);
+    }
+
+    #[test]
+    fn midi2_pitch_round_trips() {
+        for value in 0..=u16::MAX {
+            let detuned = Detuned::from_midi2_pitch(value);
+            assert_eq!(detuned.to_midi2_pitch(), Ok(value));
+            let frequency = frequency_from_midi2_pitch(value);
+            assert_eq!(midi2_pitch_from_frequency(frequency), Ok(value));
+        }
+        for index in 0..PITCHES.len() {
+            for cents in (-50..50).map(f64::from) {
+                let pitch = detuned(index, cents);
+                let back = Detuned::from_midi2_pitch(pitch.to_midi2_pitch().unwrap());
+                assert_eq!(back.pitch(), pitch.pitch());
+                // half of 1/512 of a semitone
+                assert!((back.cents().value() - cents).abs() <= 100.0 / 1024.0);
+            }
+        }
+    }
+
+    #[test]
+    fn midi2_pitch_errors() {
+        // C0 is MIDI note 12
+        assert_eq!(detuned(0, -1200.0).to_midi2_pitch(), Ok(0));
+        assert_eq!(
+            detuned(0, -1201.0).to_midi2_pitch(),
+            Err(Error::PitchNotInRange)
+        );
+        // B8 is MIDI note 119
+        assert_eq!(detuned(107, 899.0).to_midi2_pitch(), Ok(u16::MAX - 4));
+        assert_eq!(
+            detuned(107, 900.0).to_midi2_pitch(),
+            Err(Error::PitchNotInRange)
+        );
+        assert_eq!(
+            midi2_pitch_from_frequency(0.0),
+            Err(Error::IncorrectFrequency)
+        );
+        assert_eq!(
+            midi2_pitch_from_frequency(f64::NAN),
+            Err(Error::IncorrectFrequency)
+        );
+        assert_eq!(
+            midi2_pitch_from_frequency(20_000.0),
+            Err(Error::PitchNotInRange)
+        );
+    }
+
+    #[test]
+    fn spelling_survives_midi_round_trip() {
+        let note =
+            |letter, accidental| Note::new_unchecked_spelling(letter, Octave::Fifth, accidental);
+        let c_sharp = note(Letter::C, Accidental::Sharp);
+        let d_flat = note(Letter::D, Accidental::Flat);
+        let spelled = SpelledMidi::try_from(c_sharp).unwrap();
+        assert_eq!(spelled.midi, 61);
+        assert_eq!(Note::try_from(spelled), Ok(c_sharp));
+        let spelled = SpelledMidi::try_from(d_flat).unwrap();
+        assert_eq!(spelled.midi, 61);
+        assert_eq!(Note::try_from(spelled), Ok(d_flat));
+
+        for note in [
+            note(Letter::C, Accidental::Flat),
+            note(Letter::E, Accidental::Natural),
+            note(Letter::A, Accidental::None),
+            Note::new_unchecked_spelling(Letter::B, Octave::Fourth, Accidental::Sharp),
+        ] {
+            assert_eq!(
+                Note::try_from(SpelledMidi::try_from(note).unwrap()),
+                Ok(note)
+            );
+        }
+    }
+
+    #[test]
+    fn impossible_spelling_falls_back_to_sharps() {
+        let spelled = SpelledMidi {
+            midi: 61,
+            prefer: Accidental::Natural,
+        };
+        assert_eq!(
+            Note::try_from(spelled),
+            Ok(Note::new_unchecked_spelling(
+                Letter::C,
+                Octave::Fifth,
+                Accidental::Sharp
+            ))
+        );
+        let too_low = SpelledMidi {
+            midi: 11,
+            prefer: Accidental::None,
+        };
+        assert!(Note::try_from(too_low).is_err());
+    }
+}
@@ -1,32 +1,38 @@
 //! Note representation.
 
-use std::fmt::Display;
+use std::{borrow::Cow, cmp::Ordering, fmt::Display, str::FromStr};
 
-use crate::{Pitch, PITCHES};
+use lazy_static::lazy_static;
 
-/// Error that can occur during note creation.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum Error {
-    /// Incorrect note letter.
-    IncorrectLetter,
-    /// Incorrect note accidental.
-    IncorrectAccidental,
-    /// Octave is not in supported range.
-    OctaveNotInRange,
-}
+use crate::{Cents, ChromaticIndex, Duration, Error, NamedInterval, Pitch, Tuning};
 
-impl Display for Error {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Error::IncorrectLetter => write!(f, "incorrect letter"),
-            Error::IncorrectAccidental => write!(f, "incorrect accidental"),
-            Error::OctaveNotInRange => write!(f, "octave not in range"),
-        }
-    }
+lazy_static! {
+    /// [Unicode](NoteStyle::Unicode) names of all letter, accidental and octave combinations,
+    /// indexed by [Note::name_slot].
+    static ref NOTE_NAMES: Vec<String> = Letter::ALL
+        .iter()
+        .flat_map(|letter| {
+            [
+                Accidental::None,
+                Accidental::Natural,
+                Accidental::Flat,
+                Accidental::Sharp,
+            ]
+            .into_iter()
+            .flat_map(move |accidental| {
+                (0..10).map(move |octave| {
+                    let octave = Octave::from_number(octave).expect("invariant");
+                    format!("{}{}{}", letter, accidental, octave)
+                })
+            })
+        })
+        .collect();
 }
 
 /// Struct representing musical note.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+///
+/// Alternate [Debug](std::fmt::Debug) format (`{:#?}`) prints [diagnostic](Note::diagnostic) string.
+#[derive(Clone, Copy, PartialEq, Eq)]
 pub struct Note {
     letter: Letter,
     octave: Octave,
@@ -34,81 +40,328 @@ pub struct Note {
 }
 
 impl Note {
-    /// Create new note.
+    /// Create new note with common spelling.
+    ///
+    /// Rejects theoretical spellings that have a simpler natural equivalent:
+    /// E♯, B♯, C♭ and F♭ (use [Note::new_unchecked_spelling] to create them).
     pub fn new(letter: Letter, octave: Octave, accidental: Accidental) -> Result<Self, Error> {
-        match accidental {
-            Accidental::None => Ok(Note {
-                letter,
-                octave,
-                accidental,
-            }),
-            Accidental::Flat => match letter {
-                Letter::C | Letter::F => Err(Error::IncorrectAccidental),
-                _ => Ok(Note {
-                    letter,
-                    octave,
-                    accidental,
-                }),
-            },
-            Accidental::Sharp => match letter {
-                Letter::E | Letter::B => Err(Error::IncorrectAccidental),
-                _ => Ok(Note {
-                    letter,
-                    octave,
-                    accidental,
-                }),
-            },
+        match (letter, accidental) {
+            (Letter::C | Letter::F, Accidental::Flat)
+            | (Letter::E | Letter::B, Accidental::Sharp) => Err(Error::IncorrectAccidental),
+            _ => Ok(Note::new_unchecked_spelling(letter, octave, accidental)),
+        }
+    }
+
+    /// Create new note with any spelling, including theoretical ones
+    /// rejected by [Note::new] (E♯, B♯, C♭ and F♭).
+    ///
+    /// Such notes are otherwise fully supported - they convert to [Pitch]
+    /// (for example: B♯₃ is C₄) and are displayed and parsed as expected.
+    /// Note that some of them (C♭₀ and B♯₉) have no corresponding [Pitch].
+    #[must_use]
+    pub fn new_unchecked_spelling(letter: Letter, octave: Octave, accidental: Accidental) -> Self {
+        Note {
+            letter,
+            octave,
+            accidental,
         }
     }
 
     /// Get note letter.
+    #[must_use]
     pub fn letter(&self) -> Letter {
         self.letter
     }
 
     /// Get note octave.
+    #[must_use]
     pub fn octave(&self) -> Octave {
         self.octave
     }
 
     /// Get note accidental (sharp - ♯ or flat - ♭).
+    #[must_use]
     pub fn accidental(&self) -> Accidental {
         self.accidental
     }
 
+    /// Get position of the note on chromatic scale.
+    ///
+    /// Well-defined even for notes that have no corresponding [Pitch].
+    #[must_use]
+    pub fn chromatic_index(&self) -> ChromaticIndex {
+        let octave: u8 = self.octave.into();
+        ChromaticIndex(
+            octave as i16 * 12
+                + self.letter.semitones() as i16
+                + self.accidental.alteration() as i16,
+        )
+    }
+
+    /// Get note an interval above this one, spelled according to the interval
+    /// (for example: major third above C₄ is E₄, minor third above C₄ is E♭₄).
+    ///
+    /// Returns `None` if correctly spelled note can't be represented (for example: augmented fifth above B).
+    #[must_use]
+    pub fn apply_interval(&self, interval: NamedInterval) -> Option<Note> {
+        let letter_index = self.letter.index() + interval.number() - 1;
+        let letter = Letter::from_index(letter_index);
+        let octave = u8::from(self.octave) + letter_index / 7;
+        let natural = octave as i16 * 12 + letter.semitones() as i16;
+        let target = self
+            .chromatic_index()
+            .transpose(interval.semitones() as i16);
+        let accidental = match target.value() - natural {
+            -1 => Accidental::Flat,
+            0 => Accidental::None,
+            1 => Accidental::Sharp,
+            _ => return None,
+        };
+        Some(Note::new_unchecked_spelling(
+            letter,
+            octave.try_into().ok()?,
+            accidental,
+        ))
+    }
+
+    /// Key for sorting notes in musical order.
+    ///
+    /// Notes are ordered by sounding pitch ([chromatic index](Note::chromatic_index)),
+    /// then by spelling: no accidental before natural before sharp before flat
+    /// (so C♯₄ goes before D♭₄ which goes before D₄) - the same way as [Ord] orders them.
+    ///
+    /// Theoretical spellings crossing octave boundary are sorted by the pitch they sound
+    /// (for example: C♭₄ goes right after B₃, B♯₄ right after C₅).
+    ///
+    /// This ordering is stable and won't change in future versions.
+    #[must_use]
+    pub fn sort_key(&self) -> impl Ord {
+        (self.chromatic_index(), self.spelling_rank())
+    }
+
+    /// Rank of note spelling among spellings of the same pitch.
+    fn spelling_rank(&self) -> u8 {
+        match self.accidental {
+            Accidental::None => 0,
+            Accidental::Natural => 1,
+            Accidental::Sharp => 2,
+            Accidental::Flat => 3,
+        }
+    }
+
+    /// Human-readable diagnostic description of the note, for example:
+    /// `D♭₄ = chromatic index 49 = 277.18 Hz = MIDI 61 (non-canonical spelling of C♯₄)`.
+    ///
+    /// Canonical spelling is the one obtained by converting [Pitch] to [Note].
+    ///
+    /// Format is meant for debugging only and may change between versions.
+    #[must_use]
+    pub fn diagnostic(&self) -> String {
+        let index = self.chromatic_index();
+        match index.try_into_pitch() {
+            Ok(pitch) => {
+                let canonical = Note::from(pitch);
+                let spelling = if canonical == *self {
+                    "canonical spelling".to_string()
+                } else {
+                    format!("non-canonical spelling of {}", canonical)
+                };
+                format!(
+                    "{} = chromatic index {} = {} Hz = MIDI {} ({})",
+                    self,
+                    index.value(),
+                    pitch.frequency(),
+                    pitch.midi_number(),
+                    spelling
+                )
+            }
+            Err(_) => format!("{} = chromatic index {} (no pitch)", self, index.value()),
+        }
+    }
+
+    /// Format note name in given style.
+    ///
+    /// [Unicode](NoteStyle::Unicode) names are borrowed from a table computed once,
+    /// so formatting in default style doesn't allocate.
+    #[must_use]
+    pub fn format_in(&self, style: NoteStyle) -> Cow<'static, str> {
+        match style {
+            NoteStyle::Unicode => Cow::Borrowed(self.unicode_name()),
+            NoteStyle::Ascii => {
+                let accidental = match self.accidental {
+                    Accidental::None => "",
+                    Accidental::Natural => "n",
+                    Accidental::Flat => "b",
+                    Accidental::Sharp => "#",
+                };
+                Cow::Owned(format!(
+                    "{}{}{}",
+                    self.letter,
+                    accidental,
+                    u8::from(self.octave)
+                ))
+            }
+        }
+    }
+
+    /// Braille music notation of the note as a quarter note - see [Note::to_braille_with].
+    #[must_use]
+    pub fn to_braille(&self) -> String {
+        self.to_braille_with(Duration::QUARTER)
+            .expect("quarter note has Braille note cell")
+    }
+
+    /// Braille music notation of the note with given duration (for example: "⠐⠹" for quarter C₄,
+    /// "⠩⠨⠟" for half F♯₅) - accidental sign, octave mark and note cell
+    /// (encoding both letter and duration), followed by dot for dotted notes.
+    ///
+    /// Octave mark is always written. As in Braille music code, note cells
+    /// of eighth, quarter, half and whole notes also stand for 128th, 64th, 32nd and 16th notes.
+    /// Notes above 7th octave (octaves 8 and 9) get doubled 7th octave mark.
+    ///
+    /// Returns `None` for durations other than (single dotted) whole notes to 128th notes.
+    #[must_use]
+    pub fn to_braille_with(&self, duration: Duration) -> Option<String> {
+        // cells of eighth, quarter, half and whole notes
+        let cells = match self.letter {
+            Letter::C => ['⠙', '⠹', '⠝', '⠽'],
+            Letter::D => ['⠑', '⠱', '⠕', '⠵'],
+            Letter::E => ['⠋', '⠫', '⠏', '⠯'],
+            Letter::F => ['⠛', '⠻', '⠟', '⠿'],
+            Letter::G => ['⠓', '⠳', '⠗', '⠷'],
+            Letter::A => ['⠊', '⠪', '⠎', '⠮'],
+            Letter::B => ['⠚', '⠺', '⠞', '⠾'],
+        };
+        let (value, dotted) = [4.0, 2.0, 1.0, 0.5, 0.25, 0.125, 0.0625, 0.03125]
+            .into_iter()
+            .enumerate()
+            .find_map(|(i, beats)| {
+                let cell = 3 - i % 4;
+                if duration.beats() == beats {
+                    Some((cell, false))
+                } else if duration.beats() == 1.5 * beats {
+                    Some((cell, true))
+                } else {
+                    None
+                }
+            })?;
+        let octave = match self.octave {
+            Octave::First => "⠈⠈",
+            Octave::Second => "⠈",
+            Octave::Third => "⠘",
+            Octave::Fourth => "⠸",
+            Octave::Fifth => "⠐",
+            Octave::Sixth => "⠨",
+            Octave::Seventh => "⠰",
+            Octave::Eighth => "⠠",
+            Octave::Ninth | Octave::Tenth => "⠠⠠",
+        };
+        let accidental = match self.accidental {
+            Accidental::None => "",
+            Accidental::Natural => "⠡",
+            Accidental::Flat => "⠣",
+            Accidental::Sharp => "⠩",
+        };
+        let dot = if dotted { "⠄" } else { "" };
+        Some(format!("{}{}{}{}", accidental, octave, cells[value], dot))
+    }
+
+    /// Name of the note in [Unicode](NoteStyle::Unicode) style, from precomputed table.
+    pub(crate) fn unicode_name(&self) -> &'static str {
+        &NOTE_NAMES[self.name_slot()]
+    }
+
+    /// Position of the note in [NOTE_NAMES] table.
+    fn name_slot(&self) -> usize {
+        let accidental = match self.accidental {
+            Accidental::None => 0,
+            Accidental::Natural => 1,
+            Accidental::Flat => 2,
+            Accidental::Sharp => 3,
+        };
+        (self.letter.index() as usize * 4 + accidental) * 10 + self.octave.number() as usize
+    }
+
+    /// Position of the note in cents relative to A₄ (for example: -900 for C₄).
+    ///
+    /// Well-defined even for notes that have no corresponding [Pitch].
+    #[must_use]
+    pub fn cents_from_a4(&self) -> Cents {
+        self.chromatic_index().cents_from_a4()
+    }
+
     /// Get note with the same pitch but different accidental (or exactly the same note if there isn't one).
     ///
-    /// For example: for C♯ - D♭ is returned.
+    /// For example: for C♯ - D♭ is returned, for E♯ - F.
+    #[must_use]
     pub fn enharmonic(self) -> Note {
         match self.accidental {
-            Accidental::None => self,
-            Accidental::Flat => Note {
-                letter: self.letter.previous(),
-                octave: self.octave,
-                accidental: Accidental::Sharp,
+            Accidental::None | Accidental::Natural => self,
+            Accidental::Flat => match self.letter {
+                Letter::C | Letter::F => self.simplify(),
+                letter => {
+                    Note::new_unchecked_spelling(letter.previous(), self.octave, Accidental::Sharp)
+                }
             },
-            Accidental::Sharp => Note {
-                letter: self.letter.next(),
-                octave: self.octave,
-                accidental: Accidental::Flat,
+            Accidental::Sharp => match self.letter {
+                Letter::E | Letter::B => self.simplify(),
+                letter => {
+                    Note::new_unchecked_spelling(letter.next(), self.octave, Accidental::Flat)
+                }
             },
         }
     }
+
+    /// Get simplest spelling of the note - theoretical spellings (E♯, B♯, C♭ and F♭)
+    /// are replaced with their natural equivalents, other notes are returned unchanged.
+    ///
+    /// C♭₀ and B♯₉ are returned unchanged as well, as their natural equivalents
+    /// fall outside supported octaves.
+    #[must_use]
+    pub fn simplify(self) -> Note {
+        let (letter, octave) = match (self.letter, self.accidental) {
+            (Letter::C, Accidental::Flat) => (Letter::B, self.octave.number().checked_sub(1)),
+            (Letter::F, Accidental::Flat) => (Letter::E, Some(self.octave.number())),
+            (Letter::E, Accidental::Sharp) => (Letter::F, Some(self.octave.number())),
+            (Letter::B, Accidental::Sharp) => (Letter::C, Some(self.octave.number() + 1)),
+            _ => return self,
+        };
+        match octave.and_then(Octave::from_number) {
+            Some(octave) => Note::new_unchecked_spelling(letter, octave, Accidental::None),
+            None => self,
+        }
+    }
 }
 
 impl PartialOrd for Note {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        let a = Pitch::from(*self);
-        let b = Pitch::from(*other);
-        a.partial_cmp(&b)
+        Some(self.cmp(other))
     }
 }
 
 impl Ord for Note {
+    /// Notes are ordered by pitch, different spellings of the same pitch
+    /// by accidental: none, natural, sharp, flat (so F₄ < F♮₄ < E♯₄ and C♯₄ < D♭₄).
+    ///
+    /// Ordering is total - it also covers notes without corresponding [Pitch] (like B♯₉).
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        let a = Pitch::from(*self);
-        let b = Pitch::from(*other);
-        a.cmp(&b)
+        self.chromatic_index()
+            .cmp(&other.chromatic_index())
+            .then_with(|| self.spelling_rank().cmp(&other.spelling_rank()))
+    }
+}
+
+impl std::fmt::Debug for Note {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if f.alternate() {
+            write!(f, "{}", self.diagnostic())
+        } else {
+            f.debug_struct("Note")
+                .field("letter", &self.letter)
+                .field("octave", &self.octave)
+                .field("accidental", &self.accidental)
+                .finish()
+        }
     }
 }
 
@@ -118,11 +371,121 @@ impl Display for Note {
     }
 }
 
+/// Compare two note names (like `"C#4"` or `"D♭₄"`) by their [sort keys](Note::sort_key).
+pub fn cmp_note_names(a: &str, b: &str) -> Result<Ordering, Error> {
+    let a = parse_note(a)?;
+    let b = parse_note(b)?;
+    Ok(a.sort_key().cmp(&b.sort_key()))
+}
+
+/// Harmonic spectrum of note (in standard tuning, A₄ = 440 Hz) for additive synthesis -
+/// `(frequency, amplitude)` of first `partials` harmonic partials, starting with the fundamental.
+///
+/// Fundamental has amplitude 1, every next partial has amplitude of the previous one
+/// multiplied by `rolloff` (for example: 1, 0.5, 0.25 ... for rolloff 0.5).
+#[must_use]
+pub fn overtone_spectrum(note: Note, partials: usize, rolloff: f64) -> Vec<(f64, f64)> {
+    let fundamental = Tuning::default().frequency_of_index(note.chromatic_index());
+    (1..=partials)
+        .scan(1.0, |amplitude, partial| {
+            let current = *amplitude;
+            *amplitude *= rolloff;
+            Some((fundamental * partial as f64, current))
+        })
+        .collect()
+}
+
+/// Parse note name: letter, optional accidental (`#`, `♯`, `b`, `♭`, `n`, `♮`)
+/// and octave number (ASCII or subscript digit).
+///
+/// Theoretical spellings (like E♯) are accepted.
+pub(crate) fn parse_note(name: &str) -> Result<Note, Error> {
+    let mut characters = name.chars().peekable();
+    let letter: Letter = characters
+        .next()
+        .ok_or(Error::IncorrectLetter)?
+        .try_into()?;
+    let accidental = match characters.peek() {
+        Some('#' | '♯') => Accidental::Sharp,
+        Some('b' | '♭') => Accidental::Flat,
+        Some('n' | '♮') => Accidental::Natural,
+        _ => Accidental::None,
+    };
+    if accidental != Accidental::None {
+        characters.next();
+    }
+
+    let octave = match characters.next() {
+        Some(digit @ '0'..='9') => digit as u32 - '0' as u32,
+        Some(digit @ '₀'..='₉') => digit as u32 - '₀' as u32,
+        Some(_) if accidental == Accidental::None => return Err(Error::IncorrectAccidental),
+        _ => return Err(Error::OctaveNotInRange),
+    };
+    if characters.next().is_some() {
+        return Err(Error::OctaveNotInRange);
+    }
+
+    Ok(Note::new_unchecked_spelling(
+        letter,
+        (octave as u8).try_into()?,
+        accidental,
+    ))
+}
+
+/// Style of note names.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NoteStyle {
+    /// Unicode accidentals and subscript octave numbers (for example: C♯₄),
+    /// same as [Display] implementation.
+    #[default]
+    Unicode,
+    /// ASCII accidentals and octave numbers (for example: C#4, natural sign is written as `n`).
+    Ascii,
+}
+
+impl FromStr for Note {
+    type Err = Error;
+
+    /// Parse note name in scientific pitch notation - letter, optional accidental
+    /// (`#`, `♯`, `b`, `♭`, `n`, `♮`) and octave number (ASCII or subscript digit),
+    /// for example: "C#4", "Db5" or "C♯₄".
+    ///
+    /// Like [Note::new], rejects theoretical spellings (like E♯ or C♭) with
+    /// [IncorrectAccidental](Error::IncorrectAccidental).
+    /// Missing or unsupported octave number is [OctaveNotInRange](Error::OctaveNotInRange).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let note = parse_note(s)?;
+        Note::new(note.letter, note.octave, note.accidental)
+    }
+}
+
+impl TryFrom<&str> for Note {
+    type Error = Error;
+
+    /// Same as [Note::from_str].
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
 impl From<Pitch> for Note {
     fn from(pitch: Pitch) -> Self {
-        let number = pitch.number();
-        let octave = pitch.octave();
-        match number {
+        ChromaticIndex::from(pitch)
+            .try_into()
+            .expect("every pitch has a note")
+    }
+}
+
+impl TryFrom<ChromaticIndex> for Note {
+    type Error = Error;
+
+    /// Notes with accidentals are spelled with sharps.
+    fn try_from(value: ChromaticIndex) -> Result<Self, Self::Error> {
+        let octave = value.value().div_euclid(12);
+        let octave: Octave = u8::try_from(octave)
+            .map_err(|_| Error::OctaveNotInRange)?
+            .try_into()?;
+        match value.class().number() {
             0 => Note::new(Letter::C, octave, Accidental::None),
             1 => Note::new(Letter::C, octave, Accidental::Sharp),
             2 => Note::new(Letter::D, octave, Accidental::None),
@@ -137,34 +500,54 @@ impl From<Pitch> for Note {
             11 => Note::new(Letter::B, octave, Accidental::None),
             _ => unreachable!(),
         }
-        .unwrap()
     }
 }
 
 impl From<Note> for Pitch {
+    /// Panics if note has no corresponding pitch (like notes of octave 9).
+    #[track_caller]
     fn from(note: Note) -> Self {
-        let letter = note.letter();
-        let index = match letter {
-            Letter::C => 0,
-            Letter::D => 2,
-            Letter::E => 4,
-            Letter::F => 5,
-            Letter::G => 7,
-            Letter::A => 9,
-            Letter::B => 11,
-        };
+        note.chromatic_index()
+            .try_into_pitch()
+            .expect("note has no corresponding pitch")
+    }
+}
 
-        let octave: u8 = note.octave().into();
-        let mut index = octave * 12 + index;
+impl From<Note> for (char, i8, u8) {
+    /// Convert note to `(letter, alteration, octave)` tuple - uppercase letter character,
+    /// [alteration](Accidental::alteration) (-1 for flat, 0 for no accidental or natural, 1 for sharp)
+    /// and octave number (0 - 9, 4 being octave of A₄).
+    ///
+    /// This representation is stable and won't change in future versions.
+    fn from(note: Note) -> Self {
+        (
+            note.letter.into(),
+            note.accidental.alteration(),
+            note.octave.number(),
+        )
+    }
+}
 
-        let accidental = note.accidental();
-        match accidental {
-            Accidental::Flat => index -= 1,
-            Accidental::Sharp => index += 1,
-            _ => (),
-        }
+impl TryFrom<(char, i8, u8)> for Note {
+    type Error = Error;
 
-        PITCHES[index as usize]
+    /// Create note from `(letter, alteration, octave)` tuple
+    /// (the same as the one note is converted into).
+    ///
+    /// Letter may be lowercase, alteration must be -1, 0 or 1.
+    /// Theoretical spellings (like E♯) are accepted.
+    fn try_from((letter, alteration, octave): (char, i8, u8)) -> Result<Self, Self::Error> {
+        let accidental = match alteration {
+            -1 => Accidental::Flat,
+            0 => Accidental::None,
+            1 => Accidental::Sharp,
+            _ => return Err(Error::IncorrectAccidental),
+        };
+        Ok(Note::new_unchecked_spelling(
+            letter.try_into()?,
+            octave.try_into()?,
+            accidental,
+        ))
     }
 }
 
@@ -181,13 +564,65 @@ pub enum Letter {
 }
 
 impl Letter {
+    /// All note letters, starting with C.
+    pub const ALL: [Letter; 7] = [
+        Letter::C,
+        Letter::D,
+        Letter::E,
+        Letter::F,
+        Letter::G,
+        Letter::A,
+        Letter::B,
+    ];
+
+    /// Position of the letter, counting from C = 0.
+    fn index(&self) -> u8 {
+        match self {
+            Letter::C => 0,
+            Letter::D => 1,
+            Letter::E => 2,
+            Letter::F => 3,
+            Letter::G => 4,
+            Letter::A => 5,
+            Letter::B => 6,
+        }
+    }
+
+    /// Letter at given position (wrapping around), counting from C = 0.
+    fn from_index(index: u8) -> Letter {
+        match index % 7 {
+            0 => Letter::C,
+            1 => Letter::D,
+            2 => Letter::E,
+            3 => Letter::F,
+            4 => Letter::G,
+            5 => Letter::A,
+            6 => Letter::B,
+            _ => unreachable!(),
+        }
+    }
+
+    /// Number of semitones from C to the (natural) note with this letter.
+    pub(crate) fn semitones(&self) -> u8 {
+        match self {
+            Letter::C => 0,
+            Letter::D => 2,
+            Letter::E => 4,
+            Letter::F => 5,
+            Letter::G => 7,
+            Letter::A => 9,
+            Letter::B => 11,
+        }
+    }
+
     /// Get previous note letter.
+    #[must_use]
     pub fn previous(&self) -> Letter {
         match self {
             Letter::C => Letter::B,
             Letter::D => Letter::C,
             Letter::E => Letter::D,
-            Letter::F => Letter::A,
+            Letter::F => Letter::E,
             Letter::G => Letter::F,
             Letter::A => Letter::G,
             Letter::B => Letter::A,
@@ -195,6 +630,7 @@ impl Letter {
     }
 
     /// Get next note letter.
+    #[must_use]
     pub fn next(&self) -> Letter {
         match self {
             Letter::C => Letter::D,
@@ -210,7 +646,13 @@ impl Letter {
 
 impl Display for Letter {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let character = match self {
+        write!(f, "{}", char::from(*self))
+    }
+}
+
+impl From<Letter> for char {
+    fn from(letter: Letter) -> Self {
+        match letter {
             Letter::C => 'C',
             Letter::D => 'D',
             Letter::E => 'E',
@@ -218,8 +660,7 @@ impl Display for Letter {
             Letter::G => 'G',
             Letter::A => 'A',
             Letter::B => 'B',
-        };
-        write!(f, "{}", character)
+        }
     }
 }
 
@@ -273,29 +714,27 @@ impl Display for Octave {
     }
 }
 
-impl TryFrom<u8> for Octave {
-    type Error = Error;
-
-    fn try_from(value: u8) -> Result<Self, Self::Error> {
-        match value {
-            0 => Ok(Octave::First),
-            1 => Ok(Octave::Second),
-            2 => Ok(Octave::Third),
-            3 => Ok(Octave::Fourth),
-            4 => Ok(Octave::Fifth),
-            5 => Ok(Octave::Sixth),
-            6 => Ok(Octave::Seventh),
-            7 => Ok(Octave::Eighth),
-            8 => Ok(Octave::Ninth),
-            9 => Ok(Octave::Tenth),
-            _ => Err(Error::OctaveNotInRange),
+impl Octave {
+    /// Octave with given number (0 - 9).
+    pub(crate) const fn from_number(number: u8) -> Option<Octave> {
+        match number {
+            0 => Some(Octave::First),
+            1 => Some(Octave::Second),
+            2 => Some(Octave::Third),
+            3 => Some(Octave::Fourth),
+            4 => Some(Octave::Fifth),
+            5 => Some(Octave::Sixth),
+            6 => Some(Octave::Seventh),
+            7 => Some(Octave::Eighth),
+            8 => Some(Octave::Ninth),
+            9 => Some(Octave::Tenth),
+            _ => None,
         }
     }
-}
 
-impl From<Octave> for u8 {
-    fn from(octave: Octave) -> Self {
-        match octave {
+    /// Number of the octave (0 - 9).
+    pub(crate) const fn number(&self) -> u8 {
+        match self {
             Octave::First => 0,
             Octave::Second => 1,
             Octave::Third => 2,
@@ -310,23 +749,386 @@ impl From<Octave> for u8 {
     }
 }
 
+impl TryFrom<u8> for Octave {
+    type Error = Error;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        Octave::from_number(value).ok_or(Error::OctaveNotInRange)
+    }
+}
+
+impl From<Octave> for u8 {
+    fn from(octave: Octave) -> Self {
+        octave.number()
+    }
+}
+
 /// Musical note accidental.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Accidental {
     /// No accidental.
     None,
+    /// Explicit natural - ♮.
+    ///
+    /// Same pitch as [Accidental::None], differing only in display (engraving) intent
+    /// (for example: a courtesy natural). Notes spelled with it are not equal to
+    /// ones without accidental, but they convert to the same [Pitch].
+    Natural,
     /// Flat - ♭.
     Flat,
     /// Sharp - ♯.
     Sharp,
 }
 
+impl Accidental {
+    /// Number of semitones the accidental raises (positive) or lowers (negative) the note by.
+    #[must_use]
+    pub fn alteration(&self) -> i8 {
+        match self {
+            Accidental::None | Accidental::Natural => 0,
+            Accidental::Flat => -1,
+            Accidental::Sharp => 1,
+        }
+    }
+}
+
 impl Display for Accidental {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Accidental::None => write!(f, ""),
+            Accidental::Natural => write!(f, "♮"),
             Accidental::Flat => write!(f, "♭"),
             Accidental::Sharp => write!(f, "♯"),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PITCHES;
+
+    #[test]
+    fn explicit_natural_is_different_spelling_of_same_pitch() {
+        let plain = Note::new(Letter::F, Octave::Fifth, Accidental::None).unwrap();
+        let natural = Note::new(Letter::F, Octave::Fifth, Accidental::Natural).unwrap();
+        assert_ne!(plain, natural);
+        assert_eq!(Pitch::from(plain), Pitch::from(natural));
+        assert_eq!(plain.chromatic_index(), natural.chromatic_index());
+        assert_eq!(plain.cmp(&natural), Ordering::Less);
+        assert_eq!(natural.cmp(&plain), Ordering::Greater);
+        assert_eq!("F♮4".parse::<Note>(), Ok(natural));
+        assert_eq!("Fn4".parse::<Note>(), Ok(natural));
+        assert_eq!("F4".parse::<Note>(), Ok(plain));
+    }
+
+    fn all_spellings(octave: Octave) -> impl Iterator<Item = Note> {
+        Letter::ALL.into_iter().flat_map(move |letter| {
+            [
+                Accidental::None,
+                Accidental::Natural,
+                Accidental::Flat,
+                Accidental::Sharp,
+            ]
+            .into_iter()
+            .map(move |accidental| Note::new_unchecked_spelling(letter, octave, accidental))
+        })
+    }
+
+    #[test]
+    fn notes_without_pitch_are_ordered() {
+        let lowest = Note::new_unchecked_spelling(Letter::C, Octave::First, Accidental::Flat);
+        let c0 = Note::new(Letter::C, Octave::First, Accidental::None).unwrap();
+        let b9 = Note::new(Letter::B, Octave::Tenth, Accidental::None).unwrap();
+        let highest = Note::new_unchecked_spelling(Letter::B, Octave::Tenth, Accidental::Sharp);
+        assert_eq!(lowest.cmp(&c0), Ordering::Less);
+        assert_eq!(highest.cmp(&b9), Ordering::Greater);
+        assert_eq!(lowest.cmp(&highest), Ordering::Less);
+        assert_eq!(highest.cmp(&highest), Ordering::Equal);
+    }
+
+    #[test]
+    fn relaxed_spellings_round_trip_through_pitch() {
+        for octave in 0..10 {
+            for note in all_spellings(Octave::from_number(octave).unwrap()) {
+                match note.chromatic_index().try_into_pitch() {
+                    Ok(pitch) => {
+                        assert_eq!(Pitch::from(note), pitch, "{}", note);
+                        let back = Note::from(pitch);
+                        assert_eq!(back.chromatic_index(), note.chromatic_index(), "{}", note);
+                        assert_eq!(Pitch::from(back), pitch, "{}", note);
+                        assert_eq!(Pitch::from(note.simplify()), pitch, "{}", note);
+                        assert_eq!(Pitch::from(note.enharmonic()), pitch, "{}", note);
+                    }
+                    Err(_) => assert!(
+                        note.to_string() == "C♭₀"
+                            || note.to_string() == "B♯₈"
+                            || note.octave() == Octave::Tenth,
+                        "{}",
+                        note
+                    ),
+                }
+                assert_eq!(parse_note(&note.to_string()), Ok(note));
+            }
+        }
+    }
+
+    #[test]
+    fn sort_key_orders_full_spellings() {
+        let expected: Vec<Note> = [
+            "B3", "C♭4", "C4", "C♮4", "B♯3", "C♯4", "D♭4", "D4", "D♮4", "D♯4", "E♭4", "E4", "E♮4",
+            "F♭4", "F4", "F♮4", "E♯4", "F♯4", "G♭4", "G4", "G♮4", "G♯4", "A♭4", "A4", "A♮4", "A♯4",
+            "B♭4", "B4", "B♮4", "C♭5", "C5", "B♯4",
+        ]
+        .into_iter()
+        .map(|name| parse_note(name).unwrap())
+        .collect();
+        let mut notes: Vec<Note> = (0..expected.len())
+            .map(|i| expected[i * 11 % expected.len()])
+            .collect();
+        assert_ne!(notes, expected);
+        notes.sort_by_key(Note::sort_key);
+        assert_eq!(notes, expected);
+        assert_eq!(cmp_note_names("C#4", "Db4"), Ok(Ordering::Less));
+        assert_eq!(cmp_note_names("Cb4", "B4"), Ok(Ordering::Less));
+        assert_eq!(cmp_note_names("Cb4", "B3"), Ok(Ordering::Greater));
+        let mut sorted = expected.clone();
+        sorted.sort();
+        assert_eq!(sorted, expected);
+    }
+
+    #[test]
+    fn applying_thirds_to_c4() {
+        let c4 = Note::new(Letter::C, Octave::Fifth, Accidental::None).unwrap();
+        let e4 = Note::new(Letter::E, Octave::Fifth, Accidental::None).unwrap();
+        let e_flat_4 = Note::new(Letter::E, Octave::Fifth, Accidental::Flat).unwrap();
+        assert_eq!(c4.apply_interval(NamedInterval::MajorThird), Some(e4));
+        assert_eq!(c4.apply_interval(NamedInterval::MinorThird), Some(e_flat_4));
+        // spelling follows interval, not pitch
+        let d_sharp_4 = Note::new(Letter::D, Octave::Fifth, Accidental::Sharp).unwrap();
+        assert_eq!(
+            c4.apply_interval(NamedInterval::AugmentedSecond),
+            Some(d_sharp_4)
+        );
+        // B♯ isn't representable
+        let b4 = Note::new(Letter::B, Octave::Fifth, Accidental::None).unwrap();
+        assert_eq!(b4.apply_interval(NamedInterval::AugmentedFifth), None);
+    }
+
+    #[test]
+    fn ordering_agrees_with_equality() {
+        let notes: Vec<Note> = [Octave::First, Octave::Fifth, Octave::Tenth]
+            .into_iter()
+            .flat_map(all_spellings)
+            .collect();
+        for a in &notes {
+            for b in &notes {
+                assert_eq!(a.cmp(b) == Ordering::Equal, a == b, "{} and {}", a, b);
+                assert_eq!(a.cmp(b), b.cmp(a).reverse(), "{} and {}", a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn diagnostics() {
+        let c_sharp_4 = Note::new(Letter::C, Octave::Fifth, Accidental::Sharp).unwrap();
+        let d_flat_4 = Note::new(Letter::D, Octave::Fifth, Accidental::Flat).unwrap();
+        let b_10 = Note::new_unchecked_spelling(Letter::B, Octave::Tenth, Accidental::None);
+        assert_eq!(
+            format!("{:#?}", c_sharp_4),
+            "C♯₄ = chromatic index 49 = 277.18 Hz = MIDI 61 (canonical spelling)"
+        );
+        assert_eq!(
+            d_flat_4.diagnostic(),
+            "D♭₄ = chromatic index 49 = 277.18 Hz = MIDI 61 (non-canonical spelling of C♯₄)"
+        );
+        assert_eq!(
+            format!("{:#?}", b_10),
+            "B₉ = chromatic index 119 (no pitch)"
+        );
+        assert_eq!(
+            format!("{:?}", d_flat_4),
+            "Note { letter: D, octave: Fifth, accidental: Flat }"
+        );
+        assert_eq!(
+            format!("{:#?}", PITCHES[57]),
+            "index 57 = A₄ = 440 Hz = MIDI 69"
+        );
+        assert_eq!(format!("{:?}", PITCHES[57]), "Pitch { index: 57 }");
+    }
+
+    #[test]
+    fn diagnostics_of_every_value() {
+        for pitch in PITCHES.iter() {
+            let diagnostic = pitch.diagnostic();
+            assert!(diagnostic.starts_with(&format!("index {} = ", pitch.index())));
+            assert_eq!(format!("{:#?}", pitch), diagnostic);
+        }
+        for octave in 0..10 {
+            for note in all_spellings(Octave::from_number(octave).unwrap()) {
+                let diagnostic = note.diagnostic();
+                assert!(diagnostic.starts_with(&format!("{} = chromatic index ", note)));
+                assert_eq!(format!("{:#?}", note), diagnostic);
+                assert_eq!(
+                    diagnostic.ends_with("(no pitch)"),
+                    note.chromatic_index().try_into_pitch().is_err(),
+                    "{}",
+                    diagnostic
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn stable_tuple_conversions() {
+        let a4 = Note::from(PITCHES[57]);
+        assert_eq!(<(char, i8, u8)>::from(a4), ('A', 0, 4));
+        let d_flat_4 = Note::new(Letter::D, Octave::Fifth, Accidental::Flat).unwrap();
+        assert_eq!(<(char, i8, u8)>::from(d_flat_4), ('D', -1, 4));
+        assert_eq!(Note::try_from(('d', -1, 4)), Ok(d_flat_4));
+        assert_eq!(Note::try_from(('C', 2, 4)), Err(Error::IncorrectAccidental));
+        assert_eq!(Note::try_from(('H', 0, 4)), Err(Error::IncorrectLetter));
+        assert_eq!(Note::try_from(('C', 0, 10)), Err(Error::OctaveNotInRange));
+
+        for octave in 0..10 {
+            for note in all_spellings(Octave::from_number(octave).unwrap()) {
+                let tuple: (char, i8, u8) = note.into();
+                assert_eq!(tuple.2, octave);
+                match Note::try_from(tuple) {
+                    // explicit natural is written as no accidental
+                    Ok(back) if note.accidental() == Accidental::Natural => {
+                        let written = Note::new(note.letter(), note.octave(), Accidental::None);
+                        assert_eq!(Ok(back), written, "{}", note);
+                    }
+                    Ok(back) => assert_eq!(back, note),
+                    Err(_) => assert!(note.chromatic_index().try_into_pitch().is_err()),
+                }
+            }
+        }
+
+        for index in 0..=u8::MAX {
+            match Pitch::try_from(index) {
+                Ok(pitch) => {
+                    assert_eq!(pitch, PITCHES[index as usize]);
+                    assert_eq!(u8::from(pitch), index);
+                }
+                Err(error) => {
+                    assert!(index as usize >= PITCHES.len());
+                    assert_eq!(error, Error::PitchNotInRange);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn overtone_spectrum_of_a4() {
+        let a4 = Note::new(Letter::A, Octave::Fifth, Accidental::None).unwrap();
+        let spectrum = overtone_spectrum(a4, 5, 0.5);
+        assert_eq!(spectrum.len(), 5);
+        for (partial, (frequency, amplitude)) in spectrum.iter().enumerate() {
+            assert!((frequency - 440.0 * (partial + 1) as f64).abs() < 1e-9);
+            assert_eq!(*amplitude, 0.5_f64.powi(partial as i32));
+        }
+        assert_eq!(spectrum[0].1, 1.0);
+        assert!(spectrum.windows(2).all(|pair| pair[1].1 < pair[0].1));
+        assert!(overtone_spectrum(a4, 0, 0.5).is_empty());
+    }
+
+    #[test]
+    fn braille_notes() {
+        let braille = |letter, octave: u8, accidental, beats| {
+            let octave = Octave::try_from(octave).unwrap();
+            Note::new_unchecked_spelling(letter, octave, accidental)
+                .to_braille_with(crate::Duration::new(beats).unwrap())
+        };
+        let cases = [
+            (Letter::C, 4, Accidental::None, 1.0, "⠐⠹"),
+            (Letter::F, 5, Accidental::Sharp, 2.0, "⠩⠨⠟"),
+            (Letter::B, 3, Accidental::Flat, 0.5, "⠣⠸⠚"),
+            (Letter::D, 4, Accidental::None, 1.5, "⠐⠱⠄"),
+            (Letter::A, 1, Accidental::Natural, 4.0, "⠡⠈⠮"),
+            (Letter::G, 6, Accidental::None, 0.25, "⠰⠷"),
+            (Letter::E, 0, Accidental::None, 6.0, "⠈⠈⠯⠄"),
+            (Letter::B, 8, Accidental::None, 0.03125, "⠠⠠⠚"),
+        ];
+        for (letter, octave, accidental, beats, expected) in cases {
+            assert_eq!(
+                braille(letter, octave, accidental, beats).as_deref(),
+                Some(expected),
+                "{:?}{:?}{} {}",
+                letter,
+                accidental,
+                octave,
+                beats
+            );
+        }
+        let c4 = Note::new(Letter::C, Octave::Fifth, Accidental::None).unwrap();
+        assert_eq!(c4.to_braille(), "⠐⠹");
+        // triplet eighth and double dotted quarter
+        assert_eq!(braille(Letter::C, 4, Accidental::None, 1.0 / 3.0), None);
+        assert_eq!(braille(Letter::C, 4, Accidental::None, 1.75), None);
+        // above 7th octave
+        assert_eq!(
+            braille(Letter::C, 9, Accidental::None, 1.0).as_deref(),
+            Some("⠠⠠⠹")
+        );
+    }
+
+    #[test]
+    fn parsing_note_names() {
+        let note = |letter, octave: u8, accidental| {
+            Note::new(letter, Octave::try_from(octave).unwrap(), accidental).unwrap()
+        };
+        let cases = [
+            ("C#4", Ok(note(Letter::C, 4, Accidental::Sharp))),
+            ("Db5", Ok(note(Letter::D, 5, Accidental::Flat))),
+            ("eb2", Ok(note(Letter::E, 2, Accidental::Flat))),
+            ("A4", Ok(note(Letter::A, 4, Accidental::None))),
+            ("C♯₄", Ok(note(Letter::C, 4, Accidental::Sharp))),
+            ("B♭₂", Ok(note(Letter::B, 2, Accidental::Flat))),
+            ("G♮7", Ok(note(Letter::G, 7, Accidental::Natural))),
+            ("Fn0", Ok(note(Letter::F, 0, Accidental::Natural))),
+            ("E#4", Err(Error::IncorrectAccidental)),
+            ("Cb4", Err(Error::IncorrectAccidental)),
+            ("C$4", Err(Error::IncorrectAccidental)),
+            ("H4", Err(Error::IncorrectLetter)),
+            (" C4", Err(Error::IncorrectLetter)),
+            ("", Err(Error::IncorrectLetter)),
+            ("C", Err(Error::OctaveNotInRange)),
+            ("C#", Err(Error::OctaveNotInRange)),
+            ("C4 ", Err(Error::OctaveNotInRange)),
+            ("C10", Err(Error::OctaveNotInRange)),
+        ];
+        for (name, expected) in cases {
+            assert_eq!(name.parse::<Note>(), expected, "{:?}", name);
+            assert_eq!(Note::try_from(name), expected, "{:?}", name);
+        }
+    }
+
+    #[test]
+    fn note_names_round_trip() {
+        for pitch in PITCHES.iter() {
+            for note in pitch.spellings() {
+                let unicode = note.to_string();
+                let ascii = note.format_in(NoteStyle::Ascii).into_owned();
+                let lowercase = ascii.to_lowercase();
+                for name in [unicode, ascii, lowercase] {
+                    assert_eq!(Note::try_from(name.as_str()), Ok(note), "{}", name);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn displayed_note_of_every_pitch_parses_back() {
+        for pitch in PITCHES.iter() {
+            let note = Note::from(*pitch);
+            let name = note.to_string();
+            assert!(name
+                .chars()
+                .last()
+                .is_some_and(|c| ('₀'..='₉').contains(&c)));
+            assert_eq!(name.parse::<Note>(), Ok(note), "{}", name);
+        }
+    }
+}
@@ -0,0 +1,113 @@
+//! Fixed-capacity lists of notes.
+
+use std::ops::Deref;
+
+use crate::{Accidental, Error, Letter, Note, Octave};
+
+/// List of at most `N` notes, stored inline (without allocation) - for example:
+/// [notes of a chord](crate::Chord::note_array).
+///
+/// Dereferences to slice of notes it contains.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct NoteArray<const N: usize> {
+    /// Notes, slots past `len` hold C₀.
+    notes: [Note; N],
+    len: usize,
+}
+
+impl<const N: usize> NoteArray<N> {
+    /// Maximal number of notes.
+    pub const CAPACITY: usize = N;
+
+    /// Create new empty list.
+    #[must_use]
+    pub fn new() -> Self {
+        NoteArray {
+            notes: [Note::new_unchecked_spelling(Letter::C, Octave::First, Accidental::None); N],
+            len: 0,
+        }
+    }
+
+    /// Add note at the end of the list.
+    ///
+    /// Fails if the list is full.
+    pub fn push(&mut self, note: Note) -> Result<(), Error> {
+        let slot = self.notes.get_mut(self.len).ok_or(Error::TooManyNotes)?;
+        *slot = note;
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Get notes as a slice.
+    #[must_use]
+    pub fn as_slice(&self) -> &[Note] {
+        &self.notes[..self.len]
+    }
+}
+
+impl<const N: usize> Default for NoteArray<N> {
+    fn default() -> Self {
+        NoteArray::new()
+    }
+}
+
+impl<const N: usize> Deref for NoteArray<N> {
+    type Target = [Note];
+
+    fn deref(&self) -> &Self::Target {
+        self.as_slice()
+    }
+}
+
+impl<const N: usize> std::fmt::Debug for NoteArray<N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.as_slice()).finish()
+    }
+}
+
+impl<'a, const N: usize> IntoIterator for &'a NoteArray<N> {
+    type Item = &'a Note;
+    type IntoIter = std::slice::Iter<'a, Note>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.as_slice().iter()
+    }
+}
+
+impl<const N: usize> TryFrom<&[Note]> for NoteArray<N> {
+    type Error = Error;
+
+    /// Fails if there are more than `N` notes.
+    fn try_from(notes: &[Note]) -> Result<Self, Self::Error> {
+        let mut array = NoteArray::new();
+        for note in notes {
+            array.push(*note)?;
+        }
+        Ok(array)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn filling_note_array() {
+        let c4 = Note::new(Letter::C, Octave::Fifth, Accidental::None).unwrap();
+        let e4 = Note::new(Letter::E, Octave::Fifth, Accidental::None).unwrap();
+        let mut notes = NoteArray::<2>::new();
+        assert!(notes.is_empty());
+        assert_eq!(notes.push(c4), Ok(()));
+        assert_eq!(notes.push(e4), Ok(()));
+        assert_eq!(notes.push(c4), Err(Error::TooManyNotes));
+        assert_eq!(notes.as_slice(), [c4, e4]);
+        assert_eq!(notes[1], e4);
+        assert_eq!(notes.iter().count(), 2);
+        assert_eq!(NoteArray::<2>::try_from(&[c4, e4][..]), Ok(notes));
+        assert_eq!(
+            NoteArray::<1>::try_from(&[c4, e4][..]),
+            Err(Error::TooManyNotes)
+        );
+        assert_eq!(format!("{:?}", NoteArray::<3>::new()), "[]");
+    }
+}
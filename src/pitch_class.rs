@@ -0,0 +1,347 @@
+//! Pitch classes and sets of pitch classes.
+
+use std::f64::consts::TAU;
+
+use crate::Pitch;
+
+/// Pitch class - pitch without regard to octave.
+///
+/// Numbered the same way as [Pitch::number](crate::Pitch::number), 0 being C.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct PitchClass {
+    number: u8,
+}
+
+impl PitchClass {
+    /// Number of pitch classes.
+    pub const COUNT: u8 = 12;
+
+    /// Create new pitch class.
+    ///
+    /// `number` wraps around, so 12 is C again, 13 is C♯/D♭ and so on.
+    #[must_use]
+    pub const fn new(number: u8) -> Self {
+        PitchClass {
+            number: number % Self::COUNT,
+        }
+    }
+
+    /// Get 'number' of pitch class (0 - 11).
+    #[must_use]
+    pub const fn number(&self) -> u8 {
+        self.number
+    }
+
+    /// Distance (in semitones, 0 - 6) to other pitch class around the pitch class circle -
+    /// the shorter of clockwise and counterclockwise distances (for example: 1 between B and C).
+    #[must_use]
+    pub const fn circular_distance(&self, other: &PitchClass) -> u8 {
+        let clockwise = (other.number + Self::COUNT - self.number) % Self::COUNT;
+        let counterclockwise = Self::COUNT - clockwise;
+        if clockwise < counterclockwise {
+            clockwise
+        } else {
+            counterclockwise % Self::COUNT
+        }
+    }
+
+    /// Angle (in radians) of pitch class on a clock face.
+    ///
+    /// C is at 12 o'clock (angle 0), angle grows clockwise.
+    #[must_use]
+    pub fn clock_angle(&self, arrangement: CircleArrangement) -> f64 {
+        let step = (self.number as u16 * arrangement.generator() as u16) % Self::COUNT as u16;
+        TAU * step as f64 / Self::COUNT as f64
+    }
+
+    /// Hue (in degrees, 0 - 360) representing pitch class on a color wheel.
+    ///
+    /// Pitch classes are spread evenly, starting with C = 0° (red),
+    /// so pitches an octave apart have the same hue.
+    #[must_use]
+    pub fn hue(&self) -> f64 {
+        360.0 * self.number as f64 / Self::COUNT as f64
+    }
+
+    /// Fully saturated and bright color `(red, green, blue)` of pitch class [hue](PitchClass::hue).
+    #[must_use]
+    pub fn rgb(&self) -> (u8, u8, u8) {
+        let hue = self.hue() / 60.0;
+        let x = 1.0 - (hue % 2.0 - 1.0).abs();
+        let (r, g, b) = match hue as u8 {
+            0 => (1.0, x, 0.0),
+            1 => (x, 1.0, 0.0),
+            2 => (0.0, 1.0, x),
+            3 => (0.0, x, 1.0),
+            4 => (x, 0.0, 1.0),
+            _ => (1.0, 0.0, x),
+        };
+        let channel = |value: f64| (value * 255.0).round() as u8;
+        (channel(r), channel(g), channel(b))
+    }
+
+    /// Coordinates `(x, y)` of pitch class on a unit circle.
+    ///
+    /// C is at 12 o'clock - `(0, 1)`, next positions follow clockwise.
+    #[must_use]
+    pub fn clock_position(&self, arrangement: CircleArrangement) -> (f64, f64) {
+        let angle = self.clock_angle(arrangement);
+        (angle.sin(), angle.cos())
+    }
+}
+
+/// Hue (in degrees, 0 - 360) representing pitch on a color wheel -
+/// [hue](PitchClass::hue) of its pitch class.
+#[must_use]
+pub fn pitch_to_hue(pitch: Pitch) -> f64 {
+    pitch.class().hue()
+}
+
+/// Arrangement of pitch classes on a circle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircleArrangement {
+    /// Chromatic circle - neighbours are a semitone apart.
+    Chromatic,
+    /// Circle of fifths - neighbours are a perfect fifth apart.
+    Fifths,
+}
+
+impl CircleArrangement {
+    /// Interval (in semitones) between neighbouring positions on the circle.
+    ///
+    /// Circle is generated by repeatedly adding it to C.
+    #[must_use]
+    pub fn generator(&self) -> u8 {
+        match self {
+            CircleArrangement::Chromatic => 1,
+            CircleArrangement::Fifths => 7,
+        }
+    }
+}
+
+/// Set of pitch classes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PitchClassSet {
+    mask: u16,
+}
+
+impl PitchClassSet {
+    const FULL_MASK: u16 = (1 << PitchClass::COUNT) - 1;
+
+    /// Create new empty set.
+    #[must_use]
+    pub fn new() -> Self {
+        PitchClassSet { mask: 0 }
+    }
+
+    /// Create set from bit mask, bit `n` representing pitch class number `n`.
+    ///
+    /// Bits above 11th are ignored.
+    #[must_use]
+    pub fn from_mask(mask: u16) -> Self {
+        PitchClassSet {
+            mask: mask & Self::FULL_MASK,
+        }
+    }
+
+    /// Get bit mask of the set, bit `n` representing pitch class number `n`.
+    #[must_use]
+    pub fn mask(&self) -> u16 {
+        self.mask
+    }
+
+    /// Add pitch class to the set.
+    pub fn insert(&mut self, class: PitchClass) {
+        self.mask |= 1 << class.number();
+    }
+
+    /// Remove pitch class from the set.
+    pub fn remove(&mut self, class: PitchClass) {
+        self.mask &= !(1 << class.number());
+    }
+
+    /// Check if set contains pitch class.
+    #[must_use]
+    pub fn contains(&self, class: PitchClass) -> bool {
+        self.mask & (1 << class.number()) != 0
+    }
+
+    /// Number of pitch classes in the set.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.mask.count_ones() as usize
+    }
+
+    /// Check if set is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.mask == 0
+    }
+
+    /// Get set with every pitch class raised by given number of semitones (wrapping around).
+    #[must_use]
+    pub fn transpose(&self, semitones: u8) -> PitchClassSet {
+        let shift = semitones % PitchClass::COUNT;
+        let mask = self.mask as u32;
+        PitchClassSet::from_mask(((mask << shift) | (mask >> (PitchClass::COUNT - shift))) as u16)
+    }
+
+    /// Get set of pitch classes not belonging to this set.
+    #[must_use]
+    pub fn complement(&self) -> PitchClassSet {
+        PitchClassSet::from_mask(!self.mask)
+    }
+
+    /// Get inverted set - every pitch class `n` replaced with `12 - n` (mirrored around C).
+    #[must_use]
+    pub fn invert(&self) -> PitchClassSet {
+        self.iter()
+            .map(|class| PitchClass::new(PitchClass::COUNT - class.number()))
+            .collect()
+    }
+
+    /// Prime form of the set (as defined by Rahn) - the most compact of its transpositions
+    /// and transpositions of its inversion, starting with C (for example: {0, 3, 7} for C major triad).
+    ///
+    /// Sets with the same prime form belong to the same set class.
+    #[must_use]
+    pub fn prime_form(&self) -> PitchClassSet {
+        // mask compared as number favours sets packed towards C, comparing the highest classes first
+        let inverted = self.invert();
+        (0..PitchClass::COUNT)
+            .flat_map(|semitones| [self.transpose(semitones), inverted.transpose(semitones)])
+            .min_by_key(|set| set.mask)
+            .expect("invariant")
+    }
+
+    /// Interval vector of the set - number of pairs of its pitch classes
+    /// forming every interval class (minor second / major seventh first, tritone last).
+    #[must_use]
+    pub fn interval_vector(&self) -> [u8; 6] {
+        let mut vector = [0; 6];
+        for a in self.iter() {
+            for b in self.iter().filter(|b| *b > a) {
+                vector[a.circular_distance(&b) as usize - 1] += 1;
+            }
+        }
+        vector
+    }
+
+    /// Iterate over pitch classes in the set, in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = PitchClass> {
+        let mask = self.mask;
+        (0..PitchClass::COUNT)
+            .filter(move |number| mask & (1 << number) != 0)
+            .map(PitchClass::new)
+    }
+
+    /// Centroid (mean of [clock positions](PitchClass::clock_position)) of the set.
+    ///
+    /// Its distance from the origin is a measure of how unevenly
+    /// pitch classes are spread around the circle - evenly spread sets
+    /// (like whole-tone scale) have centroid at the origin.
+    ///
+    /// Empty set has centroid at the origin.
+    #[must_use]
+    pub fn centroid(&self, arrangement: CircleArrangement) -> (f64, f64) {
+        if self.is_empty() {
+            return (0.0, 0.0);
+        }
+
+        let (x, y) = self
+            .iter()
+            .map(|class| class.clock_position(arrangement))
+            .fold((0.0, 0.0), |(x, y), (dx, dy)| (x + dx, y + dy));
+        let count = self.len() as f64;
+        (x / count, y / count)
+    }
+}
+
+impl FromIterator<PitchClass> for PitchClassSet {
+    fn from_iter<T: IntoIterator<Item = PitchClass>>(iter: T) -> Self {
+        let mut set = PitchClassSet::new();
+        for class in iter {
+            set.insert(class);
+        }
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PITCHES;
+
+    fn assert_near((x, y): (f64, f64), (expected_x, expected_y): (f64, f64)) {
+        assert!(
+            (x - expected_x).abs() < 1e-9 && (y - expected_y).abs() < 1e-9,
+            "({}, {}) != ({}, {})",
+            x,
+            y,
+            expected_x,
+            expected_y
+        );
+    }
+
+    #[test]
+    fn clock_positions() {
+        let chromatic = CircleArrangement::Chromatic;
+        assert_near(PitchClass::new(0).clock_position(chromatic), (0.0, 1.0));
+        assert_near(PitchClass::new(3).clock_position(chromatic), (1.0, 0.0));
+        assert_near(PitchClass::new(6).clock_position(chromatic), (0.0, -1.0));
+        assert_near(PitchClass::new(9).clock_position(chromatic), (-1.0, 0.0));
+
+        // G is one step clockwise from C on circle of fifths
+        let fifths = CircleArrangement::Fifths;
+        assert_eq!(
+            PitchClass::new(7).clock_angle(fifths),
+            PitchClass::new(1).clock_angle(chromatic)
+        );
+        assert_near(PitchClass::new(6).clock_position(fifths), (0.0, -1.0));
+    }
+
+    #[test]
+    fn centroids() {
+        let whole_tone: PitchClassSet = (0..6).map(|i| PitchClass::new(2 * i)).collect();
+        for arrangement in [CircleArrangement::Chromatic, CircleArrangement::Fifths] {
+            assert_near(whole_tone.centroid(arrangement), (0.0, 0.0));
+            assert_near(PitchClassSet::new().centroid(arrangement), (0.0, 0.0));
+        }
+        let c: PitchClassSet = [PitchClass::new(0)].into_iter().collect();
+        assert_near(c.centroid(CircleArrangement::Chromatic), (0.0, 1.0));
+    }
+
+    #[test]
+    fn hues_are_same_across_octaves() {
+        let (c4, c5) = (PITCHES[48], PITCHES[60]);
+        assert_eq!(c4.class().hue(), 0.0);
+        assert_eq!(c4.class().hue(), c5.class().hue());
+        assert_eq!(c4.class().rgb(), c5.class().rgb());
+        for pitch in PITCHES[12..].iter() {
+            let octave_below = PITCHES[pitch.index() as usize - 12];
+            assert_eq!(pitch_to_hue(*pitch), pitch.class().hue());
+            assert_eq!(pitch_to_hue(*pitch), pitch_to_hue(octave_below));
+            assert!((0.0..360.0).contains(&pitch_to_hue(*pitch)));
+        }
+        assert_eq!(PitchClass::new(0).rgb(), (255, 0, 0));
+        assert_eq!(PitchClass::new(4).rgb(), (0, 255, 0));
+        assert_eq!(PitchClass::new(8).rgb(), (0, 0, 255));
+        assert_eq!(PitchClass::new(3).hue(), 90.0);
+    }
+
+    #[test]
+    fn circular_distances() {
+        let distance = |a, b| PitchClass::new(a).circular_distance(&PitchClass::new(b));
+        assert_eq!(distance(0, 11), 1);
+        assert_eq!(distance(11, 0), 1);
+        assert_eq!(distance(4, 4), 0);
+        assert_eq!(distance(0, 6), 6);
+        assert_eq!(distance(2, 9), 5);
+        for a in 0..PitchClass::COUNT {
+            for b in 0..PitchClass::COUNT {
+                assert_eq!(distance(a, b), distance(b, a));
+                assert!(distance(a, b) <= 6);
+            }
+        }
+    }
+}
@@ -0,0 +1,337 @@
+//! Ranges of pitches.
+
+use crate::{Error, Note, Pitch, PitchClass, PITCHES};
+
+/// Inclusive range of pitches (for example: range of an instrument).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Range {
+    low: Pitch,
+    high: Pitch,
+}
+
+impl Range {
+    /// Range of guitar in standard tuning with 24 frets - E₂ to E₆.
+    pub const GUITAR: Range = Range::from_indices(28, 76);
+    /// Range of 4-string bass guitar with 24 frets - E₁ to G₄.
+    pub const BASS_GUITAR: Range = Range::from_indices(16, 55);
+    /// Range of violin - G₃ to A₇.
+    pub const VIOLIN: Range = Range::from_indices(43, 93);
+    /// Range of 88-key piano - A₀ to C₈.
+    pub const PIANO: Range = Range::from_indices(9, 96);
+
+    /// Range of pitches with given [indices](Pitch::index), for constants.
+    const fn from_indices(low: u8, high: u8) -> Range {
+        Range {
+            low: Pitch { index: low },
+            high: Pitch { index: high },
+        }
+    }
+
+    /// Create new range from `low` to `high` (inclusive).
+    ///
+    /// Fails if `low` is higher than `high`.
+    pub fn new(low: Pitch, high: Pitch) -> Result<Self, Error> {
+        if low > high {
+            return Err(Error::IncorrectRange);
+        }
+        Ok(Range { low, high })
+    }
+
+    /// Get lowest pitch of the range.
+    #[must_use]
+    pub fn low(&self) -> Pitch {
+        self.low
+    }
+
+    /// Get highest pitch of the range.
+    #[must_use]
+    pub fn high(&self) -> Pitch {
+        self.high
+    }
+
+    /// Check if pitch is in the range.
+    #[must_use]
+    pub fn contains(&self, pitch: Pitch) -> bool {
+        self.low <= pitch && pitch <= self.high
+    }
+
+    /// Precomputed bit set of the range, bit `n` representing pitch with [index](Pitch::index) `n`.
+    ///
+    /// Bit sets of several ranges can be combined with bitwise operations
+    /// (for example: `&` for their intersection) and checked with [Range::contains_fast].
+    /// For a single range it isn't faster than [Range::contains] (just two comparisons).
+    #[must_use]
+    pub fn membership_bitset(&self) -> u128 {
+        let bits = |count: u8| u128::MAX.checked_shr(128 - count as u32).unwrap_or(0);
+        bits(self.high.index() + 1) & !bits(self.low.index())
+    }
+
+    /// Check if pitch is in the range with given [membership bit set](Range::membership_bitset).
+    ///
+    /// Equivalent to [Range::contains].
+    #[must_use]
+    pub fn contains_fast(bitset: u128, pitch: Pitch) -> bool {
+        bitset & (1 << pitch.index()) != 0
+    }
+
+    /// Band `(low, high)` (in Hz) of fundamental frequencies of the range -
+    /// from half a semitone below the lowest pitch to half a semitone above the highest one
+    /// (exact frequencies in equal-tempered scale, A₄ = 440 Hz).
+    ///
+    /// ```
+    /// use pitches::*;
+    /// // E₂ (82.41 Hz) - E₆ (1318.51 Hz)
+    /// let (low, high) = Range::GUITAR.frequency_band();
+    /// assert_eq!((low.round(), high.round()), (80.0, 1357.0));
+    /// ```
+    #[must_use]
+    pub fn frequency_band(&self) -> (f64, f64) {
+        let margin = 2.0_f64.powf(50.0 / 1200.0);
+        (
+            self.low.exact_frequency() / margin,
+            self.high.exact_frequency() * margin,
+        )
+    }
+
+    /// [Frequency band](Range::frequency_band) of the range extended to include
+    /// harmonic partials up to `up_to_partial` (1 being the fundamental) of its pitches.
+    ///
+    /// Fails if `up_to_partial` is zero.
+    ///
+    /// ```
+    /// use pitches::*;
+    /// // bass guitar up to 4th partial, for example: for EQ preset of the instrument
+    /// let (low, high) = Range::BASS_GUITAR.harmonic_band(4).unwrap();
+    /// assert_eq!((low.round(), high.round()), (40.0, 1614.0));
+    /// ```
+    pub fn harmonic_band(&self, up_to_partial: u32) -> Result<(f64, f64), Error> {
+        if up_to_partial == 0 {
+            return Err(Error::IncorrectPartial);
+        }
+        let (low, high) = self.frequency_band();
+        Ok((low, high * up_to_partial as f64))
+    }
+
+    /// Iterate over all pitches of the range, in ascending order.
+    pub fn pitches(&self) -> impl Iterator<Item = Pitch> {
+        PITCHES[self.low.index() as usize..=self.high.index() as usize]
+            .iter()
+            .copied()
+    }
+}
+
+/// Get pitch of given class within range, nearest (in semitones) to `near`.
+///
+/// When two pitches are equally near, the lower one is chosen.
+/// Returns `None` if range doesn't contain any pitch of given class.
+#[must_use]
+pub fn closest_octave_placement(class: PitchClass, near: Pitch, range: &Range) -> Option<Pitch> {
+    range
+        .pitches()
+        .filter(|pitch| pitch.class() == class)
+        .min_by_key(|pitch| near.semitones_to(*pitch).abs())
+}
+
+/// Place pitch classes (for example: of a chord) within range, near previous voicing.
+///
+/// Placement is greedy: each pitch class is independently
+/// [placed](closest_octave_placement) nearest to the pitch of previous voicing it's closest to,
+/// so resulting pitches may coincide or cross.
+///
+/// Returns `None` if previous voicing is empty or range doesn't contain some of the pitch classes.
+#[must_use]
+pub fn closest_voicing(
+    classes: &[PitchClass],
+    previous: &[Pitch],
+    range: &Range,
+) -> Option<Vec<Pitch>> {
+    classes
+        .iter()
+        .map(|class| {
+            previous
+                .iter()
+                .filter_map(|near| {
+                    closest_octave_placement(*class, *near, range)
+                        .map(|pitch| (near.semitones_to(pitch).abs(), pitch))
+                })
+                .min()
+                .map(|(_, pitch)| pitch)
+        })
+        .collect()
+}
+
+/// Find octave transposition (in semitones, multiple of 12) of melody placing it within range
+/// from `low` to `high` (inclusive), with its center (midpoint between the lowest and the highest note)
+/// nearest to center of the range.
+///
+/// When two transpositions are equally good, the smaller one (in absolute value) is chosen.
+/// Returns `None` if melody doesn't fit in the range in any octave (or `low` is higher than `high`).
+#[must_use]
+pub fn fit_into_range(notes: &[Note], low: Pitch, high: Pitch) -> Option<i32> {
+    if low > high {
+        return None;
+    }
+    let indices = notes
+        .iter()
+        .map(|note| note.chromatic_index().value() as i32);
+    let (lowest, highest) = match (indices.clone().min(), indices.max()) {
+        (Some(lowest), Some(highest)) => (lowest, highest),
+        _ => return Some(0),
+    };
+    let (low, high) = (low.index() as i32, high.index() as i32);
+
+    let min_octaves = (low - lowest).div_euclid(12);
+    let max_octaves = (high - highest).div_euclid(12);
+    (min_octaves..=max_octaves)
+        .map(|octaves| octaves * 12)
+        .filter(|shift| lowest + shift >= low && highest + shift <= high)
+        .min_by_key(|shift| {
+            (
+                (lowest + highest + 2 * shift - low - high).abs(),
+                shift.abs(),
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ranges() -> Vec<Range> {
+        [
+            (0, 107),
+            (48, 59),
+            (40, 76),
+            (57, 57),
+            (60, 66),
+            (0, 5),
+            (100, 107),
+        ]
+        .iter()
+        .map(|(low, high)| Range::new(PITCHES[*low], PITCHES[*high]).unwrap())
+        .collect()
+    }
+
+    fn classes() -> impl Iterator<Item = PitchClass> {
+        (0..PitchClass::COUNT).map(PitchClass::new)
+    }
+
+    #[test]
+    fn octave_placement_is_nearest_in_range() {
+        for range in ranges() {
+            for class in classes() {
+                let instances: Vec<Pitch> = range
+                    .pitches()
+                    .filter(|pitch| pitch.class() == class)
+                    .collect();
+                for near in PITCHES.iter().copied() {
+                    let placement = closest_octave_placement(class, near, &range);
+                    let Some(pitch) = placement else {
+                        assert!(instances.is_empty());
+                        continue;
+                    };
+                    assert!(range.contains(pitch));
+                    assert_eq!(pitch.class(), class);
+                    let distance = near.semitones_to(pitch).abs();
+                    for other in &instances {
+                        let other_distance = near.semitones_to(*other).abs();
+                        assert!(other_distance >= distance);
+                        if other_distance == distance {
+                            assert!(*other >= pitch);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn octave_placement_ties_go_down() {
+        let range = Range::new(PITCHES[36], PITCHES[71]).unwrap();
+        let placement = closest_octave_placement(PitchClass::new(6), PITCHES[48], &range);
+        assert_eq!(placement, Some(PITCHES[42]));
+    }
+
+    #[test]
+    fn voicing_is_nearest_to_previous() {
+        let previous = [PITCHES[48], PITCHES[52], PITCHES[55]];
+        for range in ranges() {
+            let classes = [2, 5, 7, 11].map(PitchClass::new);
+            let voicing = closest_voicing(&classes, &previous, &range);
+            let Some(voicing) = voicing else {
+                let missing = classes
+                    .iter()
+                    .any(|class| !range.pitches().any(|pitch| pitch.class() == *class));
+                assert!(missing);
+                continue;
+            };
+            assert_eq!(voicing.len(), classes.len());
+            for (pitch, class) in voicing.iter().zip(classes) {
+                assert!(range.contains(*pitch));
+                assert_eq!(pitch.class(), class);
+                let distance = |pitch: Pitch| {
+                    previous
+                        .iter()
+                        .map(|near| near.semitones_to(pitch).abs())
+                        .min()
+                        .unwrap()
+                };
+                for other in range.pitches().filter(|pitch| pitch.class() == class) {
+                    assert!(distance(other) >= distance(*pitch));
+                }
+            }
+        }
+        assert_eq!(
+            closest_voicing(&[PitchClass::new(0)], &[], &ranges()[0]),
+            None
+        );
+    }
+
+    #[test]
+    fn frequency_bands_of_guitar_range() {
+        let guitar = Range::GUITAR;
+        assert_eq!(guitar, Range::new(PITCHES[28], PITCHES[76]).unwrap());
+        let edges = |range: Range| (range.low().frequency(), range.high().frequency());
+        assert_eq!(edges(Range::BASS_GUITAR), (41.2, 392.0));
+        assert_eq!(edges(Range::VIOLIN), (196.0, 3520.0));
+        assert_eq!(edges(Range::PIANO), (27.5, 4186.01));
+        let (low, high) = guitar.frequency_band();
+        // E2 (82.41 Hz) and E6 (1318.51 Hz), half a semitone (2^(1/24)) outside
+        assert!((low - 80.060925).abs() < 1e-6, "{}", low);
+        assert!((high - 1357.145526).abs() < 1e-6, "{}", high);
+
+        assert_eq!(guitar.harmonic_band(1), Ok((low, high)));
+        let (harmonic_low, harmonic_high) = guitar.harmonic_band(5).unwrap();
+        assert_eq!(harmonic_low, low);
+        assert!(
+            (harmonic_high - 6785.727632).abs() < 1e-6,
+            "{}",
+            harmonic_high
+        );
+        assert_eq!(guitar.harmonic_band(0), Err(Error::IncorrectPartial));
+
+        let full = Range::new(PITCHES[0], PITCHES[107]).unwrap();
+        let (low, high) = full.harmonic_band(u32::MAX).unwrap();
+        assert!(low.is_finite() && high.is_finite());
+    }
+
+    #[test]
+    fn high_melody_is_shifted_down_an_octave() {
+        let notes = |indices: &[usize]| -> Vec<Note> {
+            indices
+                .iter()
+                .map(|index| Note::from(PITCHES[*index]))
+                .collect()
+        };
+        let (low, high) = (PITCHES[48], PITCHES[72]);
+        // C6 E6 G6 into C4 - C6
+        assert_eq!(fit_into_range(&notes(&[72, 76, 79]), low, high), Some(-12));
+        // C3 D3 into C4 - C6 - two octaves up centers it better than one
+        assert_eq!(fit_into_range(&notes(&[36, 38]), low, high), Some(24));
+        assert_eq!(fit_into_range(&notes(&[57, 64]), low, high), Some(0));
+        assert_eq!(fit_into_range(&notes(&[40, 70, 75]), low, high), None);
+        assert_eq!(fit_into_range(&[], low, high), Some(0));
+        assert_eq!(fit_into_range(&notes(&[60]), high, low), None);
+        assert_eq!(fit_into_range(&[], high, low), None);
+    }
+}
@@ -0,0 +1,156 @@
+//! Note durations.
+
+use crate::{Cents, Error, Interval, Pitch};
+
+/// Duration of a note, measured in beats (quarter notes).
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Duration {
+    beats: f64,
+}
+
+impl Duration {
+    /// Whole note.
+    pub const WHOLE: Duration = Duration { beats: 4.0 };
+    /// Half note.
+    pub const HALF: Duration = Duration { beats: 2.0 };
+    /// Quarter note.
+    pub const QUARTER: Duration = Duration { beats: 1.0 };
+    /// Eighth note.
+    pub const EIGHTH: Duration = Duration { beats: 0.5 };
+    /// Sixteenth note.
+    pub const SIXTEENTH: Duration = Duration { beats: 0.25 };
+    /// Thirty-second note.
+    pub const THIRTY_SECOND: Duration = Duration { beats: 0.125 };
+
+    /// Create new duration lasting given number of beats.
+    ///
+    /// Number of beats must be positive and finite.
+    pub fn new(beats: f64) -> Result<Self, Error> {
+        if !(beats.is_finite() && beats > 0.0) {
+            return Err(Error::IncorrectDuration);
+        }
+        Ok(Duration { beats })
+    }
+
+    /// Get number of beats.
+    #[must_use]
+    pub fn beats(&self) -> f64 {
+        self.beats
+    }
+}
+
+/// Snap raw durations (in beats, for example: measured from a performance)
+/// to the nearest multiple of `grid`.
+///
+/// Durations shorter than half of `grid` (or not positive) are snapped to `grid` itself,
+/// so no note disappears. So are durations that are not finite or whose multiple of `grid`
+/// would overflow.
+#[must_use]
+pub fn quantize_durations(durations: &[f64], grid: Duration) -> Vec<Duration> {
+    durations
+        .iter()
+        .map(|duration| {
+            let steps = (duration / grid.beats).round().max(1.0);
+            let beats = steps * grid.beats;
+            if beats.is_finite() {
+                Duration { beats }
+            } else {
+                grid
+            }
+        })
+        .collect()
+}
+
+/// Frequency (in Hz) of notes of given duration played at tempo of `bpm` beats (quarter notes) per minute
+/// (for example: 2 Hz for quarter notes at 120 BPM).
+///
+/// Subdivision is a [Duration] - a bare number could mean either its length in beats
+/// or number of notes per beat (off by a factor of 16 for sixteenth notes).
+///
+/// Returns `None` if `bpm` isn't positive and finite.
+#[must_use]
+pub fn frequency_from_bpm(bpm: f64, subdivision: Duration) -> Option<f64> {
+    if !(bpm.is_finite() && bpm > 0.0) {
+        return None;
+    }
+    Some(bpm / 60.0 / subdivision.beats)
+}
+
+/// Pitch whose period is nearest to given delay time (in seconds),
+/// along with deviation of frequency implied by delay time from it.
+///
+/// Uses exact frequencies ([Pitch::exact_frequency]).
+/// Returns `None` if delay time isn't positive and finite or implied frequency
+/// is out of supported range (like 2 Hz of 0.5 s delay).
+#[must_use]
+pub fn nearest_pitch_for_delay_time(seconds: f64) -> Option<(Pitch, Cents)> {
+    if !(seconds.is_finite() && seconds > 0.0) {
+        return None;
+    }
+    let frequency = 1.0 / seconds;
+    let pitch = Pitch::from_frequency(frequency)?;
+    Some((
+        pitch,
+        Interval::new(pitch.exact_frequency(), frequency).cents(),
+    ))
+}
+
+/// Delay time (in seconds) equal to period of pitch ([exact frequency](Pitch::exact_frequency)).
+#[must_use]
+pub fn delay_time_of(pitch: Pitch) -> f64 {
+    1.0 / pitch.exact_frequency()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PITCHES;
+
+    #[test]
+    fn durations_snap_to_sixteenth_grid() {
+        let quantized = quantize_durations(
+            &[0.24, 0.26, 0.49, 0.9, 0.1, 0.0, -1.0],
+            Duration::SIXTEENTH,
+        );
+        let beats: Vec<f64> = quantized.iter().map(Duration::beats).collect();
+        assert_eq!(beats, [0.25, 0.25, 0.5, 1.0, 0.25, 0.25, 0.25]);
+        assert_eq!(quantized[0], Duration::SIXTEENTH);
+        assert_eq!(quantize_durations(&[], Duration::SIXTEENTH), []);
+        assert_eq!(
+            quantize_durations(&[f64::INFINITY, 1e308, f64::NAN], Duration::SIXTEENTH),
+            [Duration::SIXTEENTH; 3]
+        );
+    }
+
+    #[test]
+    fn tempo_and_frequency() {
+        assert_eq!(frequency_from_bpm(120.0, Duration::QUARTER), Some(2.0));
+        assert_eq!(frequency_from_bpm(120.0, Duration::EIGHTH), Some(4.0));
+        assert_eq!(frequency_from_bpm(120.0, Duration::WHOLE), Some(0.5));
+        assert_eq!(frequency_from_bpm(90.0, Duration::SIXTEENTH), Some(6.0));
+        for bpm in [0.0, -120.0, f64::NAN, f64::INFINITY] {
+            assert_eq!(frequency_from_bpm(bpm, Duration::QUARTER), None);
+        }
+    }
+
+    #[test]
+    fn delay_times_and_pitches() {
+        let a4 = PITCHES[57];
+        assert_eq!(delay_time_of(a4), 1.0 / 440.0);
+        // quarter note at 120 BPM - 0.5 s, 2 Hz, far below C0
+        assert_eq!(nearest_pitch_for_delay_time(0.5), None);
+        assert_eq!(nearest_pitch_for_delay_time(1.0 / 10_000.0), None);
+        for seconds in [0.0, -0.5, f64::NAN, f64::INFINITY] {
+            assert_eq!(nearest_pitch_for_delay_time(seconds), None);
+        }
+
+        let (pitch, cents) = nearest_pitch_for_delay_time(1.0 / 445.0).unwrap();
+        assert_eq!(pitch, a4);
+        assert!((cents.value() - 19.56).abs() < 0.01, "{}", cents);
+        for pitch in PITCHES.iter() {
+            let (nearest, cents) = nearest_pitch_for_delay_time(delay_time_of(*pitch)).unwrap();
+            assert_eq!(nearest, *pitch);
+            assert!(cents.value().abs() < 1e-9);
+        }
+    }
+}
@@ -0,0 +1,991 @@
+//! Scale representation.
+
+use crate::{
+    Accidental, Cents, ChordQuality, ChromaticIndex, Detuned, Error, Letter, Note, Octave, Pitch,
+    PitchClass, PitchClassSet, Range, Tuning, PITCHES,
+};
+
+/// Predefined scale types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScaleType {
+    /// Major scale (Ionian mode).
+    Major,
+    /// Dorian mode.
+    Dorian,
+    /// Phrygian mode.
+    Phrygian,
+    /// Lydian mode.
+    Lydian,
+    /// Mixolydian mode.
+    Mixolydian,
+    /// Natural minor scale (Aeolian mode).
+    NaturalMinor,
+    /// Locrian mode.
+    Locrian,
+    /// Harmonic minor scale.
+    HarmonicMinor,
+    /// Melodic minor scale (ascending form).
+    MelodicMinor,
+    /// Major pentatonic scale.
+    MajorPentatonic,
+    /// Minor pentatonic scale.
+    MinorPentatonic,
+    /// Blues scale (minor pentatonic with added diminished fifth).
+    Blues,
+    /// Whole-tone scale (Messiaen's first mode of limited transposition).
+    WholeTone,
+    /// Octatonic (half-whole diminished) scale (Messiaen's second mode of limited transposition).
+    Octatonic,
+    /// Messiaen's third mode of limited transposition.
+    MessiaenMode3,
+    /// Messiaen's fourth mode of limited transposition.
+    MessiaenMode4,
+    /// Messiaen's fifth mode of limited transposition.
+    MessiaenMode5,
+    /// Messiaen's sixth mode of limited transposition.
+    MessiaenMode6,
+    /// Messiaen's seventh mode of limited transposition.
+    MessiaenMode7,
+}
+
+impl ScaleType {
+    /// All predefined scale types.
+    pub const ALL: [ScaleType; 19] = [
+        ScaleType::Major,
+        ScaleType::Dorian,
+        ScaleType::Phrygian,
+        ScaleType::Lydian,
+        ScaleType::Mixolydian,
+        ScaleType::NaturalMinor,
+        ScaleType::Locrian,
+        ScaleType::HarmonicMinor,
+        ScaleType::MelodicMinor,
+        ScaleType::MajorPentatonic,
+        ScaleType::MinorPentatonic,
+        ScaleType::Blues,
+        ScaleType::WholeTone,
+        ScaleType::Octatonic,
+        ScaleType::MessiaenMode3,
+        ScaleType::MessiaenMode4,
+        ScaleType::MessiaenMode5,
+        ScaleType::MessiaenMode6,
+        ScaleType::MessiaenMode7,
+    ];
+
+    /// Steps (in semitones) between consecutive scale degrees, ending with step back to the root.
+    #[must_use]
+    pub fn steps(&self) -> &'static [u8] {
+        match self {
+            ScaleType::Major => &[2, 2, 1, 2, 2, 2, 1],
+            ScaleType::Dorian => &[2, 1, 2, 2, 2, 1, 2],
+            ScaleType::Phrygian => &[1, 2, 2, 2, 1, 2, 2],
+            ScaleType::Lydian => &[2, 2, 2, 1, 2, 2, 1],
+            ScaleType::Mixolydian => &[2, 2, 1, 2, 2, 1, 2],
+            ScaleType::NaturalMinor => &[2, 1, 2, 2, 1, 2, 2],
+            ScaleType::Locrian => &[1, 2, 2, 1, 2, 2, 2],
+            ScaleType::HarmonicMinor => &[2, 1, 2, 2, 1, 3, 1],
+            ScaleType::MelodicMinor => &[2, 1, 2, 2, 2, 2, 1],
+            ScaleType::MajorPentatonic => &[2, 2, 3, 2, 3],
+            ScaleType::MinorPentatonic => &[3, 2, 2, 3, 2],
+            ScaleType::Blues => &[3, 2, 1, 1, 3, 2],
+            ScaleType::WholeTone => &[2, 2, 2, 2, 2, 2],
+            ScaleType::Octatonic => &[1, 2, 1, 2, 1, 2, 1, 2],
+            ScaleType::MessiaenMode3 => &[2, 1, 1, 2, 1, 1, 2, 1, 1],
+            ScaleType::MessiaenMode4 => &[1, 1, 3, 1, 1, 1, 3, 1],
+            ScaleType::MessiaenMode5 => &[1, 4, 1, 1, 4, 1],
+            ScaleType::MessiaenMode6 => &[2, 2, 1, 1, 2, 2, 1, 1],
+            ScaleType::MessiaenMode7 => &[1, 1, 1, 2, 1, 1, 1, 1, 2, 1],
+        }
+    }
+}
+
+/// Struct representing scale.
+///
+/// Scale is a small immutable [Copy] value, safe to share between threads.
+/// Its membership queries ([Scale::contains], [Scale::contains_fast], [Scale::degree_of]
+/// and [Scale::pitch_classes]) neither allocate nor lock, so they can be used in real-time threads.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Scale {
+    root: PitchClass,
+    pattern: PitchClassSet,
+}
+
+impl Scale {
+    /// Create new scale.
+    #[must_use]
+    pub fn new(root: PitchClass, scale_type: ScaleType) -> Self {
+        Scale::from_steps(root, scale_type.steps()).expect("steps of predefined scales are correct")
+    }
+
+    /// Create scale from steps (in semitones) between consecutive scale degrees.
+    ///
+    /// Steps must be positive and add up to an octave (12 semitones),
+    /// so the last step leads back to the root.
+    pub fn from_steps(root: PitchClass, steps: &[u8]) -> Result<Self, Error> {
+        if steps.contains(&0) || steps.iter().map(|step| *step as u32).sum::<u32>() != 12 {
+            return Err(Error::IncorrectScaleSteps);
+        }
+
+        let pattern = steps[..steps.len() - 1]
+            .iter()
+            .scan(0, |offset, step| {
+                *offset += step;
+                Some(PitchClass::new(*offset))
+            })
+            .chain(std::iter::once(PitchClass::new(0)))
+            .collect();
+        Ok(Scale { root, pattern })
+    }
+
+    /// Get scale root.
+    #[must_use]
+    pub fn root(&self) -> PitchClass {
+        self.root
+    }
+
+    /// Get scale type (or `None` if scale isn't one of predefined types).
+    #[must_use]
+    pub fn scale_type(&self) -> Option<ScaleType> {
+        ScaleType::ALL
+            .into_iter()
+            .find(|scale_type| Scale::new(self.root, *scale_type) == *self)
+    }
+
+    /// Number of scale degrees.
+    #[must_use]
+    pub fn degree_count(&self) -> usize {
+        self.pattern.len()
+    }
+
+    /// Steps (in semitones) between consecutive scale degrees, ending with step back to the root.
+    #[must_use]
+    pub fn steps(&self) -> Vec<u8> {
+        self.steps_iter().collect()
+    }
+
+    /// Iterator over [steps](Scale::steps) of the scale, without collecting them.
+    pub fn steps_iter(&self) -> impl Iterator<Item = u8> + '_ {
+        let offsets = || self.pattern.iter().map(|class| class.number());
+        offsets()
+            .zip(offsets().skip(1).chain(std::iter::once(12)))
+            .map(|(a, b)| b - a)
+    }
+
+    /// Number of distinct transpositions of the scale - 12 for most scales,
+    /// fewer for symmetric ones (for example: 2 for whole-tone scale, 3 for octatonic scale).
+    ///
+    /// It's also the smallest number of semitones transposing scale onto itself.
+    #[must_use]
+    pub fn transposition_count(&self) -> u8 {
+        (1..PitchClass::COUNT)
+            .find(|semitones| self.pattern.transpose(*semitones) == self.pattern)
+            .unwrap_or(PitchClass::COUNT)
+    }
+
+    /// Check if scale has the same interval pattern as `other`, up to rotation
+    /// (for example: Dorian is a mode of major scale).
+    ///
+    /// Roots of scales don't matter.
+    #[must_use]
+    pub fn is_mode_of(&self, other: &Scale) -> bool {
+        (0..PitchClass::COUNT).any(|semitones| other.pattern.transpose(semitones) == self.pattern)
+    }
+
+    /// Pitch classes of consecutive scale degrees, starting with root.
+    #[must_use]
+    pub fn degrees(&self) -> Vec<PitchClass> {
+        self.degrees_iter().collect()
+    }
+
+    /// Iterator over [degrees](Scale::degrees) of the scale, without collecting them.
+    pub fn degrees_iter(&self) -> impl Iterator<Item = PitchClass> + '_ {
+        self.pattern
+            .iter()
+            .map(|offset| PitchClass::new(self.root.number() + offset.number()))
+    }
+
+    /// Notes of heptatonic scale starting in given octave, spelled with consecutive letters
+    /// (for example: F G A B♭ C D E for F major, E♭ F G A♭ B♭ C D for E♭ major).
+    ///
+    /// Root is spelled the way that needs the fewest sharps and flats (sharps when tied,
+    /// for example: F♯ major rather than G♭ major).
+    /// Returns `None` if scale doesn't have 7 degrees, can't be spelled that way
+    /// without double sharps or flats, or reaches beyond supported octaves.
+    #[must_use]
+    pub fn diatonic_notes(&self, octave: Octave) -> Option<[Note; 7]> {
+        if self.degree_count() != Letter::ALL.len() {
+            return None;
+        }
+        let root = ChromaticIndex(u8::from(octave) as i16 * 12 + self.root.number() as i16);
+        let spell = |root_letter: Letter| -> Option<[Note; 7]> {
+            let mut notes =
+                [Note::new_unchecked_spelling(root_letter, octave, Accidental::None); 7];
+            let mut letter = root_letter;
+            for (note, offset) in notes.iter_mut().zip(self.pattern.iter()) {
+                let index = root.transpose(offset.number() as i16);
+                let alteration = (index.value() - letter.semitones() as i16 + 6).rem_euclid(12) - 6;
+                let accidental = match alteration {
+                    -1 => Accidental::Flat,
+                    0 => Accidental::None,
+                    1 => Accidental::Sharp,
+                    _ => return None,
+                };
+                let octave = u8::try_from((index.value() - alteration).div_euclid(12)).ok()?;
+                let octave = Octave::try_from(octave).ok()?;
+                *note = Note::new_unchecked_spelling(letter, octave, accidental);
+                letter = letter.next();
+            }
+            Some(notes)
+        };
+        [Accidental::None, Accidental::Sharp, Accidental::Flat]
+            .into_iter()
+            .filter_map(|accidental| {
+                Letter::ALL.into_iter().find(|letter| {
+                    (letter.semitones() as i8 + accidental.alteration()).rem_euclid(12) as u8
+                        == self.root.number()
+                })
+            })
+            .filter_map(spell)
+            .min_by_key(|notes| {
+                notes
+                    .iter()
+                    .filter(|note| note.accidental() != Accidental::None)
+                    .count()
+            })
+    }
+
+    /// Set of pitch classes belonging to the scale.
+    #[must_use]
+    pub fn pitch_classes(&self) -> PitchClassSet {
+        self.pattern.transpose(self.root.number())
+    }
+
+    /// Check if pitch belongs to the scale.
+    #[must_use]
+    pub fn contains(&self, pitch: Pitch) -> bool {
+        self.pitch_classes().contains(pitch.class())
+    }
+
+    /// Precomputed bit mask of the scale, bit `n` representing pitch class number `n`.
+    ///
+    /// Meant to be used with [Scale::contains_fast] in hot loops.
+    #[must_use]
+    pub fn membership_mask(&self) -> u16 {
+        self.pitch_classes().mask()
+    }
+
+    /// Check if pitch belongs to the scale with given [membership mask](Scale::membership_mask).
+    ///
+    /// Equivalent to [Scale::contains].
+    #[must_use]
+    pub fn contains_fast(mask: u16, pitch: Pitch) -> bool {
+        mask & (1 << pitch.number()) != 0
+    }
+
+    /// Get scale degree (counting from 1 - root) of pitch,
+    /// or `None` if pitch doesn't belong to the scale.
+    #[must_use]
+    pub fn degree_of(&self, pitch: Pitch) -> Option<u8> {
+        self.degrees_iter()
+            .position(|class| class == pitch.class())
+            .map(|position| position as u8 + 1)
+    }
+
+    /// Attach intonation offsets (one per scale degree, starting with root) to the scale.
+    ///
+    /// Offsets must be within ±100 cents, standard tuning (A₄ = 440 Hz) is used.
+    /// See [TunedScale::new] for other bounds and tunings.
+    pub fn with_intonation(&self, offsets: &[Cents]) -> Result<TunedScale, Error> {
+        TunedScale::new(
+            *self,
+            offsets,
+            Cents::new(100.0).expect("100 is a number"),
+            Tuning::default(),
+        )
+    }
+
+    /// Get mode of the scale starting on given degree (counting from 1 - root).
+    ///
+    /// Root of the mode is the pitch class of that degree of this scale,
+    /// so for example: `mode(2)` of C major is D dorian and `mode(5)` is G mixolydian.
+    ///
+    /// Degrees wrap around, `mode(1)` and `mode(degree_count() + 1)` are the scale itself
+    /// (and `mode(0)` is the same as `mode(degree_count())`).
+    #[must_use]
+    pub fn mode(&self, degree: usize) -> Scale {
+        let count = self.degree_count();
+        let offset = self
+            .pattern
+            .iter()
+            .nth((degree % count + count - 1) % count)
+            .expect("scale has at least one degree")
+            .number();
+        Scale {
+            root: PitchClass::new(self.root.number() + offset),
+            pattern: self.pattern.transpose(PitchClass::COUNT - offset),
+        }
+    }
+
+    /// Quality of seventh chord built on every scale degree (counting from 1 - root)
+    /// by stacking thirds - every other degree of the scale (for example: Cmaj7, Dm7, Em7,
+    /// Fmaj7, G7, Am7 and Bø7 in C major).
+    ///
+    /// Meant for heptatonic scales, degrees whose chord isn't one of known
+    /// seventh chord qualities are skipped.
+    #[must_use]
+    pub fn seventh_chords(&self) -> Vec<(u8, ChordQuality)> {
+        self.seventh_chords_iter().collect()
+    }
+
+    /// Iterator over [seventh chords](Scale::seventh_chords) of the scale, without collecting them.
+    pub fn seventh_chords_iter(&self) -> impl Iterator<Item = (u8, ChordQuality)> + '_ {
+        let count = self.degree_count();
+        (0..count).filter_map(move |degree| {
+            let class = |degree: usize| {
+                self.degrees_iter()
+                    .nth(degree % count)
+                    .expect("invariant")
+                    .number()
+            };
+            let root = class(degree);
+            let semitones = [2, 4, 6].map(|third| {
+                (class(degree + third) + PitchClass::COUNT - root) % PitchClass::COUNT
+            });
+            let quality = ChordQuality::ALL.into_iter().find(|quality| {
+                let intervals = quality.intervals();
+                intervals
+                    .iter()
+                    .map(|interval| interval.number())
+                    .eq([3, 5, 7])
+                    && intervals
+                        .iter()
+                        .map(|interval| interval.semitones())
+                        .eq(semitones)
+            })?;
+            Some((degree as u8 + 1, quality))
+        })
+    }
+}
+
+/// Find scale (type from `candidates` and root) best covering pitches -
+/// the one containing most of them (counting repeated pitches).
+///
+/// Root is a pitch class (like [Scale::root]) - scale membership doesn't depend on octave,
+/// so there's no single root pitch to return.
+///
+/// Ties are broken in favour of scales with fewer degrees, then earlier candidates,
+/// then lower roots (starting with C).
+/// Returns `None` if there are no candidates.
+#[must_use]
+pub fn best_fit_scale(
+    pitches: &[Pitch],
+    candidates: &[ScaleType],
+) -> Option<(ScaleType, PitchClass)> {
+    candidates
+        .iter()
+        .enumerate()
+        .flat_map(|(order, scale_type)| {
+            (0..PitchClass::COUNT).map(move |root| (order, *scale_type, PitchClass::new(root)))
+        })
+        .min_by_key(|(order, scale_type, root)| {
+            let scale = Scale::new(*root, *scale_type);
+            let covered = pitches
+                .iter()
+                .filter(|pitch| scale.contains(**pitch))
+                .count();
+            (
+                std::cmp::Reverse(covered),
+                scale.degree_count(),
+                *order,
+                *root,
+            )
+        })
+        .map(|(_, scale_type, root)| (scale_type, root))
+}
+
+/// Detect diatonic mode of a melody with given tonic (for example: [Mixolydian](ScaleType::Mixolydian)
+/// for melody on G using F♮) - the mode built on tonic containing most of its pitches
+/// (counting repeated pitches).
+///
+/// Melody may not determine all degrees (for example: melody without third fits
+/// both major and minor modes), ties are broken in favour of modes in order of
+/// [ScaleType::ALL] (major, dorian, phrygian, lydian, mixolydian, natural minor, locrian).
+/// Returns `None` for empty melody.
+#[must_use]
+pub fn detect_mode(pitches: &[Pitch], tonic: Pitch) -> Option<ScaleType> {
+    if pitches.is_empty() {
+        return None;
+    }
+    // the first seven scale types are diatonic modes
+    ScaleType::ALL[..7].iter().copied().min_by_key(|mode| {
+        let scale = Scale::new(tonic.class(), *mode);
+        let covered = pitches
+            .iter()
+            .filter(|pitch| scale.contains(**pitch))
+            .count();
+        std::cmp::Reverse(covered)
+    })
+}
+
+/// Policy of transposing notes not belonging to the scale by [transpose_in_scale].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChromaticPolicy {
+    /// Move note by the same number of semitones as the scale degree nearest to it
+    /// (the lower one, if two are equally near).
+    ShiftWithNearestDegree,
+    /// Move note by the same number of semitones as the previous note of the melody
+    /// belonging to the scale (or the next one, if there's no previous one), keeping
+    /// their chromatic relation (for example: of chromatic passing tone).
+    ///
+    /// If no note of the melody belongs to the scale, works like [ChromaticPolicy::ShiftWithNearestDegree].
+    ShiftChromatically,
+    /// Fail with [Error::NoteNotInScale].
+    Reject,
+}
+
+/// Transpose melody by given number of scale degrees (up if positive, down if negative).
+///
+/// Notes belonging to the scale move to another degree of the scale,
+/// notes not belonging to it are moved according to `chromatic_policy`.
+/// Fails with index of the first note that would be transposed out of supported range
+/// (or that doesn't belong to the scale, if policy is [ChromaticPolicy::Reject]).
+pub fn transpose_in_scale(
+    melody: &[Pitch],
+    scale: &Scale,
+    steps: i8,
+    chromatic_policy: ChromaticPolicy,
+) -> Result<Vec<Pitch>, Error> {
+    let degrees: Vec<Pitch> = PITCHES
+        .iter()
+        .copied()
+        .filter(|pitch| scale.contains(*pitch))
+        .collect();
+    let shift_degree = |position: usize| -> Option<i16> {
+        let target = degrees.get(usize::try_from(position as isize + steps as isize).ok()?)?;
+        Some(degrees[position].semitones_to(*target))
+    };
+    let nearest_degree_shift = |pitch: Pitch| -> Option<i16> {
+        let above = degrees.partition_point(|degree| *degree < pitch);
+        let position = match above.checked_sub(1) {
+            Some(below)
+                if above == degrees.len()
+                    || degrees[below].semitones_to(pitch) <= pitch.semitones_to(degrees[above]) =>
+            {
+                below
+            }
+            _ => above,
+        };
+        shift_degree(position)
+    };
+    let degree_shifts: Vec<Option<Option<i16>>> = melody
+        .iter()
+        .map(|pitch| degrees.binary_search(pitch).ok().map(shift_degree))
+        .collect();
+
+    melody
+        .iter()
+        .enumerate()
+        .map(|(index, pitch)| {
+            let shift = match degree_shifts[index] {
+                Some(shift) => shift,
+                None => match chromatic_policy {
+                    ChromaticPolicy::ShiftWithNearestDegree => nearest_degree_shift(*pitch),
+                    ChromaticPolicy::ShiftChromatically => degree_shifts[..index]
+                        .iter()
+                        .rev()
+                        .chain(degree_shifts[index + 1..].iter())
+                        .find_map(|shift| *shift)
+                        .unwrap_or_else(|| nearest_degree_shift(*pitch)),
+                    ChromaticPolicy::Reject => return Err(Error::NoteNotInScale { index }),
+                },
+            };
+            shift
+                .and_then(|shift| {
+                    ChromaticIndex::from(*pitch)
+                        .transpose(shift)
+                        .try_into_pitch()
+                        .ok()
+                })
+                .ok_or(Error::TranspositionNotInRange { index })
+        })
+        .collect()
+}
+
+/// Scale with intonation offsets from equal temperament attached to its degrees.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TunedScale {
+    scale: Scale,
+    offsets: Vec<Cents>,
+    tuning: Tuning,
+}
+
+impl TunedScale {
+    /// Create new tuned scale.
+    ///
+    /// There must be exactly one offset per scale degree (starting with root),
+    /// every one of them within `max_offset` from zero.
+    pub fn new(
+        scale: Scale,
+        offsets: &[Cents],
+        max_offset: Cents,
+        tuning: Tuning,
+    ) -> Result<Self, Error> {
+        if offsets.len() != scale.degree_count()
+            || offsets.iter().any(|offset| offset.abs() > max_offset.abs())
+        {
+            return Err(Error::IncorrectOffsets);
+        }
+
+        Ok(TunedScale {
+            scale,
+            offsets: offsets.to_vec(),
+            tuning,
+        })
+    }
+
+    /// Get scale.
+    #[must_use]
+    pub fn scale(&self) -> Scale {
+        self.scale
+    }
+
+    /// Get intonation offsets of scale degrees, starting with root.
+    #[must_use]
+    pub fn offsets(&self) -> &[Cents] {
+        &self.offsets
+    }
+
+    /// Get tuning.
+    #[must_use]
+    pub fn tuning(&self) -> Tuning {
+        self.tuning
+    }
+
+    /// Frequency of scale degree (counting from 1 - root), with root in given octave.
+    ///
+    /// Offset is applied to exact equal-tempered frequency in scale's tuning.
+    /// Returns `None` if there's no such degree.
+    #[must_use]
+    pub fn frequency_of_degree(&self, degree: u8, octave: Octave) -> Option<f64> {
+        let index = (degree as usize).checked_sub(1)?;
+        let offset = self.offsets.get(index)?;
+        let semitones: u8 = self.scale.steps()[..index].iter().sum();
+        let root = ChromaticIndex(u8::from(octave) as i16 * 12 + self.scale.root().number() as i16);
+        let frequency = self
+            .tuning
+            .frequency_of_index(root.transpose(semitones as i16));
+        Some(frequency * 2.0_f64.powf(offset.value() / 1200.0))
+    }
+
+    /// Get all pitches of the scale within range, detuned by offsets of their degrees.
+    #[must_use]
+    pub fn detuned_notes_in_range(&self, range: &Range) -> Vec<Detuned> {
+        self.detuned_notes_in_range_iter(range).collect()
+    }
+
+    /// Iterator over [detuned notes](TunedScale::detuned_notes_in_range) of the scale within range,
+    /// without collecting them.
+    pub fn detuned_notes_in_range_iter(&self, range: &Range) -> impl Iterator<Item = Detuned> + '_ {
+        range.pitches().filter_map(|pitch| {
+            let degree = self.scale.degree_of(pitch)?;
+            Some(Detuned::new(pitch, self.offsets[degree as usize - 1]))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Accidental, Letter, Note};
+
+    fn scale(root: u8, scale_type: ScaleType) -> Scale {
+        Scale::new(PitchClass::new(root), scale_type)
+    }
+
+    #[test]
+    fn modes_are_counted_from_1() {
+        let c_major = scale(0, ScaleType::Major);
+        assert_eq!(c_major.mode(1), c_major);
+        assert_eq!(c_major.mode(2), scale(2, ScaleType::Dorian));
+        assert_eq!(c_major.mode(5), scale(7, ScaleType::Mixolydian));
+        assert_eq!(c_major.mode(5).scale_type(), Some(ScaleType::Mixolydian));
+        assert_eq!(c_major.mode(7), scale(11, ScaleType::Locrian));
+        assert_eq!(c_major.mode(8), c_major);
+        assert_eq!(c_major.mode(0), c_major.mode(7));
+    }
+
+    #[test]
+    fn degrees_of_pitches_in_c_major() {
+        let c_major = scale(0, ScaleType::Major);
+        assert_eq!(c_major.degree_of(PITCHES[55]), Some(5));
+        assert_eq!(c_major.degree_of(PITCHES[67]), Some(5));
+        assert_eq!(c_major.degree_of(PITCHES[48]), Some(1));
+        assert_eq!(c_major.degree_of(PITCHES[59]), Some(7));
+        assert_eq!(c_major.degree_of(PITCHES[54]), None);
+        assert_eq!(scale(7, ScaleType::Major).degree_of(PITCHES[54]), Some(7));
+    }
+
+    fn cents(values: &[f64]) -> Vec<Cents> {
+        values
+            .iter()
+            .map(|value| Cents::new(*value).unwrap())
+            .collect()
+    }
+
+    fn assert_close(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-9, "{} != {}", a, b);
+    }
+
+    #[test]
+    fn intonation_offsets_are_validated() {
+        let c_major = scale(0, ScaleType::Major);
+        let offsets = cents(&[0.0, 4.0, -14.0, -2.0, 2.0, -16.0, 12.0]);
+        assert!(c_major.with_intonation(&offsets).is_ok());
+        assert_eq!(
+            c_major.with_intonation(&offsets[..6]),
+            Err(Error::IncorrectOffsets)
+        );
+        let too_wide = cents(&[0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 100.5]);
+        assert_eq!(
+            c_major.with_intonation(&too_wide),
+            Err(Error::IncorrectOffsets)
+        );
+        let bound = cents(&[0.0, 0.0, 0.0, 0.0, 0.0, 0.0, -100.0]);
+        assert!(c_major.with_intonation(&bound).is_ok());
+
+        let max_offset = Cents::new(20.0).unwrap();
+        let tuning = Tuning::default();
+        assert!(TunedScale::new(c_major, &offsets, max_offset, tuning).is_ok());
+        let offsets = cents(&[0.0, 4.0, -14.0, -2.0, 2.0, -21.0, 12.0]);
+        assert_eq!(
+            TunedScale::new(c_major, &offsets, max_offset, tuning),
+            Err(Error::IncorrectOffsets)
+        );
+    }
+
+    #[test]
+    fn tuned_degrees_wrap_into_next_octave() {
+        // B major: degrees from C♯ on lie in the octave above root
+        let offsets = cents(&[0.0, 10.0, 0.0, 0.0, 0.0, 0.0, -10.0]);
+        let tuned = scale(11, ScaleType::Major)
+            .with_intonation(&offsets)
+            .unwrap();
+        let tuning = Tuning::default();
+        assert_close(
+            tuned.frequency_of_degree(1, Octave::Fifth).unwrap(),
+            tuning.frequency(PITCHES[59]),
+        );
+        assert_close(
+            tuned.frequency_of_degree(2, Octave::Fifth).unwrap(),
+            tuning.frequency(PITCHES[61]) * 2.0_f64.powf(10.0 / 1200.0),
+        );
+        assert_close(
+            tuned.frequency_of_degree(7, Octave::Fifth).unwrap(),
+            tuning.frequency(PITCHES[70]) * 2.0_f64.powf(-10.0 / 1200.0),
+        );
+        assert_close(
+            tuned.frequency_of_degree(2, Octave::Sixth).unwrap(),
+            2.0 * tuned.frequency_of_degree(2, Octave::Fifth).unwrap(),
+        );
+        assert_eq!(tuned.frequency_of_degree(0, Octave::Fifth), None);
+        assert_eq!(tuned.frequency_of_degree(8, Octave::Fifth), None);
+    }
+
+    #[test]
+    fn tuned_scale_in_non_standard_tuning() {
+        let c_major = scale(0, ScaleType::Major);
+        let offsets = cents(&[0.0, 4.0, -14.0, -2.0, 2.0, -16.0, 12.0]);
+        let baroque = Tuning::new(415.0).unwrap();
+        let tuned =
+            TunedScale::new(c_major, &offsets, Cents::new(100.0).unwrap(), baroque).unwrap();
+        assert_eq!(tuned.tuning(), baroque);
+        assert_close(
+            tuned.frequency_of_degree(6, Octave::Fifth).unwrap(),
+            415.0 * 2.0_f64.powf(-16.0 / 1200.0),
+        );
+
+        let range = Range::new(PITCHES[48], PITCHES[72]).unwrap();
+        let detuned = tuned.detuned_notes_in_range(&range);
+        assert_eq!(detuned.len(), 15);
+        assert!(tuned
+            .detuned_notes_in_range_iter(&range)
+            .eq(detuned.clone()));
+        for (position, note) in detuned.iter().enumerate() {
+            let degree = position % 7 + 1;
+            let octave = Octave::from_number(4 + position as u8 / 7).unwrap();
+            assert_eq!(note.cents(), offsets[degree - 1]);
+            assert_close(
+                note.frequency_in(&baroque),
+                tuned.frequency_of_degree(degree as u8, octave).unwrap(),
+            );
+        }
+    }
+
+    #[test]
+    fn transposition_counts_of_presets() {
+        for scale_type in ScaleType::ALL {
+            let expected = match scale_type {
+                ScaleType::WholeTone => 2,
+                ScaleType::Octatonic => 3,
+                ScaleType::MessiaenMode3 => 4,
+                ScaleType::MessiaenMode4
+                | ScaleType::MessiaenMode5
+                | ScaleType::MessiaenMode6
+                | ScaleType::MessiaenMode7 => 6,
+                _ => 12,
+            };
+            for root in 0..PitchClass::COUNT {
+                let scale = scale(root, scale_type);
+                let count = scale.transposition_count();
+                assert_eq!(count, expected, "{:?}", scale_type);
+
+                let classes = scale.pitch_classes();
+                assert_eq!(classes.transpose(count), classes);
+                for semitones in 1..count {
+                    assert_ne!(classes.transpose(semitones), classes);
+                }
+                let distinct: Vec<PitchClassSet> = (0..PitchClass::COUNT)
+                    .map(|semitones| classes.transpose(semitones))
+                    .fold(vec![], |mut distinct, transposed| {
+                        if !distinct.contains(&transposed) {
+                            distinct.push(transposed);
+                        }
+                        distinct
+                    });
+                assert_eq!(distinct.len(), count as usize);
+            }
+        }
+    }
+
+    #[test]
+    fn modes_are_rotations() {
+        let c_major = scale(0, ScaleType::Major);
+        assert!(scale(2, ScaleType::Dorian).is_mode_of(&c_major));
+        assert!(scale(5, ScaleType::Lydian).is_mode_of(&c_major));
+        assert!(!scale(0, ScaleType::HarmonicMinor).is_mode_of(&c_major));
+        assert!(!scale(0, ScaleType::MajorPentatonic).is_mode_of(&c_major));
+        assert!(scale(1, ScaleType::Octatonic).is_mode_of(&scale(0, ScaleType::Octatonic)));
+
+        for scale_type in ScaleType::ALL {
+            let tested = scale(3, scale_type);
+            for degree in 1..=tested.degree_count() {
+                let mode = tested.mode(degree);
+                assert!(mode.is_mode_of(&tested));
+                assert!(tested.is_mode_of(&mode));
+            }
+            for other in ScaleType::ALL {
+                let other = scale(0, other);
+                assert_eq!(tested.is_mode_of(&other), other.is_mode_of(&tested));
+                if tested.is_mode_of(&other) {
+                    assert_eq!(tested.degree_count(), other.degree_count());
+                    assert_eq!(tested.transposition_count(), other.transposition_count());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn blues_pitches_fit_blues_scale() {
+        // A3 C4 D4 D♯4 E4 G4 A4 - A blues
+        let pitches: Vec<Pitch> = [45, 48, 50, 51, 52, 55, 57, 51, 50]
+            .iter()
+            .map(|index| PITCHES[*index])
+            .collect();
+        assert_eq!(
+            best_fit_scale(&pitches, &ScaleType::ALL),
+            Some((ScaleType::Blues, PitchClass::new(9)))
+        );
+        // without blue note, pentatonic scales (fewer degrees) fit, C major coming before A minor
+        let pentatonic: Vec<Pitch> = pitches
+            .iter()
+            .copied()
+            .filter(|pitch| pitch.index() != 51)
+            .collect();
+        assert_eq!(
+            best_fit_scale(&pentatonic, &ScaleType::ALL),
+            Some((ScaleType::MajorPentatonic, PitchClass::new(0)))
+        );
+        assert_eq!(best_fit_scale(&pitches, &[]), None);
+    }
+
+    fn melody(indices: &[usize]) -> Vec<Pitch> {
+        indices.iter().map(|index| PITCHES[*index]).collect()
+    }
+
+    #[test]
+    fn transposing_chromatic_passing_tone_by_scale_degrees() {
+        let c_major = scale(0, ScaleType::Major);
+        // E4 F♯4 G4 A4
+        let tune = melody(&[52, 54, 55, 57]);
+        let transpose = |policy| transpose_in_scale(&tune, &c_major, 1, policy);
+        // F♯4 follows F4 (lower of equally near F4 and G4) up a whole tone
+        assert_eq!(
+            transpose(ChromaticPolicy::ShiftWithNearestDegree),
+            Ok(melody(&[53, 56, 57, 59]))
+        );
+        // F♯4 follows E4 up a semitone
+        assert_eq!(
+            transpose(ChromaticPolicy::ShiftChromatically),
+            Ok(melody(&[53, 55, 57, 59]))
+        );
+        assert_eq!(
+            transpose(ChromaticPolicy::Reject),
+            Err(Error::NoteNotInScale { index: 1 })
+        );
+
+        // without previous note of the scale, the next one is followed
+        let tune = melody(&[54, 55]);
+        assert_eq!(
+            transpose_in_scale(&tune, &c_major, 1, ChromaticPolicy::ShiftChromatically),
+            Ok(melody(&[56, 57]))
+        );
+        assert_eq!(
+            transpose_in_scale(&tune, &c_major, -2, ChromaticPolicy::ShiftChromatically),
+            Ok(melody(&[51, 52]))
+        );
+    }
+
+    #[test]
+    fn transposing_out_of_range() {
+        let c_major = scale(0, ScaleType::Major);
+        let tune = melody(&[48, 105, 107]);
+        assert_eq!(
+            transpose_in_scale(&tune, &c_major, 1, ChromaticPolicy::Reject),
+            Err(Error::TranspositionNotInRange { index: 2 })
+        );
+        let tune = melody(&[2, 0]);
+        assert_eq!(
+            transpose_in_scale(&tune, &c_major, -2, ChromaticPolicy::Reject),
+            Err(Error::TranspositionNotInRange { index: 0 })
+        );
+    }
+
+    #[test]
+    fn diatonic_transposition_moves_letters() {
+        // in C major, moving by scale degrees is moving letters of natural notes
+        let c_major = scale(0, ScaleType::Major);
+        let naturals = melody(&[45, 47, 48, 50, 52, 53, 55, 57, 59, 60, 62, 64, 65, 67]);
+        for steps in -14..=14 {
+            let transposed =
+                transpose_in_scale(&naturals, &c_major, steps, ChromaticPolicy::Reject).unwrap();
+            for (pitch, transposed) in naturals.iter().zip(transposed) {
+                let note = Note::from(*pitch);
+                let position = Letter::ALL
+                    .iter()
+                    .position(|letter| *letter == note.letter())
+                    .unwrap() as i16
+                    + u8::from(note.octave()) as i16 * 7
+                    + steps as i16;
+                let expected = Note::new(
+                    Letter::ALL[position.rem_euclid(7) as usize],
+                    Octave::try_from(position.div_euclid(7) as u8).unwrap(),
+                    Accidental::None,
+                )
+                .unwrap();
+                assert_eq!(Note::from(transposed), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn diatonic_seventh_chords() {
+        use ChordQuality::*;
+        assert_eq!(
+            scale(0, ScaleType::Major).seventh_chords(),
+            [
+                (1, Major7),
+                (2, Minor7),
+                (3, Minor7),
+                (4, Major7),
+                (5, Dominant7),
+                (6, Minor7),
+                (7, HalfDiminished7),
+            ]
+        );
+        assert_eq!(
+            scale(9, ScaleType::HarmonicMinor).seventh_chords(),
+            [
+                (1, MinorMajor7),
+                (2, HalfDiminished7),
+                (3, AugmentedMajor7),
+                (4, Minor7),
+                (5, Dominant7),
+                (6, Major7),
+                (7, Diminished7),
+            ]
+        );
+        // every other degree of pentatonic scale doesn't form seventh chords (C-E-A-D)
+        assert!(scale(0, ScaleType::MajorPentatonic)
+            .seventh_chords()
+            .is_empty());
+        for scale_type in [
+            ScaleType::Major,
+            ScaleType::HarmonicMinor,
+            ScaleType::Dorian,
+        ] {
+            let scale = scale(2, scale_type);
+            assert!(scale.seventh_chords_iter().eq(scale.seventh_chords()));
+        }
+    }
+
+    #[test]
+    fn diatonic_notes_use_consecutive_letters() {
+        let names = |root, scale_type| -> Option<Vec<String>> {
+            let notes = scale(root, scale_type).diatonic_notes(Octave::Fifth)?;
+            Some(notes.iter().map(ToString::to_string).collect())
+        };
+        assert_eq!(
+            names(5, ScaleType::Major).unwrap(),
+            ["F₄", "G₄", "A₄", "B♭₄", "C₅", "D₅", "E₅"]
+        );
+        assert_eq!(
+            names(3, ScaleType::Major).unwrap(),
+            ["E♭₄", "F₄", "G₄", "A♭₄", "B♭₄", "C₅", "D₅"]
+        );
+        // tie between F♯ major and G♭ major goes to sharps
+        assert_eq!(
+            names(6, ScaleType::Major).unwrap(),
+            ["F♯₄", "G♯₄", "A♯₄", "B₄", "C♯₅", "D♯₅", "E♯₅"]
+        );
+        assert_eq!(names(0, ScaleType::MajorPentatonic), None);
+        assert_eq!(
+            scale(11, ScaleType::Major).diatonic_notes(Octave::Tenth),
+            None
+        );
+    }
+
+    #[test]
+    fn modes_of_melodies() {
+        // G4 A4 B4 C5 D5 E5 F5 G5 - flat seventh over G
+        let mixolydian = melody(&[55, 57, 59, 60, 62, 64, 65, 67]);
+        assert_eq!(
+            detect_mode(&mixolydian, PITCHES[55]),
+            Some(ScaleType::Mixolydian)
+        );
+        // tonic in other octave
+        assert_eq!(
+            detect_mode(&mixolydian, PITCHES[31]),
+            Some(ScaleType::Mixolydian)
+        );
+        // D4 F4 E4 G4 A4 B4 C5 D5
+        let dorian = melody(&[50, 53, 52, 55, 57, 59, 60, 62]);
+        assert_eq!(detect_mode(&dorian, PITCHES[50]), Some(ScaleType::Dorian));
+        // E4 F4 G4 E4 - flat second over E
+        assert_eq!(
+            detect_mode(&melody(&[52, 53, 55, 52]), PITCHES[52]),
+            Some(ScaleType::Phrygian)
+        );
+        // C4 D4 G4 A4 C5 - without third, tie goes to major
+        assert_eq!(
+            detect_mode(&melody(&[48, 50, 55, 57, 60]), PITCHES[48]),
+            Some(ScaleType::Major)
+        );
+        // repeated pitches count - F5 twice outweighs single F♯5
+        let mixolydian = melody(&[55, 57, 59, 60, 62, 65, 64, 65, 66, 67]);
+        assert_eq!(
+            detect_mode(&mixolydian, PITCHES[55]),
+            Some(ScaleType::Mixolydian)
+        );
+        assert_eq!(detect_mode(&[], PITCHES[55]), None);
+    }
+}
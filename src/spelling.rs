@@ -0,0 +1,191 @@
+//! Spelling pitches as notes.
+
+use std::sync::OnceLock;
+
+use crate::{Accidental, KeySignature, Note, Pitch, FREQUENCIES, PITCHES};
+
+/// Policy of choosing note spelling a pitch.
+///
+/// Policy is a small immutable [Copy] value, safe to share between threads.
+/// Note that [SpellingPolicy::spell] allocates - in real-time threads
+/// use [warmed up](SpellingCache::warm_up) [SpellingCache] instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SpellingPolicy {
+    /// Always use sharps for black keys (same as converting [Pitch] to [Note]).
+    #[default]
+    Sharps,
+    /// Always use flats for black keys.
+    Flats,
+    /// Use spelling belonging to the key with given signature if there is one,
+    /// otherwise sharps in keys with sharps (or no accidentals) and flats in keys with flats.
+    ForKey(KeySignature),
+}
+
+impl SpellingPolicy {
+    /// Spell pitch according to the policy.
+    ///
+    /// Spellings that can't be represented (like E♯) are never chosen,
+    /// natural spelling is used instead.
+    #[must_use]
+    pub fn spell(&self, pitch: Pitch) -> Note {
+        let spellings = pitch.spellings();
+        let with_accidental = |accidental: Accidental| {
+            spellings
+                .iter()
+                .find(|note| note.accidental() == accidental && note.octave() == pitch.octave())
+                .or_else(|| {
+                    spellings
+                        .iter()
+                        .find(|note| note.accidental() == accidental)
+                })
+                .copied()
+        };
+        let preferred = match self {
+            SpellingPolicy::Sharps => with_accidental(Accidental::Sharp),
+            SpellingPolicy::Flats => with_accidental(Accidental::Flat),
+            SpellingPolicy::ForKey(key) => spellings
+                .iter()
+                .find(|note| key.accidental(note.letter()) == note.accidental())
+                .copied()
+                .or_else(|| {
+                    if key.fifths() >= 0 {
+                        with_accidental(Accidental::Sharp)
+                    } else {
+                        with_accidental(Accidental::Flat)
+                    }
+                }),
+        };
+        preferred.unwrap_or_else(|| Note::from(pitch))
+    }
+}
+
+/// Cache of [spellings](SpellingPolicy::spell) and note names of pitches,
+/// filled lazily as pitches are looked up.
+///
+/// Every entry is a [OnceLock], so cache is [Sync] - it can be shared
+/// between threads, each entry being computed at most once.
+/// Computing an entry allocates (and blocks other threads asking for it), but once
+/// it's computed, reading it neither allocates nor locks - so after
+/// [warm_up](SpellingCache::warm_up) cache is safe to use in real-time threads.
+#[derive(Debug)]
+pub struct SpellingCache {
+    policy: SpellingPolicy,
+    entries: [OnceLock<Note>; FREQUENCIES.len()],
+}
+
+impl SpellingCache {
+    /// Create new empty cache.
+    #[must_use]
+    pub fn new(policy: SpellingPolicy) -> Self {
+        SpellingCache {
+            policy,
+            entries: std::array::from_fn(|_| OnceLock::new()),
+        }
+    }
+
+    /// Get spelling policy.
+    #[must_use]
+    pub fn policy(&self) -> SpellingPolicy {
+        self.policy
+    }
+
+    /// Compute all entries of the cache in advance.
+    pub fn warm_up(&self) {
+        for pitch in PITCHES.iter() {
+            let _ = self.get(*pitch);
+        }
+    }
+
+    /// Spell pitch - same as [SpellingPolicy::spell].
+    #[must_use]
+    pub fn get(&self, pitch: Pitch) -> Note {
+        *self.entries[pitch.index() as usize].get_or_init(|| self.policy.spell(pitch))
+    }
+
+    /// Name of note spelling pitch - same as its [Display](std::fmt::Display) format.
+    #[must_use]
+    pub fn name(&self, pitch: Pitch) -> &'static str {
+        self.get(pitch).unicode_name()
+    }
+}
+
+/// Spell passage of pitches following melodic direction - black keys are spelled
+/// with sharps when melody ascends from them and with flats when it descends
+/// (for example: C-C♯-D-D♯-E ascending and E-E♭-D-D♭-C descending).
+///
+/// Direction is given by the next different pitch (or, at the end of passage,
+/// by the previous one), so repeated pitches keep their spelling.
+/// Passage without any melodic motion is spelled with sharps.
+#[must_use]
+pub fn spell_passage(pitches: &[Pitch]) -> Vec<Note> {
+    pitches
+        .iter()
+        .enumerate()
+        .map(|(i, pitch)| {
+            let next = pitches[i..].iter().find(|next| *next != pitch);
+            let previous = pitches[..i]
+                .iter()
+                .rev()
+                .find(|previous| *previous != pitch);
+            let descending = match (next, previous) {
+                (Some(next), _) => next < pitch,
+                (None, Some(previous)) => previous > pitch,
+                (None, None) => false,
+            };
+            if descending {
+                SpellingPolicy::Flats.spell(*pitch)
+            } else {
+                SpellingPolicy::Sharps.spell(*pitch)
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spelled(indices: &[usize]) -> Vec<String> {
+        let pitches: Vec<Pitch> = indices.iter().map(|index| PITCHES[*index]).collect();
+        spell_passage(&pitches)
+            .iter()
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn chromatic_runs_follow_direction() {
+        assert_eq!(
+            spelled(&[48, 49, 50, 51, 52]),
+            ["C₄", "C♯₄", "D₄", "D♯₄", "E₄"]
+        );
+        assert_eq!(
+            spelled(&[52, 51, 50, 49, 48]),
+            ["E₄", "E♭₄", "D₄", "D♭₄", "C₄"]
+        );
+        // turning point and repeated pitches
+        assert_eq!(
+            spelled(&[56, 58, 58, 56, 54, 54]),
+            ["G♯₄", "B♭₄", "B♭₄", "A♭₄", "G♭₄", "G♭₄"]
+        );
+        assert_eq!(spelled(&[49, 49]), ["C♯₄", "C♯₄"]);
+        assert!(spell_passage(&[]).is_empty());
+    }
+
+    #[test]
+    fn warmed_up_cache_is_shared_between_threads() {
+        let key = KeySignature::new(-3).unwrap();
+        let cache = SpellingCache::new(SpellingPolicy::ForKey(key));
+        cache.warm_up();
+        assert!(cache.entries.iter().all(|entry| entry.get().is_some()));
+        std::thread::scope(|scope| {
+            for _ in 0..4 {
+                scope.spawn(|| {
+                    for pitch in PITCHES.iter() {
+                        assert_eq!(cache.get(*pitch), cache.policy().spell(*pitch));
+                    }
+                });
+            }
+        });
+    }
+}
@@ -0,0 +1,359 @@
+//! Aligned text tables of scales and chords.
+
+use std::fmt::Display;
+
+use crate::{Chord, ChromaticIndex, NamedInterval, Note, NoteStyle, Octave, Scale};
+
+/// Columns displayed by [ScaleTable] and [ChordTable].
+///
+/// First column (scale degree or chord tone) is always displayed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TableOptions {
+    /// Note name (in [ASCII](NoteStyle::Ascii) style).
+    pub note: bool,
+    /// Frequency in Hz.
+    pub frequency: bool,
+    /// MIDI note number.
+    pub midi: bool,
+    /// Deviation (in cents) of equal-tempered interval above root
+    /// from the [just](NamedInterval::just_ratio) one.
+    pub just_deviation: bool,
+}
+
+impl Default for TableOptions {
+    fn default() -> Self {
+        TableOptions {
+            note: true,
+            frequency: true,
+            midi: true,
+            just_deviation: true,
+        }
+    }
+}
+
+/// Table of scale degrees, implementing [Display].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScaleTable {
+    rows: Vec<Row>,
+    options: TableOptions,
+}
+
+impl ScaleTable {
+    /// Create table of scale starting in 4th octave (C₄ - B₄).
+    #[must_use]
+    pub fn new(scale: &Scale, options: TableOptions) -> Self {
+        ScaleTable::with_octave(scale, Octave::Fifth, options)
+    }
+
+    /// Create table of scale starting in given octave.
+    ///
+    /// Degrees of heptatonic scales are spelled with consecutive letters
+    /// (see [Scale::diatonic_notes], for example: F major has B♭).
+    /// Degrees of other scales (and of heptatonic scales that can't be spelled
+    /// that way) are spelled with sharps.
+    #[must_use]
+    pub fn with_octave(scale: &Scale, octave: Octave, options: TableOptions) -> Self {
+        let root = ChromaticIndex(u8::from(octave) as i16 * 12 + scale.root().number() as i16);
+        let semitones: Vec<u8> = scale
+            .steps()
+            .iter()
+            .scan(0, |semitones, step| {
+                let current = *semitones;
+                *semitones += step;
+                Some(current)
+            })
+            .collect();
+        let notes: Vec<Option<Note>> = match scale.diatonic_notes(octave) {
+            Some(notes) => notes.into_iter().map(Some).collect(),
+            None => semitones
+                .iter()
+                .map(|semitones| root.transpose(*semitones as i16).try_into().ok())
+                .collect(),
+        };
+        let rows = semitones
+            .into_iter()
+            .zip(notes)
+            .enumerate()
+            .map(|(degree, (semitones, note))| Row {
+                label: (degree + 1).to_string(),
+                note,
+                semitones,
+            })
+            .collect();
+        ScaleTable { rows, options }
+    }
+}
+
+impl Display for ScaleTable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write_table(f, "Degree", &self.rows, self.options)
+    }
+}
+
+/// Table of chord tones, implementing [Display].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChordTable {
+    rows: Vec<Row>,
+    options: TableOptions,
+}
+
+impl ChordTable {
+    /// Create table of chord with root moved to given octave.
+    #[must_use]
+    pub fn new(chord: &Chord, octave: Octave, options: TableOptions) -> Self {
+        let root = chord.root();
+        let root = Note::new_unchecked_spelling(root.letter(), octave, root.accidental());
+        let chord = Chord::from_intervals(root, chord.root_position_intervals());
+        let notes = chord.notes();
+        let rows = std::iter::once(("R".to_string(), 0))
+            .chain(
+                chord
+                    .root_position_intervals()
+                    .iter()
+                    .map(|interval| (interval.to_string(), interval.semitones())),
+            )
+            .enumerate()
+            .map(|(i, (label, semitones))| Row {
+                label,
+                note: notes.as_ref().ok().map(|notes| notes[i]),
+                semitones,
+            })
+            .collect();
+        ChordTable { rows, options }
+    }
+}
+
+impl Display for ChordTable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write_table(f, "Tone", &self.rows, self.options)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Row {
+    label: String,
+    note: Option<Note>,
+    semitones: u8,
+}
+
+fn write_table(
+    f: &mut std::fmt::Formatter<'_>,
+    label_header: &str,
+    rows: &[Row],
+    options: TableOptions,
+) -> std::fmt::Result {
+    // (cells, is right-aligned)
+    let mut columns: Vec<(Vec<String>, bool)> = vec![(
+        std::iter::once(label_header.to_string())
+            .chain(rows.iter().map(|row| row.label.clone()))
+            .collect(),
+        false,
+    )];
+
+    let pitches: Vec<_> = rows
+        .iter()
+        .map(|row| {
+            row.note
+                .and_then(|note| note.chromatic_index().try_into_pitch().ok())
+        })
+        .collect();
+    if options.note {
+        let cells = rows.iter().map(|row| match row.note {
+            Some(note) => note.format_in(NoteStyle::Ascii).into_owned(),
+            None => "-".to_string(),
+        });
+        columns.push((column("Note", cells), false));
+    }
+    if options.frequency {
+        let cells = pitches.iter().map(|pitch| match pitch {
+            Some(pitch) => format_frequency(pitch.frequency()),
+            None => "-".to_string(),
+        });
+        columns.push((column("Frequency", cells), true));
+    }
+    if options.midi {
+        let cells = pitches.iter().map(|pitch| match pitch {
+            Some(pitch) => pitch.midi_number().to_string(),
+            None => "-".to_string(),
+        });
+        columns.push((column("MIDI", cells), true));
+    }
+    if options.just_deviation {
+        let cells = rows
+            .iter()
+            .map(|row| format!("{:+.1}", just_deviation(row.semitones)));
+        columns.push((column("Just", cells), true));
+    }
+
+    let widths: Vec<usize> = columns
+        .iter()
+        .map(|(cells, _)| {
+            cells
+                .iter()
+                .map(|cell| cell.chars().count())
+                .max()
+                .expect("column has a header")
+        })
+        .collect();
+    for row in 0..=rows.len() {
+        let mut line = String::new();
+        for ((cells, right_aligned), width) in columns.iter().zip(&widths) {
+            if !line.is_empty() {
+                line.push_str("  ");
+            }
+            if *right_aligned {
+                line.push_str(&format!("{:>width$}", cells[row], width = width));
+            } else {
+                line.push_str(&format!("{:<width$}", cells[row], width = width));
+            }
+        }
+        writeln!(f, "{}", line.trim_end())?;
+    }
+    Ok(())
+}
+
+fn column(header: &str, cells: impl Iterator<Item = String>) -> Vec<String> {
+    std::iter::once(header.to_string()).chain(cells).collect()
+}
+
+/// Frequency with up to 2 decimal places, without trailing zeros.
+fn format_frequency(frequency: f64) -> String {
+    let formatted = format!("{:.2}", frequency);
+    formatted
+        .trim_end_matches('0')
+        .trim_end_matches('.')
+        .to_string()
+}
+
+/// Deviation (in cents) of equal-tempered interval from the just one.
+fn just_deviation(semitones: u8) -> f64 {
+    let adjustment = NamedInterval::from_semitones(semitones % 12)
+        .expect("invariant")
+        .just_adjustment()
+        .value();
+    // subtraction rather than negation, so unison is +0 (not -0)
+    0.0 - adjustment
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Accidental, ChordQuality, Letter, PitchClass, ScaleType};
+
+    #[test]
+    fn scale_table() {
+        let scale = Scale::new(PitchClass::new(7), ScaleType::Major);
+        assert_eq!(
+            ScaleTable::new(&scale, TableOptions::default()).to_string(),
+            "\
+Degree  Note  Frequency  MIDI   Just
+1       G4          392    67   +0.0
+2       A4          440    69   -3.9
+3       B4       493.88    71  +13.7
+4       C5       523.25    72   +2.0
+5       D5       587.33    74   -2.0
+6       E5       659.26    76  +15.6
+7       F#5      739.99    78  +11.7
+"
+        );
+        let options = TableOptions {
+            note: true,
+            frequency: false,
+            midi: true,
+            just_deviation: false,
+        };
+        assert_eq!(
+            ScaleTable::with_octave(&scale, Octave::First, options).to_string(),
+            "\
+Degree  Note  MIDI
+1       G0      19
+2       A0      21
+3       B0      23
+4       C1      24
+5       D1      26
+6       E1      28
+7       F#1     30
+"
+        );
+    }
+
+    #[test]
+    fn scale_tables_with_flats() {
+        let options = TableOptions {
+            note: true,
+            frequency: false,
+            midi: true,
+            just_deviation: false,
+        };
+        let table = |root, scale_type| {
+            ScaleTable::new(&Scale::new(PitchClass::new(root), scale_type), options).to_string()
+        };
+        assert_eq!(
+            table(5, ScaleType::Major),
+            "\
+Degree  Note  MIDI
+1       F4      65
+2       G4      67
+3       A4      69
+4       Bb4     70
+5       C5      72
+6       D5      74
+7       E5      76
+"
+        );
+        assert_eq!(
+            table(3, ScaleType::Major),
+            "\
+Degree  Note  MIDI
+1       Eb4     63
+2       F4      65
+3       G4      67
+4       Ab4     68
+5       Bb4     70
+6       C5      72
+7       D5      74
+"
+        );
+        // B♭ minor rather than A♯ minor
+        assert_eq!(
+            table(10, ScaleType::NaturalMinor),
+            "\
+Degree  Note  MIDI
+1       Bb4     70
+2       C5      72
+3       Db5     73
+4       Eb5     75
+5       F5      77
+6       Gb5     78
+7       Ab5     80
+"
+        );
+        // the same number of sharps and flats
+        assert!(table(6, ScaleType::Major).contains("E#5"));
+    }
+
+    #[test]
+    fn chord_table() {
+        let root = Note::new(Letter::A, Octave::First, Accidental::None).unwrap();
+        let chord = Chord::new(root, ChordQuality::Dominant7);
+        assert_eq!(
+            ChordTable::new(&chord, Octave::Ninth, TableOptions::default()).to_string(),
+            "\
+Tone  Note  Frequency  MIDI   Just
+R     A8         7040   117   +0.0
+M3    C#9           -     -  +13.7
+P5    E9            -     -   -2.0
+m7    G9            -     -  -17.6
+"
+        );
+    }
+
+    #[test]
+    fn just_deviation_matches_just_ratios() {
+        // major third is 13.7 cents wider than 5/4, perfect fifth is 2 cents narrower than 3/2
+        assert_eq!(format!("{:.1}", just_deviation(4)), "13.7");
+        assert_eq!(format!("{:.1}", just_deviation(7)), "-2.0");
+        assert_eq!(format!("{:+.1}", just_deviation(0)), "+0.0");
+        assert_eq!(format!("{:+.1}", just_deviation(12)), "+0.0");
+    }
+}
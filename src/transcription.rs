@@ -0,0 +1,291 @@
+//! Conversion of continuous frequency contours into pitches.
+
+use crate::{Cents, Interval, Note, Pitch, PITCHES};
+
+/// Convert sequence of frequencies (for example: output of pitch detector) into sequence of pitches.
+///
+/// Reported pitch changes only when frequency moves more than `hysteresis_cents`
+/// away from the current pitch, so small wobbles don't cause spurious pitch changes.
+/// New pitch is then the one [nearest](Pitch::from_frequency) to the frequency.
+///
+/// Frequencies that don't correspond to any pitch are skipped.
+#[must_use]
+pub fn transcribe(freqs: &[f64], hysteresis_cents: f64) -> Vec<Pitch> {
+    let mut pitches: Vec<Pitch> = Vec::with_capacity(freqs.len());
+    for &frequency in freqs {
+        let nearest = match Pitch::from_frequency(frequency) {
+            Some(pitch) => pitch,
+            None => continue,
+        };
+
+        let pitch = match pitches.last() {
+            Some(&current)
+                if Interval::new(current.frequency(), frequency)
+                    .cents()
+                    .abs()
+                    .value()
+                    <= hysteresis_cents =>
+            {
+                current
+            }
+            _ => nearest,
+        };
+        pitches.push(pitch);
+    }
+    pitches
+}
+
+/// Get pitch [nearest](Pitch::from_frequency) to given frequency, but only if frequency
+/// is within `tolerance_cents` of it (for example: to skip out-of-tune detections).
+#[must_use]
+pub fn pitch_if_close(frequency: f64, tolerance_cents: f64) -> Option<Pitch> {
+    Pitch::from_frequency(frequency)
+        .filter(|pitch| pitch.matches_frequency(frequency, tolerance_cents))
+}
+
+/// All note spellings of pitches within `tolerance_cents` from given frequency.
+///
+/// Spellings of closer pitches come first.
+#[must_use]
+pub fn spellings_near(frequency: f64, tolerance_cents: f64) -> Vec<Note> {
+    if !(frequency.is_finite() && frequency > 0.0) {
+        return vec![];
+    }
+
+    let mut pitches: Vec<(f64, Pitch)> = PITCHES
+        .iter()
+        .map(|pitch| {
+            let cents = Interval::new(pitch.frequency(), frequency).cents().abs();
+            (cents.value(), *pitch)
+        })
+        .filter(|(cents, _)| *cents <= tolerance_cents)
+        .collect();
+    pitches.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+    pitches
+        .into_iter()
+        .flat_map(|(_, pitch)| pitch.spellings())
+        .collect()
+}
+
+/// Correct isolated octave errors of pitch detector in track of frequencies
+/// (`None` meaning unvoiced frame).
+///
+/// Frame is corrected (its frequency doubled or halved) when it's not within `max_jump`
+/// of its voiced immediate neighbours, but would be within `max_jump` of all of them after correction.
+/// Frames are processed in order, so previous neighbour is already corrected.
+/// Frames without voiced neighbours are never corrected, and neither are runs
+/// of consecutive octave errors (apart from their first and last frames when they're
+/// at the edge of a voiced region), as they're indistinguishable from genuine octave leaps.
+/// Likewise, frame at the edge of a voiced region has only one neighbour to be compared with,
+/// so when that neighbour is an octave error, it's the edge frame that gets corrected.
+///
+/// Frequencies that aren't positive and finite are treated as unvoiced.
+pub fn correct_octave_errors(track: &mut [Option<f64>], max_jump: Cents) {
+    let voiced = |frequency: Option<f64>| frequency.filter(|f| f.is_finite() && *f > 0.0);
+    for i in 0..track.len() {
+        let frequency = match voiced(track[i]) {
+            Some(frequency) => frequency,
+            None => continue,
+        };
+        let previous = i
+            .checked_sub(1)
+            .and_then(|previous| voiced(track[previous]));
+        let next = track.get(i + 1).and_then(|next| voiced(*next));
+        let neighbours: Vec<f64> = previous.into_iter().chain(next).collect();
+        if neighbours.is_empty() {
+            continue;
+        }
+
+        let fits = |frequency: f64| {
+            neighbours.iter().all(|neighbour| {
+                Interval::new(*neighbour, frequency).cents().abs() <= max_jump.abs()
+            })
+        };
+        if fits(frequency) {
+            continue;
+        }
+        if let Some(corrected) = [frequency * 2.0, frequency / 2.0]
+            .into_iter()
+            .find(|corrected| fits(*corrected))
+        {
+            track[i] = Some(corrected);
+        }
+    }
+}
+
+/// Resample track of `(time in seconds, frequency)` points (`None` meaning unvoiced point),
+/// ordered by time, to points evenly spaced with given rate (points per second),
+/// starting at time of the first point and not exceeding time of the last one.
+///
+/// Between two voiced points frequency is interpolated linearly in cents (geometrically in Hz),
+/// new points falling between points of which any is unvoiced are unvoiced.
+/// Returns empty track if `new_rate_hz` or time of the first or the last point isn't finite
+/// (or `new_rate_hz` isn't positive).
+#[must_use]
+pub fn resample_track(track: &[(f64, Option<f64>)], new_rate_hz: f64) -> Vec<(f64, Option<f64>)> {
+    let (start, end) = match (track.first(), track.last()) {
+        (Some(first), Some(last))
+            if new_rate_hz.is_finite()
+                && new_rate_hz > 0.0
+                && first.0.is_finite()
+                && last.0.is_finite() =>
+        {
+            (first.0, last.0)
+        }
+        _ => return vec![],
+    };
+
+    let mut resampled = vec![];
+    let mut time = start;
+    while time <= end {
+        let after = track.partition_point(|(point_time, _)| *point_time <= time);
+        let (before_time, before) = track[after.max(1) - 1];
+        let frequency = if before_time == time {
+            before
+        } else {
+            match (before, track.get(after)) {
+                (Some(before), Some((after_time, Some(after)))) => {
+                    let progress = (time - before_time) / (after_time - before_time);
+                    Some(before * (after / before).powf(progress))
+                }
+                _ => None,
+            }
+        };
+        resampled.push((time, frequency));
+        time = start + resampled.len() as f64 / new_rate_hz;
+    }
+    resampled
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detuned(pitch: Pitch, cents: f64) -> f64 {
+        pitch.frequency() * 2.0_f64.powf(cents / 1200.0)
+    }
+
+    #[test]
+    fn wobbling_frequency_stays_on_one_pitch() {
+        let a4 = PITCHES[57];
+        // wobble crosses half a semitone, where nearest pitch changes
+        let wobble: Vec<f64> = [0.0, 30.0, 60.0, -20.0, -60.0, 55.0, 0.0]
+            .into_iter()
+            .map(|cents| detuned(a4, cents))
+            .collect();
+        assert_eq!(transcribe(&wobble, 70.0), vec![a4; wobble.len()]);
+        assert_ne!(transcribe(&wobble, 10.0), vec![a4; wobble.len()]);
+    }
+
+    #[test]
+    fn genuine_change_switches_pitch() {
+        let (a4, b4) = (PITCHES[57], PITCHES[59]);
+        let contour = [
+            detuned(a4, 0.0),
+            detuned(a4, 40.0),
+            detuned(b4, -10.0),
+            detuned(b4, 30.0),
+        ];
+        assert_eq!(transcribe(&contour, 50.0), vec![a4, a4, b4, b4]);
+        assert_eq!(transcribe(&[], 50.0), vec![]);
+    }
+
+    #[test]
+    fn spellings_near_c_sharp_4() {
+        let names =
+            |notes: Vec<Note>| -> Vec<String> { notes.iter().map(ToString::to_string).collect() };
+        assert_eq!(names(spellings_near(277.5, 10.0)), ["C♯₄", "D♭₄"]);
+        // between C₄ and C♯₄, closer to C♯₄
+        let between = detuned(PITCHES[48], 60.0);
+        assert_eq!(names(spellings_near(between, 61.0)), ["C♯₄", "D♭₄", "C₄"]);
+        assert!(spellings_near(detuned(PITCHES[49], 30.0), 20.0).is_empty());
+        assert!(spellings_near(0.0, 50.0).is_empty());
+    }
+
+    #[test]
+    fn planted_octave_errors_are_corrected() {
+        let max_jump = Cents::new(100.0).unwrap();
+        let mut track = [
+            Some(220.0),
+            Some(221.0),
+            Some(442.0),
+            Some(222.0),
+            None,
+            Some(223.0),
+            Some(223.5),
+            Some(111.5),
+            Some(224.0),
+            Some(448.0),
+        ];
+        correct_octave_errors(&mut track, max_jump);
+        assert_eq!(
+            track,
+            [220.0, 221.0, 221.0, 222.0, 0.0, 223.0, 223.5, 223.0, 224.0, 224.0]
+                .map(|frequency| Some(frequency).filter(|f| *f > 0.0))
+        );
+
+        // runs of errors, genuine leaps and isolated frames stay
+        for track in [
+            [220.0, 221.0, 440.0, 441.0, 222.0, 223.0].map(Some),
+            [220.0, 220.0, 220.0, 440.0, 440.0, 440.0].map(Some),
+            [None, Some(440.0), None, Some(220.0), None, None],
+            [220.0, 221.0, -440.0, f64::INFINITY, 222.0, 223.0].map(Some),
+        ] {
+            let mut corrected = track;
+            correct_octave_errors(&mut corrected, max_jump);
+            assert_eq!(corrected, track);
+        }
+    }
+
+    #[test]
+    fn resampling_keeps_gaps() {
+        let track = [
+            (0.0, Some(200.0)),
+            (1.0, Some(800.0)),
+            (2.0, None),
+            (3.0, Some(100.0)),
+        ];
+        let resampled = resample_track(&track, 2.0);
+        let times: Vec<f64> = resampled.iter().map(|(time, _)| *time).collect();
+        assert_eq!(times, [0.0, 0.5, 1.0, 1.5, 2.0, 2.5, 3.0]);
+        let frequencies: Vec<Option<f64>> = resampled.iter().map(|(_, f)| *f).collect();
+        // geometric midpoint of 200 Hz and 800 Hz is 400 Hz, not 500 Hz
+        assert_eq!(
+            frequencies,
+            [
+                Some(200.0),
+                Some(400.0),
+                Some(800.0),
+                None,
+                None,
+                None,
+                Some(100.0)
+            ]
+        );
+
+        let resampled = resample_track(&track[..2], 3.0);
+        assert_eq!(resampled.len(), 4);
+        let third = resampled[1].1.unwrap();
+        assert!((third - 200.0 * 4.0_f64.powf(1.0 / 3.0)).abs() < 1e-9);
+
+        assert_eq!(resample_track(&track, 0.0), []);
+        assert_eq!(resample_track(&track, f64::NAN), []);
+        assert_eq!(resample_track(&[], 2.0), []);
+        let unbounded = [(0.0, Some(440.0)), (f64::INFINITY, Some(440.0))];
+        assert_eq!(resample_track(&unbounded, 2.0), []);
+        let unbounded = [(f64::NEG_INFINITY, Some(440.0)), (1.0, Some(440.0))];
+        assert_eq!(resample_track(&unbounded, 2.0), []);
+        assert_eq!(resample_track(&[(f64::NAN, None)], 2.0), []);
+    }
+
+    #[test]
+    fn pitch_only_if_close_enough() {
+        let a4 = PITCHES[57];
+        assert_eq!(pitch_if_close(440.0, 5.0), Some(a4));
+        assert_eq!(pitch_if_close(detuned(a4, 15.0), 20.0), Some(a4));
+        assert_eq!(pitch_if_close(detuned(a4, -15.0), 20.0), Some(a4));
+        assert_eq!(pitch_if_close(detuned(a4, 30.0), 20.0), None);
+        assert_eq!(pitch_if_close(detuned(a4, -45.0), 20.0), None);
+        assert_eq!(pitch_if_close(1.0, 50.0), None);
+    }
+}
@@ -0,0 +1,221 @@
+//! Building blocks for live tuner displays.
+
+use crate::{Cents, Interval, Pitch};
+
+/// Single reading of a tuner.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TunerReading {
+    /// Pitch tuner is locked to.
+    pub pitch: Pitch,
+    /// Measured frequency.
+    pub frequency: f64,
+    /// Deviation of measured frequency from pitch.
+    pub cents: Cents,
+}
+
+impl TunerReading {
+    /// Create new reading of `frequency` relative to `pitch`.
+    ///
+    /// Frequency must be positive, otherwise may panic.
+    #[must_use]
+    #[track_caller]
+    pub fn new(pitch: Pitch, frequency: f64) -> Self {
+        TunerReading {
+            pitch,
+            frequency,
+            cents: Interval::new(pitch.frequency(), frequency).cents(),
+        }
+    }
+}
+
+/// Options of [PitchTracker].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PitchTrackerOptions {
+    /// Deviation (in cents) from current pitch that has to be exceeded
+    /// before tracker switches to another pitch.
+    ///
+    /// Default: 60 cents.
+    pub hysteresis_cents: f64,
+    /// Number of updates without signal during which last reading is still reported.
+    ///
+    /// Default: 5 updates.
+    pub hold_updates: u32,
+}
+
+impl Default for PitchTrackerOptions {
+    fn default() -> Self {
+        PitchTrackerOptions {
+            hysteresis_cents: 60.0,
+            hold_updates: 5,
+        }
+    }
+}
+
+/// Stateful pitch tracker turning stream of measured frequencies into stable tuner readings.
+///
+/// Once locked to a pitch, tracker stays on it as long as measured frequency
+/// deviates from it by no more than [hysteresis](PitchTrackerOptions::hysteresis_cents),
+/// so input sitting near the boundary between two pitches doesn't flicker between them.
+/// When deviation exceeds it, tracker immediately switches to the pitch
+/// [nearest](Pitch::from_frequency) to measured frequency - no matter how far it is,
+/// so genuine leaps (like octave jumps) are followed right away.
+///
+/// When signal disappears (`None`, or frequency not corresponding to any pitch)
+/// last reading is reported for [hold](PitchTrackerOptions::hold_updates) updates,
+/// after that tracker reports `None` and gets [reset](PitchTracker::reset).
+#[derive(Debug, Clone)]
+pub struct PitchTracker {
+    options: PitchTrackerOptions,
+    last_reading: Option<TunerReading>,
+    updates_without_signal: u32,
+}
+
+impl PitchTracker {
+    /// Create new tracker.
+    #[must_use]
+    pub fn new(options: PitchTrackerOptions) -> Self {
+        PitchTracker {
+            options,
+            last_reading: None,
+            updates_without_signal: 0,
+        }
+    }
+
+    /// Get tracker options.
+    #[must_use]
+    pub fn options(&self) -> PitchTrackerOptions {
+        self.options
+    }
+
+    /// Feed next measured frequency (or `None` if no signal was detected) to the tracker.
+    pub fn update(&mut self, frequency: Option<f64>) -> Option<TunerReading> {
+        let nearest = frequency
+            .and_then(|frequency| Pitch::from_frequency(frequency).map(|pitch| (pitch, frequency)));
+        let (nearest, frequency) = match nearest {
+            Some(nearest) => nearest,
+            None => {
+                self.updates_without_signal += 1;
+                if self.updates_without_signal > self.options.hold_updates {
+                    self.reset();
+                }
+                return self.last_reading;
+            }
+        };
+
+        let pitch = match self.last_reading {
+            Some(reading)
+                if TunerReading::new(reading.pitch, frequency)
+                    .cents
+                    .abs()
+                    .value()
+                    <= self.options.hysteresis_cents =>
+            {
+                reading.pitch
+            }
+            _ => nearest,
+        };
+
+        let reading = TunerReading::new(pitch, frequency);
+        self.last_reading = Some(reading);
+        self.updates_without_signal = 0;
+        Some(reading)
+    }
+
+    /// Forget current pitch and last reading.
+    pub fn reset(&mut self) {
+        self.last_reading = None;
+        self.updates_without_signal = 0;
+    }
+}
+
+impl Default for PitchTracker {
+    fn default() -> Self {
+        PitchTracker::new(PitchTrackerOptions::default())
+    }
+}
+
+/// Beat rate (in Hz) heard when `measured` frequency sounds together with target pitch -
+/// difference between their frequencies (for example: 1 Hz for 441 Hz against A₄).
+#[must_use]
+pub fn unison_beat_rate(target: Pitch, measured: f64) -> f64 {
+    (measured - target.frequency()).abs()
+}
+
+/// Check if `measured` frequency beats against target pitch slowly enough to be considered in tune -
+/// its [beat rate](unison_beat_rate) doesn't exceed `max_beat_rate` (in Hz).
+#[must_use]
+pub fn is_beating_in_tune(target: Pitch, measured: f64, max_beat_rate: f64) -> bool {
+    unison_beat_rate(target, measured) <= max_beat_rate
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PITCHES;
+
+    fn detuned(pitch: Pitch, cents: f64) -> f64 {
+        pitch.frequency() * 2.0_f64.powf(cents / 1200.0)
+    }
+
+    fn pitches(tracker: &mut PitchTracker, frequencies: &[Option<f64>]) -> Vec<Option<Pitch>> {
+        frequencies
+            .iter()
+            .map(|frequency| tracker.update(*frequency).map(|reading| reading.pitch))
+            .collect()
+    }
+
+    #[test]
+    fn hysteresis_keeps_pitch_near_boundary() {
+        let (a4, a_sharp_4) = (PITCHES[57], PITCHES[58]);
+        let mut tracker = PitchTracker::default();
+        let frequencies =
+            [0.0, 48.0, 55.0, 45.0, 58.0, 70.0, 45.0, 30.0].map(|cents| Some(detuned(a4, cents)));
+        assert_eq!(
+            pitches(&mut tracker, &frequencies),
+            [a4, a4, a4, a4, a4, a_sharp_4, a_sharp_4, a4].map(Some)
+        );
+    }
+
+    #[test]
+    fn leaps_switch_immediately() {
+        let (a4, a5, e2) = (PITCHES[57], PITCHES[69], PITCHES[28]);
+        let mut tracker = PitchTracker::default();
+        let frequencies = [a4, a5, e2].map(|pitch| Some(pitch.frequency()));
+        assert_eq!(pitches(&mut tracker, &frequencies), [a4, a5, e2].map(Some));
+    }
+
+    #[test]
+    fn dropouts_are_held_then_reset() {
+        let a4 = PITCHES[57];
+        let mut tracker = PitchTracker::new(PitchTrackerOptions {
+            hysteresis_cents: 60.0,
+            hold_updates: 2,
+        });
+        let frequencies = [Some(440.0), None, Some(0.0), None, None];
+        assert_eq!(
+            pitches(&mut tracker, &frequencies),
+            [Some(a4), Some(a4), Some(a4), None, None]
+        );
+
+        // after reset, boundary frequency goes to the nearest pitch
+        let boundary = detuned(a4, 55.0);
+        tracker.update(Some(440.0));
+        tracker.reset();
+        assert_eq!(tracker.update(None), None);
+        assert_eq!(
+            tracker.update(Some(boundary)).map(|reading| reading.pitch),
+            Some(PITCHES[58])
+        );
+    }
+
+    #[test]
+    fn unison_one_hertz_off_beats_once_per_second() {
+        let a4 = PITCHES[57];
+        assert_eq!(unison_beat_rate(a4, 441.0), 1.0);
+        assert_eq!(unison_beat_rate(a4, 439.0), 1.0);
+        assert_eq!(unison_beat_rate(a4, 440.0), 0.0);
+        assert!(is_beating_in_tune(a4, 441.0, 1.0));
+        assert!(is_beating_in_tune(a4, 439.5, 1.0));
+        assert!(!is_beating_in_tune(a4, 441.0, 0.5));
+    }
+}
@@ -0,0 +1,810 @@
+//! Tuning systems.
+
+use crate::{Cents, ChromaticIndex, Error, Interval, Pitch, PitchClass, PITCHES};
+
+/// Equal-tempered tuning with given reference frequency of A₄.
+///
+/// Tuning is a small immutable [Copy] value, safe to share between threads.
+/// Its frequency queries neither allocate nor lock, so they can be used in real-time threads.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tuning {
+    a4: f64,
+}
+
+impl Tuning {
+    /// Create new tuning with given frequency of A₄.
+    ///
+    /// Frequency must be positive and finite.
+    pub fn new(a4: f64) -> Result<Self, Error> {
+        if !(a4.is_finite() && a4 > 0.0) {
+            return Err(Error::IncorrectFrequency);
+        }
+        Ok(Tuning { a4 })
+    }
+
+    /// Get frequency of A₄.
+    #[must_use]
+    pub fn a4(&self) -> f64 {
+        self.a4
+    }
+
+    /// Frequency of pitch in this tuning.
+    #[must_use]
+    pub fn frequency(&self, pitch: Pitch) -> f64 {
+        self.frequency_of_index(pitch.into())
+    }
+
+    /// Frequency of any position on chromatic scale (even outside of supported pitches) in this tuning.
+    #[must_use]
+    pub fn frequency_of_index(&self, index: ChromaticIndex) -> f64 {
+        self.a4 * 2.0_f64.powf((index.value() as f64 - 57.0) / 12.0)
+    }
+
+    /// Position of frequency in cents relative to A₄ of this tuning.
+    ///
+    /// Frequency must be positive, otherwise may panic.
+    #[must_use]
+    #[track_caller]
+    pub fn cents_from_a4(&self, frequency: f64) -> Cents {
+        Interval::new(self.a4, frequency).cents()
+    }
+}
+
+impl Default for Tuning {
+    /// Standard tuning, A₄ = 440 Hz.
+    fn default() -> Self {
+        Tuning { a4: 440.0 }
+    }
+}
+
+/// Tuning changing over time (for example: drifting during a historical recording) -
+/// frequency of A₄ interpolated between given points.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TuningCurve {
+    points: Vec<(f64, f64)>,
+}
+
+impl TuningCurve {
+    /// Create tuning curve from points `(time in seconds, frequency of A₄)`.
+    ///
+    /// Times must be finite and strictly increasing, frequencies positive and finite.
+    pub fn from_points(points: &[(f64, f64)]) -> Result<Self, Error> {
+        if points.is_empty()
+            || points.iter().any(|(time, _)| !time.is_finite())
+            || points.windows(2).any(|pair| pair[0].0 >= pair[1].0)
+        {
+            return Err(Error::IncorrectTuningCurve);
+        }
+        for (_, a4) in points {
+            Tuning::new(*a4)?;
+        }
+        Ok(TuningCurve {
+            points: points.to_vec(),
+        })
+    }
+
+    /// Get points of the curve.
+    #[must_use]
+    pub fn points(&self) -> &[(f64, f64)] {
+        &self.points
+    }
+
+    /// Frequency of A₄ at given time.
+    ///
+    /// Between points frequency changes linearly in cents (geometrically in Hz),
+    /// before the first and after the last point it stays constant.
+    #[must_use]
+    pub fn a4_at(&self, time: f64) -> f64 {
+        let after = self
+            .points
+            .partition_point(|(point_time, _)| *point_time <= time);
+        if after == 0 {
+            return self.points[0].1;
+        }
+        if after == self.points.len() {
+            return self.points[after - 1].1;
+        }
+
+        let (start_time, start_a4) = self.points[after - 1];
+        let (end_time, end_a4) = self.points[after];
+        let progress = (time - start_time) / (end_time - start_time);
+        start_a4 * (end_a4 / start_a4).powf(progress)
+    }
+
+    /// Tuning at given time.
+    #[must_use]
+    pub fn tuning_at(&self, time: f64) -> Tuning {
+        Tuning {
+            a4: self.a4_at(time),
+        }
+    }
+
+    /// Pitch nearest to frequency in tuning at given time, with deviation of frequency from it.
+    ///
+    /// Frequencies outside of supported range are matched with the lowest or the highest pitch.
+    /// Frequency must be positive, otherwise may panic.
+    #[must_use]
+    #[track_caller]
+    pub fn nearest_pitch_at(&self, time: f64, frequency: f64) -> (Pitch, Cents) {
+        let tuning = self.tuning_at(time);
+        let index = (57.0 + 12.0 * (frequency / tuning.a4).log2())
+            .round()
+            .clamp(0.0, (PITCHES.len() - 1) as f64);
+        let pitch = PITCHES[index as usize];
+        (
+            pitch,
+            Interval::new(tuning.frequency(pitch), frequency).cents(),
+        )
+    }
+}
+
+/// Pitch detuned by some number of cents.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Detuned {
+    pitch: Pitch,
+    cents: Cents,
+}
+
+impl Detuned {
+    /// Create new detuned pitch.
+    #[must_use]
+    pub fn new(pitch: Pitch, cents: Cents) -> Self {
+        Detuned { pitch, cents }
+    }
+
+    /// Get pitch.
+    #[must_use]
+    pub fn pitch(&self) -> Pitch {
+        self.pitch
+    }
+
+    /// Get deviation from pitch.
+    #[must_use]
+    pub fn cents(&self) -> Cents {
+        self.cents
+    }
+
+    /// Position of detuned pitch in cents relative to A₄ (for example: -850 for C₄ detuned by 50 cents).
+    #[must_use]
+    pub fn cents_from_a4(&self) -> Cents {
+        self.pitch.cents_from_a4() + self.cents
+    }
+
+    /// Frequency of detuned pitch (in standard tuning, A₄ = 440 Hz).
+    #[must_use]
+    pub fn frequency(&self) -> f64 {
+        self.frequency_in(&Tuning::default())
+    }
+
+    /// Frequency of detuned pitch in given tuning.
+    #[must_use]
+    pub fn frequency_in(&self, tuning: &Tuning) -> f64 {
+        tuning.frequency(self.pitch) * 2.0_f64.powf(self.cents.value() / 1200.0)
+    }
+}
+
+/// Temperament - way of tuning twelve pitch classes, expressed as deviations from equal temperament.
+pub trait Temperament {
+    /// Deviation from equal temperament of pitch class, when temperament is laid out starting from `tonic`.
+    ///
+    /// Offsets are the same in every octave.
+    fn offset(&self, class: PitchClass, tonic: PitchClass) -> Cents;
+
+    /// Frequency of pitch, when temperament is laid out starting from `tonic`
+    /// (in standard tuning, A₄ = 440 Hz).
+    fn frequency(&self, pitch: Pitch, tonic: PitchClass) -> f64 {
+        self.frequency_in(pitch, tonic, &Tuning::default())
+    }
+
+    /// Frequency of pitch in given tuning, when temperament is laid out starting from `tonic`.
+    fn frequency_in(&self, pitch: Pitch, tonic: PitchClass, tuning: &Tuning) -> f64 {
+        let offset = self.offset(pitch.class(), tonic);
+        tuning.frequency(pitch) * 2.0_f64.powf(offset.value() / 1200.0)
+    }
+}
+
+/// Temperament defined directly by deviations of pitch classes from equal temperament
+/// (for example: measured on a historical instrument).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CustomTemperament {
+    offsets: [Cents; 12],
+    reference: Pitch,
+}
+
+impl CustomTemperament {
+    /// Create temperament from offsets of pitch classes (`offsets[0]` for tonic,
+    /// `offsets[1]` for pitch class a semitone above it and so on).
+    ///
+    /// Offsets apply uniformly across octaves. Whole temperament is shifted
+    /// so that `reference` pitch class keeps its equal-tempered frequency.
+    ///
+    /// Offsets must be finite and within ±100 cents -
+    /// see [CustomTemperament::from_offsets_bounded] for other bounds.
+    pub fn from_offsets(offsets: [Cents; 12], reference: Pitch) -> Result<Self, Error> {
+        CustomTemperament::from_offsets_bounded(
+            offsets,
+            reference,
+            Cents::new(100.0).expect("100 is a number"),
+        )
+    }
+
+    /// Create temperament from offsets of pitch classes, with offsets limited to `max_offset` from zero.
+    ///
+    /// See [CustomTemperament::from_offsets].
+    pub fn from_offsets_bounded(
+        offsets: [Cents; 12],
+        reference: Pitch,
+        max_offset: Cents,
+    ) -> Result<Self, Error> {
+        if offsets
+            .iter()
+            .any(|offset| !offset.value().is_finite() || offset.abs() > max_offset.abs())
+        {
+            return Err(Error::IncorrectOffsets);
+        }
+        Ok(CustomTemperament { offsets, reference })
+    }
+
+    /// Get offsets of pitch classes (starting with tonic).
+    #[must_use]
+    pub fn offsets(&self) -> [Cents; 12] {
+        self.offsets
+    }
+
+    /// Get reference pitch.
+    #[must_use]
+    pub fn reference(&self) -> Pitch {
+        self.reference
+    }
+}
+
+impl Temperament for CustomTemperament {
+    fn offset(&self, class: PitchClass, tonic: PitchClass) -> Cents {
+        let relative = |class: PitchClass| {
+            let semitones =
+                (class.number() + PitchClass::COUNT - tonic.number()) % PitchClass::COUNT;
+            self.offsets[semitones as usize]
+        };
+        relative(class) - relative(self.reference.class())
+    }
+}
+
+/// Intonation corrections of a wind instrument's problem notes, by pitch class
+/// (for example: -15 cents for a note the instrument typically plays 15 cents sharp).
+///
+/// Pitch classes without correction are 0 cents. Corrections apply uniformly across octaves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WindAdjustment {
+    corrections: [Cents; 12],
+}
+
+impl WindAdjustment {
+    /// Create table from corrections of pitch classes (`corrections[0]` for C,
+    /// `corrections[1]` for C♯/D♭ and so on).
+    #[must_use]
+    pub fn new(corrections: [Cents; 12]) -> Self {
+        WindAdjustment { corrections }
+    }
+
+    /// Table with correction of pitch class replaced.
+    #[must_use]
+    pub fn with_correction(mut self, class: PitchClass, correction: Cents) -> Self {
+        self.corrections[class.number() as usize] = correction;
+        self
+    }
+
+    /// Get correction of pitch class.
+    #[must_use]
+    pub fn correction(&self, class: PitchClass) -> Cents {
+        self.corrections[class.number() as usize]
+    }
+
+    /// Get corrections of all pitch classes (starting with C).
+    #[must_use]
+    pub fn corrections(&self) -> [Cents; 12] {
+        self.corrections
+    }
+}
+
+impl Default for WindAdjustment {
+    /// Table without corrections.
+    fn default() -> Self {
+        WindAdjustment {
+            corrections: [Cents::new_unchecked(0.0); 12],
+        }
+    }
+}
+
+/// Frequency of pitch shifted by [correction](WindAdjustment::correction) of its pitch class
+/// (for example: about 435 Hz for A₄ with correction of -20 cents).
+#[must_use]
+pub fn apply_wind_adjustment(pitch: Pitch, table: &WindAdjustment) -> f64 {
+    pitch.frequency() * 2.0_f64.powf(table.correction(pitch.class()).value() / 1200.0)
+}
+
+/// Octave stretching (like in piano tuning) - every octave is wider than 2:1
+/// by given number of cents, A₄ keeping its frequency.
+///
+/// It can be combined with any [Temperament]:
+/// `stretch.apply(pitch, temperament.frequency_in(pitch, tonic, &tuning))`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OctaveStretch {
+    cents_per_octave: Cents,
+}
+
+impl OctaveStretch {
+    /// Create new octave stretch.
+    #[must_use]
+    pub fn new(cents_per_octave: Cents) -> Self {
+        OctaveStretch { cents_per_octave }
+    }
+
+    /// Get number of cents each octave is stretched by.
+    #[must_use]
+    pub fn cents_per_octave(&self) -> Cents {
+        self.cents_per_octave
+    }
+
+    /// Deviation of pitch caused by stretching.
+    #[must_use]
+    #[track_caller]
+    pub fn offset(&self, pitch: Pitch) -> Cents {
+        let octaves = (pitch.index() as f64 - 57.0) / 12.0;
+        Cents::new(self.cents_per_octave.value() * octaves).expect("octave stretch is infinite")
+    }
+
+    /// Apply stretching to frequency of pitch.
+    #[must_use]
+    #[track_caller]
+    pub fn apply(&self, pitch: Pitch, frequency: f64) -> f64 {
+        frequency * 2.0_f64.powf(self.offset(pitch).value() / 1200.0)
+    }
+}
+
+/// Meantone temperament - chain of eleven fifths narrowed by given fraction of syntonic comma,
+/// with the remaining "wolf" fifth absorbing the difference.
+///
+/// Chain spans from the minor third to the augmented fifth above tonic
+/// (E♭ to G♯ for tonic C), so the wolf fifth lies between them.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Meantone {
+    comma_fraction: f64,
+}
+
+impl Meantone {
+    /// Create meantone temperament with fifths narrowed by given fraction of syntonic comma.
+    #[must_use]
+    pub fn new(comma_fraction: f64) -> Self {
+        Meantone { comma_fraction }
+    }
+
+    /// Quarter-comma meantone - with pure major thirds.
+    #[must_use]
+    pub fn quarter_comma() -> Self {
+        Meantone::new(0.25)
+    }
+
+    /// Get fraction of syntonic comma every regular fifth is narrowed by.
+    #[must_use]
+    pub fn comma_fraction(&self) -> f64 {
+        self.comma_fraction
+    }
+
+    /// Size of regular (tempered) fifth.
+    #[must_use]
+    #[track_caller]
+    pub fn fifth(&self) -> Interval {
+        let pure = 1200.0 * 1.5_f64.log2();
+        let comma = Interval::syntonic_comma().cents().value();
+        Interval::from_cents(
+            Cents::new(pure - self.comma_fraction * comma)
+                .expect("comma fraction of meantone is not finite"),
+        )
+    }
+
+    /// Size of wolf fifth - the one closing the circle of fifths
+    /// (so twelve fifths add up to exactly seven octaves).
+    #[must_use]
+    #[track_caller]
+    pub fn wolf_fifth(&self) -> Interval {
+        let fifth = self.fifth().cents().value();
+        Interval::from_cents(
+            Cents::new(8400.0 - 11.0 * fifth).expect("comma fraction of meantone is not finite"),
+        )
+    }
+}
+
+impl Temperament for Meantone {
+    fn offset(&self, class: PitchClass, tonic: PitchClass) -> Cents {
+        let fifth = self.fifth().cents().value();
+        let semitones = (class.number() + PitchClass::COUNT - tonic.number()) % PitchClass::COUNT;
+        // position in chain of fifths, from -3 (minor third) to 8 (augmented fifth)
+        let position = (0..PitchClass::COUNT as i32)
+            .map(|position| position - 3)
+            .find(|position| (position * 7).rem_euclid(12) == semitones as i32)
+            .expect("every pitch class is in the chain of fifths");
+        let cents = position as f64 * fifth - 700.0 * position as f64;
+        Cents::new(cents).expect("comma fraction of meantone is not finite")
+    }
+}
+
+/// Werckmeister III well temperament - fifths C-G, G-D, D-A and B-F♯ (counting from tonic C)
+/// narrowed by quarter of Pythagorean comma, the remaining fifths pure.
+///
+/// Major thirds are closest to pure in keys with few accidentals
+/// (C-E and F-A are 390 cents) and widest in remote ones (on C♯, F♯ and G♯ they're 408 cents).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Werckmeister3;
+
+impl Werckmeister3 {
+    /// Position (in cents above tonic) of each degree of chromatic scale.
+    const CENTS: [f64; 12] = [
+        0.0, 90.225, 192.18, 294.135, 390.225, 498.045, 588.27, 696.09, 792.18, 888.27, 996.09,
+        1092.18,
+    ];
+}
+
+impl Temperament for Werckmeister3 {
+    fn offset(&self, class: PitchClass, tonic: PitchClass) -> Cents {
+        let semitones = (class.number() + PitchClass::COUNT - tonic.number()) % PitchClass::COUNT;
+        let cents = Werckmeister3::CENTS[semitones as usize] - 100.0 * semitones as f64;
+        Cents::new_unchecked(cents)
+    }
+}
+
+/// Cents table of equal tuning dividing octave into `divisions` equal steps -
+/// position (in cents above the first degree) of each degree within an octave.
+///
+/// For example: `[0, 100, 200, ..., 1100]` for standard 12-tone equal temperament.
+#[must_use]
+pub fn equal_tuning_cents(divisions: u32) -> Vec<Cents> {
+    (0..divisions)
+        .map(|degree| {
+            Cents::new(1200.0 * degree as f64 / divisions as f64)
+                .expect("positions of degrees are finite")
+        })
+        .collect()
+}
+
+/// Equal division of the octave (EDO) - equal-tempered tuning with any number
+/// of steps per octave (for example: 19 or 31), anchored at reference frequency (step 0).
+///
+/// Edo is a small immutable [Copy] value, safe to share between threads.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Edo {
+    divisions: u32,
+    reference: f64,
+}
+
+impl Edo {
+    /// Create new equal division of the octave into `divisions` steps,
+    /// with step 0 at `reference` frequency.
+    ///
+    /// Fails if number of divisions is zero or reference frequency isn't positive and finite.
+    pub fn new(divisions: u32, reference: f64) -> Result<Self, Error> {
+        if divisions == 0 {
+            return Err(Error::IncorrectDivisions);
+        }
+        if !(reference.is_finite() && reference > 0.0) {
+            return Err(Error::IncorrectFrequency);
+        }
+        Ok(Edo {
+            divisions,
+            reference,
+        })
+    }
+
+    /// Get number of steps per octave.
+    #[must_use]
+    pub fn divisions(&self) -> u32 {
+        self.divisions
+    }
+
+    /// Get reference frequency (frequency of step 0).
+    #[must_use]
+    pub fn reference(&self) -> f64 {
+        self.reference
+    }
+
+    /// Size of a single step.
+    #[must_use]
+    pub fn step(&self) -> Cents {
+        Cents::new_unchecked(1200.0 / self.divisions as f64)
+    }
+
+    /// Frequency of step (negative steps are below reference frequency).
+    #[must_use]
+    pub fn frequency(&self, step: i32) -> f64 {
+        self.reference * 2.0_f64.powf(step as f64 / self.divisions as f64)
+    }
+
+    /// Step nearest to frequency (steps exactly between two steps go to the higher one),
+    /// along with deviation of frequency from it
+    /// (for example: step -1 and about +10 cents for 430 Hz in 24-EDO with reference 440 Hz).
+    ///
+    /// Frequency must be positive, otherwise may panic.
+    #[must_use]
+    #[track_caller]
+    pub fn nearest(&self, frequency: f64) -> (i32, Cents) {
+        let cents = Interval::new(self.reference, frequency).cents().value();
+        let step_cents = self.step().value();
+        let step = (cents / step_cents + 0.5).floor();
+        (step as i32, Cents::new_unchecked(cents - step * step_cents))
+    }
+}
+
+impl Default for Edo {
+    /// Standard 12-tone equal temperament, A₄ = 440 Hz.
+    fn default() -> Self {
+        Edo {
+            divisions: 12,
+            reference: 440.0,
+        }
+    }
+}
+
+/// Per-degree differences between two tuning cents tables - how much each degree
+/// of tuning `b` deviates from the same degree of tuning `a` (`b[i] - a[i]`).
+///
+/// If tables differ in length, extra degrees of the longer one are ignored.
+#[must_use]
+pub fn tuning_difference(a: &[Cents], b: &[Cents]) -> Vec<Cents> {
+    a.iter().zip(b).map(|(a, b)| *b - *a).collect()
+}
+
+/// Intervals between consecutive frequencies of a table (for example: [FREQUENCIES](crate::FREQUENCIES)).
+///
+/// Values of [FREQUENCIES](crate::FREQUENCIES) are rounded to 0.01 Hz, so its steps
+/// deviate from exactly 100 cents - by less than 0.8 cents (mostly in the lowest octave),
+/// and by less than 0.1 cents for most steps.
+///
+/// Frequencies must be positive, otherwise may panic.
+#[must_use]
+#[track_caller]
+pub fn step_cents(table: &[f64]) -> Vec<Cents> {
+    // loop rather than closure, so panics are attributed to caller
+    let mut steps = Vec::with_capacity(table.len().saturating_sub(1));
+    for pair in table.windows(2) {
+        steps.push(Interval::new(pair[0], pair[1]).cents());
+    }
+    steps
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cents(value: f64) -> Cents {
+        Cents::new(value).unwrap()
+    }
+
+    fn assert_close(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-9, "{} != {}", a, b);
+    }
+
+    #[test]
+    fn equal_tuning_compared_with_itself() {
+        let equal = equal_tuning_cents(12);
+        assert_eq!(equal.len(), 12);
+        assert_eq!(equal[7].value(), 700.0);
+        let difference = tuning_difference(&equal, &equal);
+        assert_eq!(difference, vec![cents(0.0); 12]);
+    }
+
+    #[test]
+    fn custom_temperament_round_trips_offsets() {
+        let offsets: [Cents; 12] = std::array::from_fn(|class| cents(class as f64 * 2.5 - 10.0));
+        // A₄, its offset (index 9) is 12.5
+        let temperament = CustomTemperament::from_offsets(offsets, PITCHES[57]).unwrap();
+        assert_eq!(temperament.offsets(), offsets);
+        assert_eq!(temperament.reference(), PITCHES[57]);
+
+        for tuning in [Tuning::default(), Tuning::new(415.0).unwrap()] {
+            for pitch in PITCHES.iter() {
+                let class = pitch.class().number() as usize;
+                let frequency = temperament.frequency_in(*pitch, PitchClass::new(0), &tuning);
+                let deviation = Interval::new(tuning.frequency(*pitch), frequency).cents();
+                // every octave deviates by the same offset, relative to the reference
+                assert_close(deviation.value(), offsets[class].value() - 12.5);
+            }
+            assert_close(
+                temperament.frequency_in(PITCHES[57], PitchClass::new(0), &tuning),
+                tuning.a4(),
+            );
+        }
+    }
+
+    #[test]
+    fn custom_temperament_composes_with_octave_stretch() {
+        let mut offsets = [cents(0.0); 12];
+        offsets[0] = cents(-6.0);
+        let temperament = CustomTemperament::from_offsets(offsets, PITCHES[57]).unwrap();
+        let stretch = OctaveStretch::new(cents(12.0));
+        // C₆, two octaves and a minor third above A₄
+        let pitch = PITCHES[72];
+        let frequency = stretch.apply(pitch, temperament.frequency(pitch, PitchClass::new(0)));
+        let deviation = Interval::new(Tuning::default().frequency(pitch), frequency).cents();
+        assert_close(deviation.value(), -6.0 + 12.0 * 15.0 / 12.0);
+    }
+
+    #[test]
+    fn custom_temperament_offsets_are_bounded() {
+        let mut offsets = [cents(0.0); 12];
+        offsets[4] = cents(-150.0);
+        assert_eq!(
+            CustomTemperament::from_offsets(offsets, PITCHES[57]),
+            Err(Error::IncorrectOffsets)
+        );
+        assert!(
+            CustomTemperament::from_offsets_bounded(offsets, PITCHES[57], cents(200.0)).is_ok()
+        );
+    }
+
+    #[test]
+    fn quarter_comma_wolf_fifth_is_wide() {
+        let meantone = Meantone::quarter_comma();
+        let pure = 1200.0 * 1.5_f64.log2();
+        let fifth = meantone.fifth().cents().value();
+        let wolf = meantone.wolf_fifth().cents().value();
+        assert!((fifth - 696.578).abs() < 0.001, "{}", fifth);
+        assert!((wolf - 737.637).abs() < 0.001, "{}", wolf);
+        assert!(wolf - pure > 35.0);
+        assert_close(11.0 * fifth + wolf, 8400.0);
+
+        // with pure fifths the wolf is narrow by Pythagorean comma
+        let pythagorean = Meantone::new(0.0).wolf_fifth().cents().value();
+        let comma = Interval::pythagorean_comma().cents().value();
+        assert_close(pythagorean, pure - comma);
+    }
+
+    #[test]
+    fn two_point_tuning_curve() {
+        let curve = TuningCurve::from_points(&[(0.0, 430.0), (10.0, 450.0)]).unwrap();
+        assert_eq!(curve.a4_at(0.0), 430.0);
+        assert_eq!(curve.a4_at(-5.0), 430.0);
+        assert_eq!(curve.a4_at(20.0), 450.0);
+        assert_close(curve.a4_at(10.0), 450.0);
+        // geometric midpoint: sqrt(430 * 450), not 440
+        assert_close(curve.a4_at(5.0), (430.0_f64 * 450.0).sqrt());
+        assert_close(curve.a4_at(2.5), 430.0 * (450.0_f64 / 430.0).powf(0.25));
+
+        let (pitch, deviation) = curve.nearest_pitch_at(5.0, (430.0_f64 * 450.0).sqrt());
+        assert_eq!(pitch, PITCHES[57]);
+        assert!(deviation.value().abs() < 1e-6);
+        // 440 Hz is 39.8 cents above A4 at 430 Hz and as much below A4 at 450 Hz
+        let (pitch, deviation) = curve.nearest_pitch_at(0.0, 440.0);
+        assert_eq!(pitch, PITCHES[57]);
+        assert!((deviation.value() - 39.8).abs() < 0.01, "{}", deviation);
+        let (pitch, deviation) = curve.nearest_pitch_at(10.0, 440.0);
+        assert_eq!(pitch, PITCHES[57]);
+        assert!((deviation.value() + 38.9).abs() < 0.01, "{}", deviation);
+    }
+
+    #[test]
+    fn incorrect_tuning_curves() {
+        for points in [
+            &[][..],
+            &[(0.0, 440.0), (0.0, 441.0)],
+            &[(1.0, 440.0), (0.0, 441.0)],
+            &[(0.0, 440.0), (1.0, 0.0)],
+            &[(f64::NAN, 440.0)],
+            &[(0.0, f64::INFINITY)],
+        ] {
+            assert!(TuningCurve::from_points(points).is_err(), "{:?}", points);
+        }
+        let curve = TuningCurve::from_points(&[(3.0, 415.0)]).unwrap();
+        assert_eq!(curve.a4_at(0.0), 415.0);
+        assert_eq!(curve.a4_at(3.0), 415.0);
+        assert_eq!(curve.a4_at(5.0), 415.0);
+    }
+
+    #[test]
+    fn steps_of_rounded_frequencies() {
+        let steps = step_cents(&crate::FREQUENCIES);
+        assert_eq!(steps.len(), 107);
+        let deviations: Vec<f64> = steps
+            .iter()
+            .map(|step| (step.value() - 100.0).abs())
+            .collect();
+        assert!(deviations.iter().all(|deviation| *deviation < 0.8));
+        let small = deviations
+            .iter()
+            .filter(|deviation| **deviation < 0.1)
+            .count();
+        assert!(small > steps.len() / 2, "{}", small);
+        // larger deviations come from the lowest pitches, steps from E2 up are within 0.1 cents
+        assert!(deviations[12..].iter().all(|deviation| *deviation < 0.3));
+        assert!(deviations[28..].iter().all(|deviation| *deviation < 0.1));
+
+        for step in step_cents(&*crate::FREQUENCIES_EXACT) {
+            assert_close(step.value(), 100.0);
+        }
+        assert_eq!(step_cents(&[440.0]), []);
+    }
+
+    #[test]
+    fn werckmeister_thirds_differ_across_keys() {
+        let c = PitchClass::new(0);
+        let third = |root: usize| {
+            let (root, third) = (PITCHES[48 + root], PITCHES[52 + root]);
+            Interval::new(
+                Werckmeister3.frequency(root, c),
+                Werckmeister3.frequency(third, c),
+            )
+            .cents()
+            .value()
+        };
+        let pure = 1200.0 * 1.25_f64.log2();
+        for root in [0, 5] {
+            assert!((third(root) - 390.225).abs() < 1e-6, "{}", root);
+        }
+        for root in [1, 6, 8] {
+            assert!((third(root) - 407.82).abs() < 1e-6, "{}", root);
+        }
+        for root in 0..12 {
+            assert!((390.0..408.0).contains(&third(root)), "{}", root);
+            assert!(third(root) > pure);
+        }
+        // thirds widen with accidentals: on C, G, A and C♯
+        assert!(third(0) < third(7) && third(7) < third(9) && third(9) < third(1));
+
+        // in other keys, the same temperament is laid out from another tonic
+        let g = PitchClass::new(7);
+        let (g4, b4) = (PITCHES[55], PITCHES[59]);
+        let g_third = Interval::new(
+            Werckmeister3.frequency(g4, g),
+            Werckmeister3.frequency(b4, g),
+        );
+        assert!((g_third.cents().value() - 390.225).abs() < 1e-6);
+    }
+
+    #[test]
+    fn snapping_to_quarter_tones() {
+        let edo = Edo::new(24, 440.0).unwrap();
+        assert_eq!(edo.step().value(), 50.0);
+        // A₄ + 50 cents
+        let (step, deviation) = edo.nearest(440.0 * 2.0_f64.powf(1.0 / 24.0));
+        assert_eq!(step, 1);
+        assert!(deviation.value().abs() < 1e-9);
+        let (step, deviation) = edo.nearest(430.0);
+        assert_eq!(step, -1);
+        assert!((deviation.value() - 10.2).abs() < 0.05);
+        // A₅ minus 20 cents
+        let (step, deviation) = edo.nearest(880.0 * 2.0_f64.powf(-20.0 / 1200.0));
+        assert_eq!(step, 24);
+        assert!((deviation.value() + 20.0).abs() < 1e-9);
+
+        let edo = Edo::new(19, 261.63).unwrap();
+        for step in -40..40 {
+            let (nearest, deviation) = edo.nearest(edo.frequency(step));
+            assert_eq!(nearest, step);
+            assert!(deviation.value().abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn incorrect_edos() {
+        assert_eq!(Edo::new(0, 440.0), Err(Error::IncorrectDivisions));
+        assert_eq!(Edo::new(12, 0.0), Err(Error::IncorrectFrequency));
+        assert_eq!(Edo::new(12, f64::INFINITY), Err(Error::IncorrectFrequency));
+    }
+
+    #[test]
+    fn flagged_note_shifts_by_its_correction() {
+        let flat_a = cents(-20.0);
+        let table = WindAdjustment::default().with_correction(PitchClass::new(9), flat_a);
+        assert_eq!(table.correction(PitchClass::new(9)), flat_a);
+        for pitch in PITCHES.iter() {
+            let adjusted = apply_wind_adjustment(*pitch, &table);
+            let shift = Interval::new(pitch.frequency(), adjusted).cents().value();
+            let expected = if pitch.class() == PitchClass::new(9) {
+                -20.0
+            } else {
+                0.0
+            };
+            assert_close(shift, expected);
+        }
+        let a4 = apply_wind_adjustment(PITCHES[57], &table);
+        assert!((a4 - 434.95).abs() < 0.01);
+    }
+}
@@ -0,0 +1,707 @@
+//! Exhaustive consistency checks of conversions between crate types.
+//!
+//! Every function sweeps through all supported values and reports
+//! every violation of the invariant it checks (empty report means success).
+
+use std::fmt::Display;
+
+use crate::{
+    Accidental, ChromaticIndex, Key, KeySignature, KeyTracker, KeyTrackerOptions, Letter, Mode,
+    Note, Octave, Pitch, PitchClass, PitchClassSet, Range, Scale, ScaleType, SpellingCache,
+    SpellingPolicy, PITCHES,
+};
+
+/// Single violation of a checked invariant.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Inconsistency {
+    /// Name of the check that failed.
+    pub check: &'static str,
+    /// Value for which the invariant doesn't hold.
+    pub subject: String,
+    /// Expected result.
+    pub expected: String,
+    /// Actual result.
+    pub actual: String,
+}
+
+impl Display for Inconsistency {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}: for {} expected {}, got {}",
+            self.check, self.subject, self.expected, self.actual
+        )
+    }
+}
+
+fn check<T: PartialEq + std::fmt::Debug>(
+    report: &mut Vec<Inconsistency>,
+    check: &'static str,
+    subject: impl std::fmt::Debug,
+    expected: T,
+    actual: T,
+) {
+    if expected != actual {
+        report.push(Inconsistency {
+            check,
+            subject: format!("{:?}", subject),
+            expected: format!("{:?}", expected),
+            actual: format!("{:?}", actual),
+        });
+    }
+}
+
+/// Run all checks.
+#[must_use]
+pub fn all() -> Vec<Inconsistency> {
+    let mut report = pitch_to_note();
+    report.extend(note_to_pitch());
+    report.extend(spellings());
+    report.extend(relaxed_spellings());
+    report.extend(chromatic_index());
+    report.extend(membership());
+    report.extend(transpositions());
+    report.extend(spelling());
+    report.extend(tuples());
+    report.extend(frequencies());
+    report.extend(monochord());
+    report.extend(edo());
+    report.extend(bark());
+    report.extend(braille());
+    report.extend(parsing());
+    report.extend(note_names());
+    report.extend(pitch_parsing());
+    report.extend(wind_adjustment());
+    report.extend(forte_numbers());
+    report.extend(key_tracking());
+    report
+}
+
+/// Every pitch converted to [Note] and back is the same pitch,
+/// and the note [displayed](std::fmt::Display) parses back to the same note.
+#[must_use]
+pub fn pitch_to_note() -> Vec<Inconsistency> {
+    let mut report = vec![];
+    for pitch in PITCHES.iter() {
+        let note = Note::from(*pitch);
+        check(
+            &mut report,
+            "pitch_to_note",
+            pitch,
+            *pitch,
+            Pitch::from(note),
+        );
+        check(
+            &mut report,
+            "pitch_to_note",
+            pitch,
+            Ok(note),
+            note.to_string().parse(),
+        );
+    }
+    report
+}
+
+/// Every valid note within the pitch table converted to [Pitch] and back
+/// is the same note or its [enharmonic](Note::enharmonic).
+#[must_use]
+pub fn note_to_pitch() -> Vec<Inconsistency> {
+    let mut report = vec![];
+    for note in all_notes() {
+        if let Ok(pitch) = note.chromatic_index().try_into_pitch() {
+            let back = Note::from(pitch);
+            let back = if back == note {
+                back
+            } else {
+                back.enharmonic()
+            };
+            check(&mut report, "note_to_pitch", note, note, back);
+        }
+    }
+    report
+}
+
+/// Every [spelling](Pitch::spellings) of a pitch converts back to that pitch,
+/// and spellings are exactly the valid notes having that pitch.
+#[must_use]
+pub fn spellings() -> Vec<Inconsistency> {
+    let mut report = vec![];
+    for pitch in PITCHES.iter() {
+        let spellings = pitch.spellings();
+        for note in &spellings {
+            check(&mut report, "spellings", note, *pitch, Pitch::from(*note));
+        }
+
+        let mut expected: Vec<Note> = all_notes()
+            .filter(|note| note.chromatic_index() == ChromaticIndex::from(*pitch))
+            .collect();
+        let mut actual = spellings;
+        expected.sort_by_key(|note| format!("{:?}", note));
+        actual.sort_by_key(|note| format!("{:?}", note));
+        check(&mut report, "spellings", pitch, expected, actual);
+    }
+    report
+}
+
+/// Every pitch converted to [ChromaticIndex] and back is the same pitch.
+#[must_use]
+pub fn chromatic_index() -> Vec<Inconsistency> {
+    let mut report = vec![];
+    for pitch in PITCHES.iter() {
+        let index = ChromaticIndex::from(*pitch);
+        check(
+            &mut report,
+            "chromatic_index",
+            pitch,
+            Ok(*pitch),
+            index.try_into_pitch(),
+        );
+    }
+    report
+}
+
+/// Fast membership checks of [Scale] and [Range] agree with regular ones
+/// for every pitch (checked for every scale type and root and every range).
+#[must_use]
+pub fn membership() -> Vec<Inconsistency> {
+    let mut report = vec![];
+    for scale_type in ScaleType::ALL {
+        for root in 0..PitchClass::COUNT {
+            let scale = Scale::new(PitchClass::new(root), scale_type);
+            let mask = scale.membership_mask();
+            for pitch in PITCHES.iter() {
+                check(
+                    &mut report,
+                    "membership",
+                    (scale, pitch),
+                    scale.contains(*pitch),
+                    Scale::contains_fast(mask, *pitch),
+                );
+            }
+        }
+    }
+    for low in PITCHES.iter() {
+        for high in PITCHES[low.index() as usize..].iter() {
+            let range = Range::new(*low, *high).unwrap();
+            let bitset = range.membership_bitset();
+            for pitch in PITCHES.iter() {
+                check(
+                    &mut report,
+                    "membership",
+                    (range, pitch),
+                    range.contains(*pitch),
+                    Range::contains_fast(bitset, *pitch),
+                );
+            }
+        }
+    }
+    report
+}
+
+/// Transposing any predefined scale by its [transposition count](Scale::transposition_count)
+/// maps it onto itself, and no smaller transposition does.
+#[must_use]
+pub fn transpositions() -> Vec<Inconsistency> {
+    let mut report = vec![];
+    for scale_type in ScaleType::ALL {
+        for root in 0..PitchClass::COUNT {
+            let scale = Scale::new(PitchClass::new(root), scale_type);
+            let classes = scale.pitch_classes();
+            let expected = (1..=PitchClass::COUNT)
+                .find(|semitones| classes.transpose(*semitones) == classes)
+                .unwrap();
+            check(
+                &mut report,
+                "transpositions",
+                scale,
+                expected,
+                scale.transposition_count(),
+            );
+        }
+    }
+    report
+}
+
+/// Every pitch is spelled by every [SpellingPolicy] as note with the same pitch,
+/// and [SpellingCache] returns the same spelling and its name.
+#[must_use]
+pub fn spelling() -> Vec<Inconsistency> {
+    let mut report = vec![];
+    let policies = [SpellingPolicy::Sharps, SpellingPolicy::Flats]
+        .into_iter()
+        .chain((-7..=7).map(|fifths| SpellingPolicy::ForKey(KeySignature::new(fifths).unwrap())));
+    for policy in policies {
+        let cache = SpellingCache::new(policy);
+        for pitch in PITCHES.iter() {
+            let note = policy.spell(*pitch);
+            check(
+                &mut report,
+                "spelling",
+                (policy, pitch),
+                *pitch,
+                Pitch::from(note),
+            );
+            check(
+                &mut report,
+                "spelling",
+                (policy, pitch),
+                (note, note.to_string()),
+                (cache.get(*pitch), cache.name(*pitch).to_string()),
+            );
+        }
+    }
+    for pitch in PITCHES.iter() {
+        check(
+            &mut report,
+            "spelling",
+            pitch,
+            Note::from(*pitch),
+            SpellingPolicy::Sharps.spell(*pitch),
+        );
+    }
+    report
+}
+
+/// Every note and pitch converted to its stable numeric representation and back
+/// is the same note or pitch, and no other representation converts successfully.
+#[must_use]
+pub fn tuples() -> Vec<Inconsistency> {
+    let mut report = vec![];
+    for note in all_notes() {
+        let tuple: (char, i8, u8) = note.into();
+        check(&mut report, "tuples", note, Ok(note), Note::try_from(tuple));
+    }
+    for letter in ['C', 'D', 'E', 'F', 'G', 'A', 'B'] {
+        for alteration in -2..=2 {
+            for octave in 0..=10 {
+                let tuple = (letter, alteration, octave);
+                if let Ok(note) = Note::try_from(tuple) {
+                    check(&mut report, "tuples", tuple, tuple, note.into());
+                }
+            }
+        }
+    }
+    for index in 0..=u8::MAX {
+        match Pitch::try_from(index) {
+            Ok(pitch) => check(&mut report, "tuples", index, index, pitch.into()),
+            Err(_) => check(
+                &mut report,
+                "tuples",
+                index,
+                false,
+                (index as usize) < PITCHES.len(),
+            ),
+        }
+    }
+    report
+}
+
+/// Every spelling (including [theoretical](Note::new_unchecked_spelling) ones)
+/// of a note that has a pitch round-trips through [Pitch], keeps its pitch
+/// when [simplified](Note::simplify) or [respelled](Note::enharmonic)
+/// and round-trips through its name.
+#[must_use]
+pub fn relaxed_spellings() -> Vec<Inconsistency> {
+    let mut report = vec![];
+    for octave in 0..10u8 {
+        let octave = Octave::try_from(octave).expect("invariant");
+        for letter in Letter::ALL {
+            for accidental in [
+                Accidental::None,
+                Accidental::Natural,
+                Accidental::Sharp,
+                Accidental::Flat,
+            ] {
+                let note = Note::new_unchecked_spelling(letter, octave, accidental);
+                let index = note.chromatic_index();
+                check(
+                    &mut report,
+                    "relaxed_spellings",
+                    note,
+                    Ok(note),
+                    crate::parse_note(&note.to_string()),
+                );
+                if let Ok(pitch) = index.try_into_pitch() {
+                    check(
+                        &mut report,
+                        "relaxed_spellings",
+                        note,
+                        index,
+                        Pitch::from(note).into(),
+                    );
+                    check(
+                        &mut report,
+                        "relaxed_spellings",
+                        note,
+                        pitch,
+                        Pitch::from(note.simplify()),
+                    );
+                    check(
+                        &mut report,
+                        "relaxed_spellings",
+                        note,
+                        pitch,
+                        Pitch::from(note.enharmonic()),
+                    );
+                }
+            }
+        }
+    }
+    report
+}
+
+/// Every value of [FREQUENCIES](crate::FREQUENCIES) is the [exact](crate::FREQUENCIES_EXACT)
+/// frequency rounded to 0.01 Hz - it differs from it by at most half of 0.01 Hz.
+#[must_use]
+pub fn frequencies() -> Vec<Inconsistency> {
+    const TOLERANCE: f64 = 0.005 + 1e-9;
+    crate::FREQUENCIES
+        .iter()
+        .zip(crate::FREQUENCIES_EXACT.iter())
+        .zip(PITCHES.iter())
+        .filter(|((published, exact), _)| (*published - *exact).abs() > TOLERANCE)
+        .map(|((published, exact), pitch)| Inconsistency {
+            check: "frequencies",
+            subject: format!("{:?}", pitch),
+            expected: format!("{:.2} (exact {})", exact, exact),
+            actual: format!("{}", published),
+        })
+        .collect()
+}
+
+/// String of every pitch [divided](crate::monochord_frequency) at 1/2 sounds an octave higher,
+/// divided at 2/3 - a just fifth (about 701.955 cents) higher.
+#[must_use]
+pub fn monochord() -> Vec<Inconsistency> {
+    let mut report = vec![];
+    for pitch in PITCHES.iter() {
+        let open = pitch.frequency();
+        check(
+            &mut report,
+            "monochord",
+            pitch,
+            2.0 * open,
+            crate::monochord_frequency(open, 1.0 / 2.0),
+        );
+        let fifth = crate::Interval::new(open, crate::monochord_frequency(open, 2.0 / 3.0));
+        check(
+            &mut report,
+            "monochord",
+            pitch,
+            "701.955".to_string(),
+            format!("{:.3}", fifth.cents().value()),
+        );
+    }
+    report
+}
+
+/// Exact frequency of every pitch, and frequency a quarter-tone above it,
+/// snap to the right [step](crate::Edo::nearest) of 24-EDO (A₄ = 440 Hz) with no deviation.
+#[must_use]
+pub fn edo() -> Vec<Inconsistency> {
+    let mut report = vec![];
+    let edo = crate::Edo::new(24, 440.0).unwrap();
+    for (pitch, frequency) in PITCHES.iter().zip(crate::FREQUENCIES_EXACT.iter()) {
+        let step = 2 * (pitch.index() as i32 - 57);
+        let quarter_tone = frequency * 2.0_f64.powf(1.0 / 24.0);
+        for (step, frequency) in [(step, *frequency), (step + 1, quarter_tone)] {
+            let (actual, cents) = edo.nearest(frequency);
+            check(
+                &mut report,
+                "edo",
+                frequency,
+                (step, "0.000".to_string()),
+                (actual, format!("{:.3}", cents.value().abs())),
+            );
+        }
+    }
+    report
+}
+
+/// Frequencies of known points of Bark scale (1000 Hz at about 8.53 Bark, 4000 Hz at about 17.46 Bark)
+/// match, and frequency of every pitch [converted](crate::frequency_to_bark) to Bark scale and back
+/// is the same frequency.
+#[must_use]
+pub fn bark() -> Vec<Inconsistency> {
+    let mut report = vec![];
+    for (frequency, bark) in [(1000.0, "8.53"), (4000.0, "17.46")] {
+        check(
+            &mut report,
+            "bark",
+            frequency,
+            bark.to_string(),
+            format!("{:.2}", crate::frequency_to_bark(frequency)),
+        );
+    }
+    for pitch in PITCHES.iter() {
+        let frequency = pitch.frequency();
+        let round_trip = crate::bark_to_frequency(crate::frequency_to_bark(frequency));
+        check(
+            &mut report,
+            "bark",
+            pitch,
+            format!("{:.6}", frequency),
+            format!("{:.6}", round_trip),
+        );
+    }
+    report
+}
+
+/// Notes written in [Braille music notation](Note::to_braille_with) match published examples
+/// of Braille music code.
+#[must_use]
+pub fn braille() -> Vec<Inconsistency> {
+    let mut report = vec![];
+    for (letter, octave, accidental, beats, expected) in [
+        (Letter::C, 4, Accidental::None, 1.0, "⠐⠹"),
+        (Letter::F, 5, Accidental::Sharp, 2.0, "⠩⠨⠟"),
+        (Letter::B, 3, Accidental::Flat, 0.5, "⠣⠸⠚"),
+        (Letter::D, 4, Accidental::None, 1.5, "⠐⠱⠄"),
+        (Letter::A, 1, Accidental::Natural, 4.0, "⠡⠈⠮"),
+        (Letter::G, 6, Accidental::None, 0.25, "⠰⠷"),
+    ] {
+        let note = Note::new(letter, Octave::try_from(octave).unwrap(), accidental).unwrap();
+        check(
+            &mut report,
+            "braille",
+            note,
+            Some(expected.to_string()),
+            note.to_braille_with(crate::Duration::new(beats).unwrap()),
+        );
+    }
+    report
+}
+
+/// Note names with ASCII and Unicode accidentals and octave numbers parse
+/// to expected notes, and malformed names are rejected with expected errors.
+#[must_use]
+pub fn parsing() -> Vec<Inconsistency> {
+    let mut report = vec![];
+    let note = |letter, octave: u8, accidental| {
+        Note::new(letter, Octave::try_from(octave).unwrap(), accidental).unwrap()
+    };
+    for (name, expected) in [
+        ("C#4", Ok(note(Letter::C, 4, Accidental::Sharp))),
+        ("Db5", Ok(note(Letter::D, 5, Accidental::Flat))),
+        ("A4", Ok(note(Letter::A, 4, Accidental::None))),
+        ("C♯₄", Ok(note(Letter::C, 4, Accidental::Sharp))),
+        ("B♭₂", Ok(note(Letter::B, 2, Accidental::Flat))),
+        ("G♮7", Ok(note(Letter::G, 7, Accidental::Natural))),
+        ("E#4", Err(crate::Error::IncorrectAccidental)),
+        ("Cb4", Err(crate::Error::IncorrectAccidental)),
+        ("H4", Err(crate::Error::IncorrectLetter)),
+        ("", Err(crate::Error::IncorrectLetter)),
+        ("C", Err(crate::Error::OctaveNotInRange)),
+        ("C#", Err(crate::Error::OctaveNotInRange)),
+        ("C10", Err(crate::Error::OctaveNotInRange)),
+    ] {
+        check(&mut report, "parsing", name, expected, name.parse::<Note>());
+    }
+    report
+}
+
+/// Every note (with common spelling) formatted in every [style](crate::NoteStyle),
+/// also with lowercase letter, parses back to the same note.
+#[must_use]
+pub fn note_names() -> Vec<Inconsistency> {
+    let mut report = vec![];
+    let naturals = all_notes()
+        .filter(|note| note.accidental() == Accidental::None)
+        .map(|note| {
+            Note::new(note.letter(), note.octave(), Accidental::Natural).expect("invariant")
+        });
+    for note in all_notes().chain(naturals) {
+        let unicode = note.to_string();
+        let ascii = note.format_in(crate::NoteStyle::Ascii).into_owned();
+        let lowercase = ascii[..1].to_lowercase() + &ascii[1..];
+        for name in [unicode, ascii, lowercase] {
+            check(
+                &mut report,
+                "note_names",
+                &name,
+                Ok(note),
+                Note::try_from(name.as_str()),
+            );
+        }
+    }
+    report
+}
+
+/// Every pitch [displayed](std::fmt::Display) as its frequency and named by its note
+/// parses back to the same pitch, and frequencies outside of the table are rejected.
+#[must_use]
+pub fn pitch_parsing() -> Vec<Inconsistency> {
+    let mut report = vec![];
+    for pitch in PITCHES.iter() {
+        for name in [pitch.to_string(), Note::from(*pitch).to_string()] {
+            check(
+                &mut report,
+                "pitch_parsing",
+                &name,
+                Ok(*pitch),
+                Pitch::try_from(name.as_str()),
+            );
+        }
+    }
+    for (name, expected) in [
+        ("A4", Ok(PITCHES[57])),
+        ("F#2", Ok(PITCHES[30])),
+        ("16.35", Ok(PITCHES[0])),
+        ("7902.13", Ok(PITCHES[107])),
+        ("20000", Err(crate::Error::FrequencyNotInTable)),
+        ("445", Err(crate::Error::FrequencyNotInTable)),
+        ("4.4.0", Err(crate::Error::IncorrectFrequency)),
+        ("C9", Err(crate::Error::PitchNotInRange)),
+    ] {
+        check(&mut report, "pitch_parsing", name, expected, name.parse());
+    }
+    report
+}
+
+/// Every pitch of pitch class with [wind adjustment](crate::WindAdjustment) is shifted
+/// by its correction, other pitches keep their frequencies.
+#[must_use]
+pub fn wind_adjustment() -> Vec<Inconsistency> {
+    let mut report = vec![];
+    let sharp_d = crate::Cents::new(-15.0).unwrap();
+    let table = crate::WindAdjustment::default().with_correction(PitchClass::new(2), sharp_d);
+    for pitch in PITCHES.iter() {
+        let adjusted = crate::apply_wind_adjustment(*pitch, &table);
+        let expected = if pitch.class() == PitchClass::new(2) {
+            -15.0
+        } else {
+            0.0
+        };
+        check(
+            &mut report,
+            "wind_adjustment",
+            pitch,
+            format!("{:.6}", expected),
+            format!(
+                "{:.6}",
+                crate::Interval::new(pitch.frequency(), adjusted)
+                    .cents()
+                    .value()
+            ),
+        );
+    }
+    report
+}
+
+/// Every one of 224 set classes has distinct [Forte number](crate::ForteNumber),
+/// Z-related set classes are [partners](PitchClassSet::z_partner) of each other,
+/// and published entries (like 4-Z29 and 6-Z44) match.
+#[must_use]
+pub fn forte_numbers() -> Vec<Inconsistency> {
+    let mut report = vec![];
+    let mut numbers = vec![];
+    for mask in 0..1 << PitchClass::COUNT {
+        let set = PitchClassSet::from_mask(mask);
+        let number = set.forte_number();
+        if !numbers.contains(&number) {
+            numbers.push(number);
+        }
+        check(
+            &mut report,
+            "forte_numbers",
+            set,
+            number.is_z(),
+            set.z_partner().is_some(),
+        );
+        if let Some(partner) = set.z_partner() {
+            check(
+                &mut report,
+                "forte_numbers",
+                set,
+                Some(set.prime_form()),
+                partner.z_partner(),
+            );
+        }
+    }
+    check(
+        &mut report,
+        "forte_numbers",
+        "set classes",
+        224,
+        numbers.len(),
+    );
+    for (classes, expected) in [
+        (&[0, 4, 7][..], "3-11"),
+        (&[0, 1, 4, 6], "4-Z15"),
+        (&[0, 1, 3, 7], "4-Z29"),
+        (&[0, 1, 2, 5, 6, 9], "6-Z44"),
+        (&[0, 2, 4, 5, 7, 9, 11], "7-35"),
+    ] {
+        let set: PitchClassSet = classes
+            .iter()
+            .map(|class| PitchClass::new(*class))
+            .collect();
+        check(
+            &mut report,
+            "forte_numbers",
+            set,
+            expected.to_string(),
+            set.forte_number().to_string(),
+        );
+    }
+    report
+}
+
+/// Every one of 24 keys is [tracked](crate::KeyTracker) from its tonic triad
+/// (with the fewest sharps or flats), and scripted stream modulating from C major to G major
+/// switches the estimate exactly once, at 14th pitch of G major.
+#[must_use]
+pub fn key_tracking() -> Vec<Inconsistency> {
+    let mut report = vec![];
+    let pitch = |midi: u8| PITCHES[midi as usize - 12];
+    for class in 0..PitchClass::COUNT {
+        for (mode, third) in [(Mode::Major, 4), (Mode::Minor, 3)] {
+            let mut tracker = KeyTracker::new(KeyTrackerOptions {
+                min_evidence: 0.5,
+                ..Default::default()
+            });
+            for _ in 0..4 {
+                for degree in [0, third, 7, 0] {
+                    tracker.push(pitch(60 + (class + degree) % 12), 1.0);
+                }
+            }
+            check(
+                &mut report,
+                "key_tracking",
+                (class, mode),
+                Key::spellings_for_pitch_class(PitchClass::new(class), mode).first(),
+                tracker.current_estimate().map(|(key, _)| key).as_ref(),
+            );
+        }
+    }
+
+    let c_major = [60, 62, 64, 65, 67, 69, 71, 72, 67, 64, 60];
+    let g_major = [67, 69, 71, 72, 74, 76, 78, 79, 74, 71, 67];
+    let mut tracker = KeyTracker::default();
+    let mut estimates = vec![];
+    for midi in c_major.repeat(4).into_iter().chain(g_major.repeat(4)) {
+        tracker.push(pitch(midi), 1.0);
+        estimates.push(tracker.current_estimate().map(|(key, _)| key.to_string()));
+    }
+    let switch = c_major.len() * 4 + 13;
+    for (i, estimate) in estimates.into_iter().enumerate() {
+        let expected = match i {
+            0..=3 => None,
+            i if i < switch => Some("C major".to_string()),
+            _ => Some("G major".to_string()),
+        };
+        check(&mut report, "key_tracking", i, expected, estimate);
+    }
+    report
+}
+
+fn all_notes() -> impl Iterator<Item = Note> {
+    (0..10u8).flat_map(|octave| {
+        let octave = Octave::try_from(octave).unwrap();
+        Letter::ALL.into_iter().flat_map(move |letter| {
+            [Accidental::None, Accidental::Sharp, Accidental::Flat]
+                .into_iter()
+                .filter_map(move |accidental| Note::new(letter, octave, accidental).ok())
+        })
+    })
+}
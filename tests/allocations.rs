@@ -0,0 +1,94 @@
+//! Formatting notes and pitches in default style doesn't allocate.
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    borrow::Cow,
+    cell::Cell,
+    fmt::Write,
+};
+
+use pitches::{Note, NoteStyle, SpellingCache, SpellingPolicy, PITCHES};
+
+/// Allocator counting allocations made by current thread.
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|count| count.set(count.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Number of allocations made by `f`.
+fn allocations(f: impl FnOnce()) -> usize {
+    let before = ALLOCATIONS.with(Cell::get);
+    f();
+    ALLOCATIONS.with(Cell::get) - before
+}
+
+/// Fixed-capacity buffer, so writing to it doesn't allocate.
+struct Buffer {
+    bytes: [u8; 64],
+    len: usize,
+}
+
+impl Write for Buffer {
+    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        let end = self.len + s.len();
+        self.bytes
+            .get_mut(self.len..end)
+            .ok_or(std::fmt::Error)?
+            .copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
+
+#[test]
+fn default_style_does_not_allocate() {
+    let cache = SpellingCache::new(SpellingPolicy::Flats);
+    cache.warm_up();
+    // initialize lazily computed tables
+    let _ = PITCHES[0].name();
+
+    let mut total = 0;
+    let count = allocations(|| {
+        for pitch in PITCHES.iter() {
+            let note = Note::from(*pitch);
+            for name in [
+                pitch.name(),
+                note.format_in(NoteStyle::Unicode),
+                Cow::Borrowed(cache.name(*pitch)),
+            ] {
+                assert!(matches!(name, Cow::Borrowed(_)));
+                total += name.len();
+            }
+            let mut buffer = Buffer {
+                bytes: [0; 64],
+                len: 0,
+            };
+            write!(buffer, "{}", note).unwrap();
+            total += buffer.len;
+        }
+    });
+    assert_eq!(count, 0);
+    assert!(total > 0);
+
+    // allocations are really counted
+    let count = allocations(|| {
+        let name = PITCHES[57].name().into_owned();
+        assert_eq!(name, "A₄");
+    });
+    assert_eq!(count, 1);
+}
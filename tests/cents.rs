@@ -0,0 +1,53 @@
+//! Everything `Cents` offers is usable without depending on `ordered_float`.
+
+use pitches::{Cents, Interval};
+
+#[test]
+fn construction_and_value() {
+    let cents = Cents::new(-12.5).unwrap();
+    assert_eq!(cents.value(), -12.5);
+    assert_eq!(cents.abs().value(), 12.5);
+    assert_eq!(Interval::from_cents(cents).cents(), cents);
+}
+
+#[test]
+fn non_finite_values_are_rejected() {
+    assert_eq!(Cents::new(f64::NAN), None);
+    assert_eq!(Cents::new(f64::INFINITY), None);
+    assert_eq!(Cents::new(f64::NEG_INFINITY), None);
+}
+
+#[test]
+fn arithmetic() {
+    let a = Cents::new(700.0).unwrap();
+    let b = Cents::new(400.0).unwrap();
+    assert_eq!((a + b).value(), 1100.0);
+    assert_eq!((a - b).value(), 300.0);
+}
+
+#[test]
+fn arithmetic_saturates() {
+    let max = Cents::new(f64::MAX).unwrap();
+    let min = Cents::new(f64::MIN).unwrap();
+    assert_eq!(max + max, max);
+    assert_eq!(min + min, min);
+    assert_eq!(max - min, max);
+    assert_eq!(min - max, min);
+    assert_eq!((max + min).value(), 0.0);
+}
+
+#[test]
+fn ordering_and_formatting() {
+    let mut cents: Vec<Cents> = [3.0, -1.0, 2.0]
+        .into_iter()
+        .map(|value| Cents::new(value).unwrap())
+        .collect();
+    cents.sort();
+    assert_eq!(
+        cents.iter().map(|cents| cents.value()).collect::<Vec<_>>(),
+        [-1.0, 2.0, 3.0]
+    );
+    assert_eq!(cents.iter().max(), Some(&Cents::new(3.0).unwrap()));
+    assert_eq!(Cents::new(2.5).unwrap().to_string(), "2.5");
+    assert!(!format!("{:?}", Cents::new(2.5).unwrap()).is_empty());
+}
@@ -0,0 +1,77 @@
+//! Runs examples on inputs from `examples/fixtures` and compares their output
+//! with expected one stored next to the inputs.
+
+use std::{
+    io::Write,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+};
+
+/// Path of built example - examples are built next to the directory of test binaries.
+fn example(name: &str) -> PathBuf {
+    let test = std::env::current_exe().expect("path of test binary");
+    let directory = test
+        .parent()
+        .and_then(Path::parent)
+        .expect("target directory");
+    let path = directory
+        .join("examples")
+        .join(name)
+        .with_extension(std::env::consts::EXE_EXTENSION);
+    assert!(
+        path.exists(),
+        "example {} is not built (run `cargo build --examples`)",
+        name
+    );
+    path
+}
+
+fn fixture(name: &str) -> String {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("examples/fixtures")
+        .join(name);
+    std::fs::read_to_string(&path).unwrap_or_else(|_| panic!("missing fixture {}", name))
+}
+
+/// Run example with arguments and input from fixture (if any), compare its output with fixture.
+fn check(name: &str, arguments: &[&str], input: Option<&str>, expected: &str) {
+    let mut child = Command::new(example(name))
+        .args(arguments)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .expect("example runs");
+    if let Some(input) = input {
+        child
+            .stdin
+            .take()
+            .expect("stdin")
+            .write_all(fixture(input).as_bytes())
+            .expect("input is written");
+    }
+    let output = child.wait_with_output().expect("example finishes");
+    assert!(output.status.success(), "example {} failed", name);
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), fixture(expected));
+}
+
+#[test]
+fn tuner() {
+    check("tuner", &[], Some("tuner.in"), "tuner.out");
+}
+
+#[test]
+fn transpose() {
+    check("transpose", &["M3"], Some("transpose.in"), "transpose.out");
+}
+
+#[test]
+fn chords() {
+    let symbols = ["Cmaj7", "F#m", "Bb7", "Eø7", "C°7"];
+    check("chords", &symbols, None, "chords.out");
+}
+
+#[test]
+fn scales() {
+    check("scales", &["D", "Dorian"], None, "scales.out");
+}
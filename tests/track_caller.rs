@@ -0,0 +1,65 @@
+//! Panics of `#[track_caller]` functions are attributed to their callers.
+
+use std::{
+    panic::{self, Location},
+    sync::Mutex,
+};
+
+use pitches::*;
+
+static LAST_PANIC: Mutex<Option<(String, String, u32)>> = Mutex::new(None);
+
+/// Run `f`, returning message, file and line of its panic.
+fn catch(f: impl FnOnce() + panic::UnwindSafe) -> (String, String, u32) {
+    panic::set_hook(Box::new(|info| {
+        let location = info.location().unwrap_or(Location::caller());
+        let message = info
+            .payload()
+            .downcast_ref::<&str>()
+            .map(|message| message.to_string())
+            .or_else(|| info.payload().downcast_ref::<String>().cloned())
+            .unwrap_or_default();
+        *LAST_PANIC.lock().unwrap() = Some((message, location.file().to_string(), location.line()));
+    }));
+    let result = panic::catch_unwind(f);
+    let _ = panic::take_hook();
+    assert!(result.is_err(), "no panic");
+    LAST_PANIC
+        .lock()
+        .unwrap()
+        .take()
+        .expect("panic not recorded")
+}
+
+macro_rules! assert_panics_here {
+    ($message:expr, $call:expr) => {{
+        let (message, file, line) = catch(|| {
+            let _ = $call;
+        });
+        assert_eq!(message, $message);
+        assert_eq!((file.as_str(), line), (file!(), line!()));
+    }};
+}
+
+#[test]
+fn panics_are_attributed_to_callers() {
+    let not_finite = "interval between frequencies is not finite";
+    let no_pitch = "note has no corresponding pitch: PitchNotInRange";
+    let b9 = Note::new(Letter::B, Octave::Tenth, Accidental::None).unwrap();
+    let a4 = PITCHES[57];
+
+    assert_panics_here!(not_finite, Interval::new(0.0, 440.0));
+    assert_panics_here!(not_finite, Tuning::default().cents_from_a4(0.0));
+    assert_panics_here!(not_finite, TunerReading::new(a4, 0.0));
+    assert_panics_here!(not_finite, step_cents(&[440.0, 0.0]));
+    assert_panics_here!(not_finite, Edo::default().nearest(0.0));
+    let curve = TuningCurve::from_points(&[(0.0, 440.0)]).unwrap();
+    assert_panics_here!(not_finite, curve.nearest_pitch_at(0.0, 0.0));
+    assert_panics_here!(no_pitch, Pitch::from(b9));
+    let meantone = Meantone::new(f64::INFINITY);
+    let message = "comma fraction of meantone is not finite";
+    assert_panics_here!(message, meantone.fifth());
+    assert_panics_here!(message, meantone.wolf_fifth());
+    let stretch = OctaveStretch::new(Cents::new(f64::MAX).unwrap());
+    assert_panics_here!("octave stretch is infinite", stretch.offset(PITCHES[107]));
+}
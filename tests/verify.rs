@@ -0,0 +1,10 @@
+use pitches::verify;
+
+#[test]
+fn consistent() {
+    let report = verify::all();
+    for inconsistency in &report {
+        eprintln!("{}", inconsistency);
+    }
+    assert!(report.is_empty(), "{} inconsistencies found", report.len());
+}